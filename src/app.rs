@@ -1,15 +1,37 @@
 /// Main application component: AppModel (state), AppMsg (messages), AppWidgets (UI references).
-use crate::config::{Theme, UserSettings};
+use crate::config::{MeasurementSystem, Theme, UserSettings};
 use crate::ui_constants::*;
-use crate::dialogs::IngredientInfo;
+use crate::cooking::{remaining_after, Consume, CookingLog};
+use crate::dialogs::{IngredientInfo, PantryExtras};
+use crate::history::{Change, History, Snapshot};
+use crate::ingredient_file::{ingredient_path, read_extra_keys, write_with_extra_keys};
+use crate::low_stock::Threshold;
+use crate::meal_plan::{week_start, Meal, MealPlan};
+use crate::pantry_details::PantryDetails;
+use crate::pantry_io::{ImportRow, PantryRow, RowStatus};
+use crate::print::PrintBlock;
+use crate::recipe_filter::{FilterChange, RecipeFilter};
+use crate::recipe_import::ImportedRecipe;
+use crate::recipes::RecipeSort;
+use crate::shopping_list::ShoppingList;
+use crate::toasts::{error_chain, ToastRequest};
+use crate::units::IngredientUnits;
+use crate::watcher::{ChangeSummary, DataDirWatcher};
+use chrono::{Days, NaiveDate};
 use janus_engine::{fold_for_matching, DataManager, Ingredient, Recipe};
 use libadwaita as adw;
 use relm4::gtk;
 use relm4::{gtk::prelude::*, ComponentParts, ComponentSender, SimpleComponent};
 use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long after one of our own writes file-system events are attributed to us
+/// rather than to another device.
+const OWN_WRITE_GRACE: Duration = Duration::from_secs(2);
 
 // ── Tab enum ──────────────────────────────────────────────────────────────────
 
@@ -17,6 +39,8 @@ use std::sync::mpsc;
 pub enum Tab {
     Recipes,
     Pantry,
+    Planner,
+    Shopping,
     Settings,
 }
 
@@ -32,13 +56,48 @@ pub enum AppMsg {
     SelectRecipe(Option<String>),
     AddRecipe,
     EditRecipe(String),
+    ShowImportRecipe,
+    /// Import a recipe from an HTML page or JSON-LD; `source` is the file it was read from.
+    ImportRecipe { text: String, source: Option<PathBuf> },
+    /// Import recipe files: Cooklang files are added directly, web pages go
+    /// through `ImportRecipe`.
+    ImportRecipeFiles(Vec<PathBuf>),
+    ExportRecipe(String),
+    ExportRecipePdf(String),
+    PrintRecipe(String),
+    ShowExportCookbook,
+    /// Lay out a PDF cookbook of the recipes with this tag (all when `None`).
+    ExportCookbook(Option<String>),
+    /// Create bare ingredients with these names (for unmatched imported ones).
+    CreateIngredients(Vec<String>),
     DeleteRecipe(String),
-    SaveRecipe { original: Option<String>, recipe: Recipe },
+    /// `new_image` is a picked image file to copy into the image folder.
+    SaveRecipe { original: Option<String>, recipe: Recipe, new_image: Option<PathBuf> },
+    SetRecipeServings { title: String, servings: u32 },
+    SetRecipeSort(RecipeSort),
+    ToggleRecipeGrouping(bool),
+    UpdateRecipeFilter(FilterChange),
+    CookRecipe(String),
+    ConfirmCooked { title: String, servings: Option<u32>, consumed: Vec<(String, Consume)> },
+    ShowCookingHistory,
+    StartCookMode(String),
+    /// Recipe image thumbnails finished generating in the background.
+    ThumbnailsReady,
 
     // Pantry
     SearchIngredients(String),
     SelectIngredient(Option<String>),
+    /// Open the Pantry tab on an ingredient (from a link in a recipe).
+    ShowIngredient(String),
     ToggleInStockOnly(bool),
+    SetCategoryFilter { category: String, selected: bool },
+    SetTagFilter { tag: String, selected: bool },
+    ClearPantryFilters,
+    ToggleExpiringOnly(bool),
+    /// Group the pantry list by storage location instead of category.
+    ToggleGroupByLocation(bool),
+    EditStorageLocations,
+    SaveStorageLocations(Vec<String>),
     AddIngredient,
     EditIngredient(String),
     DeleteIngredient(String),
@@ -48,16 +107,54 @@ pub enum AppMsg {
         in_pantry: bool,
         qty: Option<f64>,
         qty_type: String,
+        best_before: Option<NaiveDate>,
+        location: Option<String>,
+        threshold: Option<Threshold>,
     },
+    /// Put these running-low ingredients on the shopping list.
+    RestockIngredients(Vec<String>),
+    ShowImportPantry,
+    /// Check a CSV or JSON pantry file read from `path` before importing it.
+    PreviewPantryImport { path: PathBuf, text: String },
+    /// Write reviewed import rows; `replace` overwrites items already in the pantry.
+    ImportPantry { rows: Vec<ImportRow>, replace: bool },
+    ExportPantry,
+
+    // Planner
+    PlanRecipe { date: NaiveDate, meal: Meal, title: String },
+    UnplanRecipe { date: NaiveDate, meal: Meal, title: String },
+    /// Move the planner by this many weeks; 0 jumps back to the current week.
+    ShiftPlannerWeek(i64),
+    AddWeekToBasket,
+
+    // Shopping
+    ToggleBasket(String),
+    RemoveFromBasket(String),
+    SetShoppingItemChecked(usize, bool),
+    RebuildShoppingList,
+    MoveCheckedToPantry,
+    ClearShoppingList,
+
+    // History
+    Undo,
+    Redo,
+    /// Undo from a toast: only while the change with this id is the next to undo.
+    UndoChange(u64),
 
     // Settings
     SetDataDir(String),
-    DataDirReady(String),
+    /// A background load of the data directory finished; `generation` tells
+    /// whether it is still the current one.
+    DataDirReady { dir: String, generation: u64 },
+    DataChangedExternally(BTreeSet<PathBuf>),
+    DataReloaded { summary: ChangeSummary, generation: u64 },
     SetTheme(String),
     SetLanguage(String),
+    SetMeasurementSystem(String),
 
     // System
     ShowToast(String),
+    ShowError { summary: String, details: String },
     ReloadAll,
 }
 
@@ -73,27 +170,90 @@ pub struct App {
     // Recipes state
     pub recipe_search: String,
     pub selected_recipe: Option<String>,
+    /// Serving count chosen in the detail scaler, per recipe title (session only).
+    pub recipe_servings: HashMap<String, u32>,
+    pub recipe_sort: RecipeSort,
+    /// Split the recipe list into cookability sections.
+    pub recipe_grouped: bool,
+    pub recipe_filter: RecipeFilter,
 
     // Pantry state
     pub ingredient_search: String,
     pub selected_ingredient: Option<String>,
     pub category_filter: Vec<String>,
+    pub tag_filter: Vec<String>,
     pub in_stock_only: bool,
+    pub expiring_only: bool,
+    pub group_by_location: bool,
+
+    // Planner state (plan persisted in the data directory)
+    pub meal_plan: MealPlan,
+    /// Monday of the week shown in the planner.
+    pub planner_week: NaiveDate,
+
+    // Shopping state (basket + list, persisted in the data directory)
+    pub shopping: ShoppingList,
+
+    // When each recipe was cooked (persisted in the data directory)
+    pub cooking_log: CookingLog,
+
+    // Best-before dates of pantry items (persisted in the data directory)
+    pub pantry_details: PantryDetails,
+
+    // Data files that failed to load; they are not saved over until they load
+    pub unreadable: BTreeSet<&'static str>,
 
     // Dirty flags (Cell<bool> avoids &mut self in update_view)
     pub recipes_dirty: Cell<bool>,
     pub pantry_dirty: Cell<bool>,
     pub recipe_detail_dirty: Cell<bool>,
     pub ingredient_detail_dirty: Cell<bool>,
+    pub shopping_dirty: Cell<bool>,
+    pub planner_dirty: Cell<bool>,
 
     // Pending dialog requests (RefCell allows mutation from &self in update_view)
     pub pending_add_recipe: Cell<bool>,
     pub pending_edit_recipe: RefCell<Option<String>>,
+    pub pending_cook_recipe: RefCell<Option<String>>,
+    pub pending_cooking_history: Cell<bool>,
+    pub pending_cook_mode: RefCell<Option<String>>,
+    pub pending_import_recipe: Cell<bool>,
+    pub pending_imported_recipe: RefCell<Option<ImportedRecipe>>,
+    pub pending_unmatched: RefCell<Vec<String>>,
+    pub pending_export_recipe: RefCell<Option<String>>,
+    /// Suggested file name and laid-out pages of a PDF to save.
+    pub pending_export_pdf: RefCell<Option<(String, Vec<PrintBlock>)>>,
+    pub pending_print: RefCell<Option<(String, Vec<PrintBlock>)>>,
+    pub pending_export_cookbook: Cell<bool>,
     pub pending_add_ingredient: Cell<bool>,
     pub pending_edit_ingredient: RefCell<Option<String>>,
+    pub pending_storage_locations: Cell<bool>,
+    pub pending_import_pantry: Cell<bool>,
+    pub pending_pantry_preview: RefCell<Option<Vec<ImportRow>>>,
+    pub pending_export_pantry: RefCell<Option<Vec<PantryRow>>>,
+
+    // Undo/redo of data mutations
+    pub history: History,
+
+    // Notifications waiting to be shown in the toast overlay
+    pub pending_toasts: RefCell<VecDeque<ToastRequest>>,
+
+    // Channel for receiving the data loaded on a background thread, and the
+    // number of the newest load; results of older loads are dropped
+    pub pending_dm: Option<mpsc::Receiver<LoadedData>>,
+    pub load_generation: u64,
+
+    // Data directory watcher (live reload on external changes), and the files
+    // we wrote recently with the end of their grace period
+    pub watcher: Option<DataDirWatcher>,
+    pub own_writes: HashMap<PathBuf, Instant>,
+}
 
-    // Channel for receiving a DataManager loaded on a background thread
-    pub pending_dm: Option<mpsc::Receiver<Result<DataManager, String>>>,
+/// What a background load of the data directory reads.
+pub struct LoadedData {
+    pub dm: Result<DataManager, String>,
+    pub units: BTreeMap<String, IngredientUnits>,
+    pub thresholds: BTreeMap<String, Threshold>,
 }
 
 // ── Widget references ─────────────────────────────────────────────────────────
@@ -101,18 +261,30 @@ pub struct App {
 pub struct AppWidgets {
     pub window: adw::ApplicationWindow,
     pub toast_overlay: adw::ToastOverlay,
+    pub undo_btn: gtk::Button,
+    pub redo_btn: gtk::Button,
     pub main_stack: gtk::Stack,
     pub nav_list: gtk::ListBox,
 
     // Recipes
     pub recipe_list: gtk::ListBox,
     pub recipe_detail: gtk::Box,
+    pub recipe_filter_panel: crate::recipes::FilterPanel,
 
     // Pantry
     pub pantry_list: gtk::ListBox,
     pub ingredient_detail: gtk::Box,
     pub in_stock_switch: gtk::Switch,
+    pub expiring_switch: gtk::Switch,
+    pub location_switch: gtk::Switch,
+    pub pantry_filter: crate::pantry::FilterMenu,
 
+    // Planner
+    pub planner_recipe_list: gtk::ListBox,
+    pub planner_content: gtk::Box,
+
+    // Shopping
+    pub shopping_content: gtk::Box,
 }
 
 // ── SimpleComponent impl ──────────────────────────────────────────────────────
@@ -141,20 +313,15 @@ impl SimpleComponent for App {
 
         let settings = UserSettings::load();
         crate::i18n::set_language(crate::i18n::Language::from_tag(&settings.language));
+        crate::units::set_preferred_system(settings.measurement_system);
         let data_dir = UserSettings::effective_data_dir();
 
         // Load DataManager on a background thread so the window appears immediately
         // even if the data directory is on a slow/network filesystem (e.g. pCloud FUSE).
-        let (tx, rx) = mpsc::channel();
         let sender_startup = sender.clone();
-        let data_dir_startup = data_dir.clone();
-        let device_id_startup = UserSettings::effective_device_id();
-        std::thread::spawn(move || {
-            let result = DataManager::new(&data_dir_startup, device_id_startup).map_err(|e| e.to_string());
-            let _ = tx.send(result);
-            sender_startup.input(AppMsg::DataDirReady(
-                data_dir_startup.display().to_string(),
-            ));
+        let dir = data_dir.display().to_string();
+        let rx = spawn_load(data_dir.clone(), move || {
+            sender_startup.input(AppMsg::DataDirReady { dir, generation: 0 });
         });
 
         let app_state = App {
@@ -164,19 +331,53 @@ impl SimpleComponent for App {
             tab: Tab::Recipes,
             recipe_search: String::new(),
             selected_recipe: None,
+            recipe_servings: HashMap::new(),
+            recipe_sort: RecipeSort::default(),
+            recipe_grouped: false,
+            recipe_filter: RecipeFilter::default(),
             ingredient_search: String::new(),
             selected_ingredient: None,
-            category_filter: Vec::new(),
+            category_filter: settings.pantry_categories.clone(),
+            tag_filter: settings.pantry_tags.clone(),
             in_stock_only: false,
+            expiring_only: false,
+            group_by_location: false,
+            meal_plan: MealPlan::default(),
+            planner_week: week_start(chrono::Local::now().date_naive()),
+            shopping: ShoppingList::default(),
+            cooking_log: CookingLog::default(),
+            unreadable: BTreeSet::new(),
+            pantry_details: PantryDetails::default(),
             recipes_dirty: Cell::new(true),
             pantry_dirty: Cell::new(true),
             recipe_detail_dirty: Cell::new(false),
             ingredient_detail_dirty: Cell::new(false),
+            shopping_dirty: Cell::new(true),
+            planner_dirty: Cell::new(true),
             pending_add_recipe: Cell::new(false),
             pending_edit_recipe: RefCell::new(None),
+            pending_cook_recipe: RefCell::new(None),
+            pending_cooking_history: Cell::new(false),
+            pending_cook_mode: RefCell::new(None),
+            pending_import_recipe: Cell::new(false),
+            pending_imported_recipe: RefCell::new(None),
+            pending_unmatched: RefCell::new(Vec::new()),
+            pending_export_recipe: RefCell::new(None),
+            pending_export_pdf: RefCell::new(None),
+            pending_print: RefCell::new(None),
+            pending_export_cookbook: Cell::new(false),
             pending_add_ingredient: Cell::new(false),
             pending_edit_ingredient: RefCell::new(None),
+            pending_storage_locations: Cell::new(false),
+            pending_import_pantry: Cell::new(false),
+            pending_pantry_preview: RefCell::new(None),
+            pending_export_pantry: RefCell::new(None),
+            history: History::default(),
+            pending_toasts: RefCell::new(VecDeque::new()),
             pending_dm: Some(rx),
+            load_generation: 0,
+            watcher: Some(crate::watcher::watch_data_dir(&data_dir, sender.clone())),
+            own_writes: HashMap::new(),
         };
 
         // ── Apply initial theme ───────────────────────────────────────────────
@@ -191,8 +392,47 @@ impl SimpleComponent for App {
         let header = adw::HeaderBar::new();
         let win_title = adw::WindowTitle::new("Cookbook", "");
         header.set_title_widget(Some(&win_title));
+
+        let undo_btn = gtk::Button::from_icon_name("edit-undo-symbolic");
+        undo_btn.set_sensitive(false);
+        let redo_btn = gtk::Button::from_icon_name("edit-redo-symbolic");
+        redo_btn.set_sensitive(false);
+        {
+            let sender_undo = sender.clone();
+            undo_btn.connect_clicked(move |_| sender_undo.input(AppMsg::Undo));
+            let sender_redo = sender.clone();
+            redo_btn.connect_clicked(move |_| sender_redo.input(AppMsg::Redo));
+        }
+        header.pack_start(&undo_btn);
+        header.pack_start(&redo_btn);
+
+        let history_btn = gtk::Button::from_icon_name("document-open-recent-symbolic");
+        history_btn.set_tooltip_text(Some(crate::i18n::strings().cooking_history));
+        {
+            let sender_history = sender.clone();
+            history_btn.connect_clicked(move |_| sender_history.input(AppMsg::ShowCookingHistory));
+        }
+        header.pack_end(&history_btn);
         toolbar_view.add_top_bar(&header);
 
+        // Ctrl+Z / Ctrl+Shift+Z. Bubble phase, so text entries keep their own undo.
+        let shortcuts = gtk::ShortcutController::new();
+        for (trigger, msg) in [
+            ("<Control>z", AppMsg::Undo),
+            ("<Control><Shift>z", AppMsg::Redo),
+        ] {
+            let sender_shortcut = sender.clone();
+            let action = gtk::CallbackAction::new(move |_, _| {
+                sender_shortcut.input(msg.clone());
+                gtk::glib::Propagation::Stop
+            });
+            shortcuts.add_shortcut(gtk::Shortcut::new(
+                gtk::ShortcutTrigger::parse_string(trigger),
+                Some(action),
+            ));
+        }
+        root.add_controller(shortcuts);
+
         // Content: sidebar + stack
         let content_box = gtk::Box::new(gtk::Orientation::Horizontal, 0);
 
@@ -208,6 +448,8 @@ impl SimpleComponent for App {
         for (icon, label, tab_name) in &[
             ("emblem-documents-symbolic", "Recipes", "recipes"),
             ("view-list-symbolic", "Pantry", "pantry"),
+            ("x-office-calendar-symbolic", "Planner", "planner"),
+            ("view-list-bullet-symbolic", "Shopping", "shopping"),
             ("preferences-system-symbolic", "Settings", "settings"),
         ] {
             let row = gtk::ListBoxRow::new();
@@ -240,6 +482,8 @@ impl SimpleComponent for App {
                     let tab = match name.as_str() {
                         "recipes" => Tab::Recipes,
                         "pantry" => Tab::Pantry,
+                        "planner" => Tab::Planner,
+                        "shopping" => Tab::Shopping,
                         "settings" => Tab::Settings,
                         _ => Tab::Recipes,
                     };
@@ -258,15 +502,31 @@ impl SimpleComponent for App {
         main_stack.set_transition_duration(150);
 
         // Recipes tab
-        let (recipes_widget, recipe_list, recipe_detail) =
+        let (recipes_widget, recipe_list, recipe_detail, recipe_filter_panel) =
             crate::recipes::build_recipes_tab(&None, sender.clone());
         main_stack.add_named(&recipes_widget, Some("recipes"));
 
         // Pantry tab
-        let (pantry_widget, pantry_list, ingredient_detail, in_stock_switch) =
-            crate::pantry::build_pantry_tab(&None, false, sender.clone());
+        let (
+            pantry_widget,
+            pantry_list,
+            ingredient_detail,
+            in_stock_switch,
+            expiring_switch,
+            location_switch,
+            pantry_filter,
+        ) = crate::pantry::build_pantry_tab(&None, false, sender.clone());
         main_stack.add_named(&pantry_widget, Some("pantry"));
 
+        // Planner tab
+        let (planner_widget, planner_recipe_list, planner_content) =
+            crate::planner::build_planner_tab();
+        main_stack.add_named(&planner_widget, Some("planner"));
+
+        // Shopping tab
+        let (shopping_widget, shopping_content) = crate::shopping::build_shopping_tab();
+        main_stack.add_named(&shopping_widget, Some("shopping"));
+
         // Settings tab
         let settings_widget = crate::settings::build_settings_page(&sender);
         main_stack.add_named(&settings_widget, Some("settings"));
@@ -284,13 +544,22 @@ impl SimpleComponent for App {
         let widgets = AppWidgets {
             window: root,
             toast_overlay,
+            undo_btn,
+            redo_btn,
             main_stack,
             nav_list,
             recipe_list,
             recipe_detail,
+            recipe_filter_panel,
             pantry_list,
             ingredient_detail,
             in_stock_switch,
+            expiring_switch,
+            location_switch,
+            pantry_filter,
+            planner_recipe_list,
+            planner_content,
+            shopping_content,
         };
 
         ComponentParts {
@@ -316,45 +585,402 @@ impl SimpleComponent for App {
                 self.selected_recipe = title;
                 self.recipe_detail_dirty.set(true);
             }
+            AppMsg::SetRecipeServings { title, servings } => {
+                if self.recipe_servings.get(&title) != Some(&servings) {
+                    if self.shopping.contains(&title) {
+                        self.shopping.add_recipe(&title, Some(servings));
+                        self.rebuild_shopping(&sender);
+                    }
+                    // The detail pane already shows the new amounts
+                    self.recipe_servings.insert(title, servings);
+                }
+            }
+            AppMsg::SetRecipeSort(sort) => {
+                if self.recipe_sort != sort {
+                    self.recipe_sort = sort;
+                    self.recipes_dirty.set(true);
+                }
+            }
+            AppMsg::ToggleRecipeGrouping(grouped) => {
+                if self.recipe_grouped != grouped {
+                    self.recipe_grouped = grouped;
+                    self.recipes_dirty.set(true);
+                }
+            }
+            AppMsg::UpdateRecipeFilter(change) => {
+                let before = self.recipe_filter.clone();
+                self.recipe_filter.apply(change);
+                if self.recipe_filter != before {
+                    self.recipes_dirty.set(true);
+                }
+            }
+            AppMsg::CookRecipe(title) => {
+                *self.pending_cook_recipe.borrow_mut() = Some(title);
+            }
+            AppMsg::StartCookMode(title) => {
+                *self.pending_cook_mode.borrow_mut() = Some(title);
+            }
+            AppMsg::ThumbnailsReady => {
+                self.recipes_dirty.set(true);
+                self.recipe_detail_dirty.set(true);
+                self.planner_dirty.set(true);
+            }
+            AppMsg::ConfirmCooked { title, servings, consumed } => {
+                let Some(dm) = self.dm.clone() else {
+                    return;
+                };
+                let mut files = self.pantry_files();
+                files.push(self.data_dir.join(crate::cooking::COOKING_LOG_FILE));
+                self.note_own_write(files);
+                // One undo step for every deduction; the log entry isn't undone
+                let (mut before, mut after) = (Vec::new(), Vec::new());
+                for (name, consume) in consumed {
+                    let Some(item) = dm.borrow().get_pantry_item(&name).cloned() else {
+                        continue;
+                    };
+                    let snapshot = self.ingredient_snapshot(&name);
+                    let result = match remaining_after(item.quantity, &item.quantity_type, consume) {
+                        Some(qty) => dm
+                            .borrow_mut()
+                            .update_pantry_item(&name, qty, Some(item.quantity_type.clone()))
+                            .map(|_| ())
+                            .map_err(|e| error_chain(&e)),
+                        // Used up: take it out of the pantry, leaving the
+                        // ingredient file alone
+                        None => dm
+                            .borrow_mut()
+                            .remove_from_pantry(&name)
+                            .map(|_| ())
+                            .map_err(|e| error_chain(&e)),
+                    };
+                    match result {
+                        Ok(_) => {
+                            before.push(snapshot);
+                            after.push(self.ingredient_snapshot(&name));
+                        }
+                        Err(e) => sender.input(AppMsg::ShowError {
+                            summary: crate::i18n::fmt_save_failed(&name),
+                            details: e,
+                        }),
+                    }
+                }
+                if !before.is_empty() {
+                    self.history.record(Change {
+                        label: crate::i18n::fmt_used_for(&title),
+                        before: Snapshot::Group(before),
+                        after: Snapshot::Group(after),
+                    });
+                }
+
+                // Re-read first so cooks logged on other devices are kept
+                let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+                let logged = CookingLog::load(&self.data_dir).and_then(|mut log| {
+                    log.record(&title, &today, servings);
+                    log.save(&self.data_dir)?;
+                    self.cooking_log = log;
+                    self.unreadable.remove(crate::cooking::COOKING_LOG_FILE);
+                    Ok(())
+                });
+                if let Err(e) = logged {
+                    sender.input(AppMsg::ShowError {
+                        summary: crate::i18n::strings().cooking_log_save_failed.to_string(),
+                        details: e,
+                    });
+                }
+
+                // Dates of used-up items go with them
+                if self.pantry_details.prune(&dm.borrow()) {
+                    self.save_pantry_details(&sender);
+                }
+
+                sender.input(AppMsg::ShowToast(crate::i18n::fmt_cooked(&title)));
+                self.recipes_dirty.set(true);
+                self.pantry_dirty.set(true);
+                self.recipe_detail_dirty.set(true);
+                self.ingredient_detail_dirty.set(true);
+            }
+            AppMsg::ImportRecipeFiles(paths) => {
+                let Some(dm) = self.dm.clone() else {
+                    return;
+                };
+                let (infos, units) = {
+                    let dm = dm.borrow();
+                    (build_ingredient_infos(&dm), build_known_units(&dm))
+                };
+                let s = crate::i18n::strings();
+                let mut imported = 0;
+                let mut unmatched: Vec<String> = Vec::new();
+                for path in paths {
+                    let is_cook =
+                        path.extension().is_some_and(|e| e.eq_ignore_ascii_case("cook"));
+                    if !is_cook {
+                        match std::fs::read(&path) {
+                            Ok(bytes) => sender.input(AppMsg::ImportRecipe {
+                                text: String::from_utf8_lossy(&bytes).into_owned(),
+                                source: Some(path),
+                            }),
+                            Err(e) => sender.input(AppMsg::ShowError {
+                                summary: s.recipe_import_failed.to_string(),
+                                details: format!("{}: {}", path.display(), e),
+                            }),
+                        }
+                        continue;
+                    }
+                    let ImportedRecipe { mut recipe, image_file, unmatched: names } =
+                        match crate::cooklang::import_cook_file(&path, &infos, &units) {
+                            Ok(imported) => imported,
+                            Err(e) => {
+                                sender.input(AppMsg::ShowError {
+                                    summary: s.recipe_import_failed.to_string(),
+                                    details: e,
+                                });
+                                continue;
+                            }
+                        };
+                    if let Some(source) = image_file {
+                        let slug = janus_engine::slugify(&recipe.title);
+                        match crate::images::import_image(&self.data_dir, &source, &slug) {
+                            Ok(name) => recipe.image = Some(name),
+                            Err(e) => sender.input(AppMsg::ShowError {
+                                summary: s.image_import_failed.to_string(),
+                                details: e,
+                            }),
+                        }
+                    }
+                    let result = dm.borrow_mut().create_recipe(recipe.clone());
+                    match result {
+                        Ok(_) => {
+                            self.note_own_write(self.recipe_files(&recipe.title));
+                            self.history.record(Change {
+                                label: crate::i18n::fmt_saved(&recipe.title),
+                                before: Snapshot::Recipe(None),
+                                after: Snapshot::recipe(
+                                    &dm.borrow(),
+                                    &self.data_dir,
+                                    &recipe.title,
+                                ),
+                            });
+                            imported += 1;
+                            for name in names {
+                                if !unmatched.contains(&name) {
+                                    unmatched.push(name);
+                                }
+                            }
+                        }
+                        Err(e) => sender.input(AppMsg::ShowError {
+                            summary: crate::i18n::fmt_save_failed(&recipe.title),
+                            details: error_chain(&e),
+                        }),
+                    }
+                }
+                if imported > 0 {
+                    sender.input(AppMsg::ShowToast(crate::i18n::fmt_imported_recipes(imported)));
+                    self.recipes_dirty.set(true);
+                    self.planner_dirty.set(true);
+                }
+                *self.pending_unmatched.borrow_mut() = unmatched;
+            }
+            AppMsg::ExportRecipe(title) => {
+                *self.pending_export_recipe.borrow_mut() = Some(title);
+            }
+            AppMsg::ExportRecipePdf(title) => {
+                if let Some(blocks) = self.recipe_print_blocks(&title) {
+                    let name = format!("{}.pdf", janus_engine::slugify(&title));
+                    *self.pending_export_pdf.borrow_mut() = Some((name, blocks));
+                }
+            }
+            AppMsg::PrintRecipe(title) => {
+                if let Some(blocks) = self.recipe_print_blocks(&title) {
+                    *self.pending_print.borrow_mut() = Some((title, blocks));
+                }
+            }
+            AppMsg::ShowExportCookbook => {
+                self.pending_export_cookbook.set(true);
+            }
+            AppMsg::ExportCookbook(tag) => {
+                if let Some(dm) = &self.dm {
+                    let dm = dm.borrow();
+                    let mut recipes: Vec<&Recipe> = dm
+                        .get_all_recipes()
+                        .iter()
+                        .filter(|r| match &tag {
+                            Some(tag) => crate::tags::has_tag(&r.tags, tag),
+                            None => true,
+                        })
+                        .collect();
+                    recipes.sort_by(|a, b| a.title.cmp(&b.title));
+                    let title = crate::i18n::fmt_cookbook_title(tag.as_deref());
+                    let blocks = crate::print::cookbook_blocks(&dm, &title, &recipes);
+                    let name = format!("{}.pdf", janus_engine::slugify(&title));
+                    *self.pending_export_pdf.borrow_mut() = Some((name, blocks));
+                }
+            }
+            AppMsg::CreateIngredients(names) => {
+                let files: Vec<PathBuf> = names.iter().map(|n| self.ingredient_file(n)).collect();
+                self.note_own_write(files);
+                if let Some(dm) = &self.dm {
+                    let mut created = 0;
+                    for name in names {
+                        if dm.borrow().get_ingredient(&name).is_some() {
+                            continue;
+                        }
+                        let ingredient = Ingredient {
+                            name: name.clone(),
+                            slug: janus_engine::slugify(&name),
+                            file_stem: String::new(),
+                            category: String::new(),
+                            tags: None,
+                            plural: None,
+                        };
+                        let result = dm.borrow_mut().create_ingredient(ingredient);
+                        match result {
+                            Ok(_) => {
+                                self.history.record(Change {
+                                    label: crate::i18n::fmt_saved(&name),
+                                    before: Snapshot::Ingredient(None),
+                                    after: self.ingredient_snapshot(&name),
+                                });
+                                created += 1;
+                            }
+                            Err(e) => sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_save_failed(&name),
+                                details: error_chain(&e),
+                            }),
+                        }
+                    }
+                    if created > 0 {
+                        let text = crate::i18n::fmt_created_ingredients(created);
+                        sender.input(AppMsg::ShowToast(text));
+                        self.pantry_dirty.set(true);
+                        self.recipes_dirty.set(true);
+                        self.recipe_detail_dirty.set(true);
+                    }
+                }
+            }
+            AppMsg::ShowCookingHistory => {
+                self.pending_cooking_history.set(true);
+            }
             AppMsg::AddRecipe => {
                 self.pending_add_recipe.set(true);
             }
             AppMsg::EditRecipe(title) => {
                 *self.pending_edit_recipe.borrow_mut() = Some(title);
             }
+            AppMsg::ShowImportRecipe => {
+                self.pending_import_recipe.set(true);
+            }
+            AppMsg::ImportRecipe { text, source } => {
+                let (infos, units) = match &self.dm {
+                    Some(dm) => {
+                        let dm = dm.borrow();
+                        (build_ingredient_infos(&dm), build_known_units(&dm))
+                    }
+                    None => (Vec::new(), Vec::new()),
+                };
+                let result =
+                    crate::recipe_import::import_json_ld(&text, source.as_deref(), &infos, &units);
+                match result {
+                    Ok(imported) => *self.pending_imported_recipe.borrow_mut() = Some(imported),
+                    Err(e) => sender.input(AppMsg::ShowError {
+                        summary: crate::i18n::strings().recipe_import_failed.to_string(),
+                        details: e.to_string(),
+                    }),
+                }
+            }
             AppMsg::DeleteRecipe(title) => {
+                self.note_own_write(self.recipe_files(&title));
                 if let Some(dm) = &self.dm {
-                    match dm.borrow_mut().delete_recipe(&title) {
+                    let before = Snapshot::recipe(&dm.borrow(), &self.data_dir, &title);
+                    let result = dm.borrow_mut().delete_recipe(&title);
+                    match result {
                         Ok(_) => {
+                            let label = crate::i18n::fmt_deleted(&title);
+                            let id = self.history.record(Change {
+                                label: label.clone(),
+                                before,
+                                after: Snapshot::Recipe(None),
+                            });
+                            self.pending_toasts
+                                .borrow_mut()
+                                .push_back(ToastRequest::undo(label, id));
                             if self.selected_recipe.as_deref() == Some(&title) {
                                 self.selected_recipe = None;
                             }
+                            self.recipe_servings.remove(&title);
                             self.recipes_dirty.set(true);
                             self.recipe_detail_dirty.set(true);
                         }
                         Err(e) => {
-                            sender.input(AppMsg::ShowToast(format!("Error: {}", e)));
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_delete_failed(&title),
+                                details: error_chain(&e),
+                            });
                         }
                     }
                 }
             }
-            AppMsg::SaveRecipe { original, recipe } => {
+            AppMsg::SaveRecipe { original, mut recipe, new_image } => {
+                if let Some(ref orig) = original {
+                    self.note_own_write(self.recipe_files(orig));
+                }
+                if let Some(source) = new_image {
+                    let slug = janus_engine::slugify(&recipe.title);
+                    match crate::images::import_image(&self.data_dir, &source, &slug) {
+                        Ok(name) => recipe.image = Some(name),
+                        Err(e) => sender.input(AppMsg::ShowError {
+                            summary: crate::i18n::strings().image_import_failed.to_string(),
+                            details: e,
+                        }),
+                    }
+                }
                 if let Some(dm) = &self.dm {
+                    let before = match original {
+                        Some(ref orig) => Snapshot::recipe(&dm.borrow(), &self.data_dir, orig),
+                        None => Snapshot::Recipe(None),
+                    };
                     let result = match original {
-                        Some(ref orig) => dm.borrow_mut().update_recipe(orig, recipe.clone()),
+                        // Frontmatter written by other frontends is put back after the engine saves
+                        Some(ref orig) => crate::frontmatter::keep_unknown_keys(
+                            &mut dm.borrow_mut(),
+                            &self.data_dir,
+                            orig,
+                            &recipe.title,
+                            |dm| dm.update_recipe(orig, recipe.clone()),
+                        ),
                         None => dm
                             .borrow_mut()
                             .create_recipe(recipe.clone())
-                            .map(|_| true),
+                            .map(|_| true)
+                            .map_err(|e| error_chain(&e)),
                     };
                     match result {
                         Ok(_) => {
+                            self.history.record(Change {
+                                label: crate::i18n::fmt_saved(&recipe.title),
+                                before,
+                                after: Snapshot::recipe(
+                                    &dm.borrow(),
+                                    &self.data_dir,
+                                    &recipe.title,
+                                ),
+                            });
+                            self.note_own_write(self.recipe_files(&recipe.title));
+                            // Keep the chosen scale and basket entry across a rename
+                            if let Some(orig) = original.as_ref().filter(|o| **o != recipe.title) {
+                                if let Some(n) = self.recipe_servings.remove(orig) {
+                                    self.recipe_servings.insert(recipe.title.clone(), n);
+                                }
+                                self.follow_recipe_rename(orig, &recipe.title, &sender);
+                            }
                             self.selected_recipe = Some(recipe.title.clone());
                             self.recipes_dirty.set(true);
                             self.recipe_detail_dirty.set(true);
                         }
                         Err(e) => {
-                            sender.input(AppMsg::ShowToast(format!("Error saving recipe: {}", e)));
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_save_failed(&recipe.title),
+                                details: e,
+                            });
                         }
                     }
                 }
@@ -369,10 +995,45 @@ impl SimpleComponent for App {
                 self.selected_ingredient = name;
                 self.ingredient_detail_dirty.set(true);
             }
+            AppMsg::ShowIngredient(name) => {
+                self.tab = Tab::Pantry;
+                self.selected_ingredient = Some(name);
+                self.pantry_dirty.set(true);
+                self.ingredient_detail_dirty.set(true);
+            }
             AppMsg::ToggleInStockOnly(val) => {
                 self.in_stock_only = val;
                 self.pantry_dirty.set(true);
             }
+            AppMsg::SetCategoryFilter { category, selected } => {
+                crate::pantry_filter::set_selected(&mut self.category_filter, &category, selected);
+                self.save_pantry_filters();
+            }
+            AppMsg::SetTagFilter { tag, selected } => {
+                crate::pantry_filter::set_selected(&mut self.tag_filter, &tag, selected);
+                self.save_pantry_filters();
+            }
+            AppMsg::ClearPantryFilters => {
+                self.category_filter.clear();
+                self.tag_filter.clear();
+                self.save_pantry_filters();
+            }
+            AppMsg::ToggleExpiringOnly(val) => {
+                self.expiring_only = val;
+                self.pantry_dirty.set(true);
+            }
+            AppMsg::ToggleGroupByLocation(val) => {
+                self.group_by_location = val;
+                self.pantry_dirty.set(true);
+            }
+            AppMsg::EditStorageLocations => {
+                self.pending_storage_locations.set(true);
+            }
+            AppMsg::SaveStorageLocations(locations) => {
+                self.pantry_details.set_locations(locations);
+                self.save_pantry_details(&sender);
+                self.ingredient_detail_dirty.set(true);
+            }
             AppMsg::AddIngredient => {
                 self.pending_add_ingredient.set(true);
             }
@@ -380,9 +1041,22 @@ impl SimpleComponent for App {
                 *self.pending_edit_ingredient.borrow_mut() = Some(name);
             }
             AppMsg::DeleteIngredient(name) => {
+                self.note_own_write(self.ingredient_files(&name));
                 if let Some(dm) = &self.dm {
-                    match dm.borrow_mut().delete_ingredient(&name) {
+                    let before = self.ingredient_snapshot(&name);
+                    let result = dm.borrow_mut().delete_ingredient(&name);
+                    match result {
                         Ok(_) => {
+                            crate::ingredient_file::remember_extra_keys(&name, None);
+                            let label = crate::i18n::fmt_deleted(&name);
+                            let id = self.history.record(Change {
+                                label: label.clone(),
+                                before,
+                                after: Snapshot::Ingredient(None),
+                            });
+                            self.pending_toasts
+                                .borrow_mut()
+                                .push_back(ToastRequest::undo(label, id));
                             if self.selected_ingredient.as_deref() == Some(&name) {
                                 self.selected_ingredient = None;
                             }
@@ -391,63 +1065,362 @@ impl SimpleComponent for App {
                             self.recipes_dirty.set(true); // availability may have changed
                         }
                         Err(e) => {
-                            sender.input(AppMsg::ShowToast(format!("Error: {}", e)));
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_delete_failed(&name),
+                                details: error_chain(&e),
+                            });
                         }
                     }
                 }
             }
+            AppMsg::RestockIngredients(names) => {
+                let Some(dm) = &self.dm else {
+                    return;
+                };
+                let low =
+                    crate::low_stock::running_low(&dm.borrow(), &crate::low_stock::thresholds());
+                let mut added = 0;
+                for item in low.iter().filter(|l| names.contains(&l.ingredient)) {
+                    let restock = crate::low_stock::restock_item(&dm.borrow(), item);
+                    self.shopping.add_restock(restock);
+                    added += 1;
+                }
+                if added > 0 {
+                    self.save_shopping(&sender);
+                    sender.input(AppMsg::ShowToast(crate::i18n::fmt_restocked(added)));
+                }
+            }
+            AppMsg::ShowImportPantry => {
+                self.pending_import_pantry.set(true);
+            }
+            AppMsg::PreviewPantryImport { path, text } => {
+                let Some(dm) = &self.dm else {
+                    return;
+                };
+                match crate::pantry_io::read_rows(&path, &text) {
+                    Ok(rows) => {
+                        let rows = crate::pantry_io::preview(&dm.borrow(), rows);
+                        *self.pending_pantry_preview.borrow_mut() = Some(rows);
+                    }
+                    Err(e) => sender.input(AppMsg::ShowError {
+                        summary: crate::i18n::strings().pantry_import_failed.to_string(),
+                        details: format!("{}: {}", path.display(), e),
+                    }),
+                }
+            }
+            AppMsg::ImportPantry { rows, replace } => {
+                let Some(dm) = self.dm.clone() else {
+                    return;
+                };
+                self.note_own_write(self.pantry_files());
+                let mut imported = 0;
+                // One undo step for the whole import, covering the rows written
+                let (mut before, mut after) = (Vec::new(), Vec::new());
+                for row in rows {
+                    match row.status {
+                        RowStatus::New | RowStatus::UnknownIngredient => {}
+                        RowStatus::Conflict { .. } if replace => {}
+                        _ => continue,
+                    }
+                    let snapshot = self.ingredient_snapshot(&row.ingredient);
+                    let created = row.status == RowStatus::UnknownIngredient;
+                    if created {
+                        let ingredient = Ingredient {
+                            name: row.ingredient.clone(),
+                            slug: janus_engine::slugify(&row.ingredient),
+                            file_stem: String::new(),
+                            category: row.category.clone(),
+                            tags: None,
+                            plural: None,
+                        };
+                        self.note_own_write(self.ingredient_files(&row.ingredient));
+                        if let Err(e) = dm.borrow_mut().create_ingredient(ingredient) {
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_save_failed(&row.ingredient),
+                                details: error_chain(&e),
+                            });
+                            continue;
+                        }
+                    }
+                    let unit = if row.unit.is_empty() { None } else { Some(row.unit.clone()) };
+                    let result =
+                        dm.borrow_mut().update_pantry_item(&row.ingredient, row.quantity, unit);
+                    match result {
+                        Ok(_) => imported += 1,
+                        Err(e) => {
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_save_failed(&row.ingredient),
+                                details: error_chain(&e),
+                            });
+                            // A created ingredient stays, and can be undone
+                            if !created {
+                                continue;
+                            }
+                        }
+                    }
+                    before.push(snapshot);
+                    after.push(self.ingredient_snapshot(&row.ingredient));
+                }
+                if imported > 0 {
+                    sender.input(AppMsg::ShowToast(crate::i18n::fmt_imported_pantry(imported)));
+                }
+                if !before.is_empty() {
+                    self.history.record(Change {
+                        label: crate::i18n::fmt_imported_pantry(before.len()),
+                        before: Snapshot::Group(before),
+                        after: Snapshot::Group(after),
+                    });
+                }
+                self.pantry_dirty.set(true);
+                self.ingredient_detail_dirty.set(true);
+                self.recipes_dirty.set(true);
+                self.recipe_detail_dirty.set(true);
+            }
+            AppMsg::ExportPantry => {
+                if let Some(dm) = &self.dm {
+                    let rows = crate::pantry_io::export_rows(&dm.borrow());
+                    *self.pending_export_pantry.borrow_mut() = Some(rows);
+                }
+            }
             AppMsg::SaveIngredient {
                 original,
                 ingredient,
                 in_pantry,
                 qty,
                 qty_type,
+                best_before,
+                location,
+                threshold,
             } => {
-                if let Some(dm) = &self.dm {
+                if let Some(ref orig) = original {
+                    self.note_own_write(self.ingredient_files(orig));
+                }
+                if let Some(dm) = self.dm.clone() {
+                    let before = match original {
+                        Some(ref orig) => self.ingredient_snapshot(orig),
+                        None => Snapshot::Ingredient(None),
+                    };
+                    // The threshold is written along with the keys the engine
+                    // doesn't know, in the same save
+                    let mut extra = match original {
+                        Some(ref orig) => {
+                            read_extra_keys(&ingredient_path(&self.data_dir, &dm.borrow(), orig))
+                        }
+                        None => serde_yaml::Mapping::new(),
+                    };
+                    crate::low_stock::set_threshold_keys(&mut extra, threshold.as_ref());
                     let result = if let Some(ref orig) = original {
-                        dm.borrow_mut().update_ingredient_with_pantry(
+                        write_with_extra_keys(
+                            &mut dm.borrow_mut(),
+                            &self.data_dir,
                             orig,
-                            ingredient.clone(),
-                            if in_pantry { qty } else { None },
-                            if in_pantry { Some(qty_type) } else { None },
-                            !in_pantry,
+                            &ingredient.name,
+                            &extra,
+                            |dm| {
+                                dm.update_ingredient_with_pantry(
+                                    orig,
+                                    ingredient.clone(),
+                                    if in_pantry { qty } else { None },
+                                    if in_pantry { Some(qty_type) } else { None },
+                                    !in_pantry,
+                                )
+                            },
                         )
                     } else {
                         // Create new ingredient first
-                        let create_result =
-                            dm.borrow_mut().create_ingredient(ingredient.clone());
+                        let create_result = write_with_extra_keys(
+                            &mut dm.borrow_mut(),
+                            &self.data_dir,
+                            &ingredient.name,
+                            &ingredient.name,
+                            &extra,
+                            |dm| dm.create_ingredient(ingredient.clone()),
+                        );
                         if let Err(e) = create_result {
-                            sender.input(AppMsg::ShowToast(format!("Error: {}", e)));
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_save_failed(&ingredient.name),
+                                details: e,
+                            });
                             return;
                         }
                         if in_pantry {
-                            dm.borrow_mut().update_pantry_item(
-                                &ingredient.name,
-                                qty,
-                                if qty_type.is_empty() { None } else { Some(qty_type) },
-                            )
+                            dm.borrow_mut()
+                                .update_pantry_item(
+                                    &ingredient.name,
+                                    qty,
+                                    if qty_type.is_empty() { None } else { Some(qty_type) },
+                                )
+                                .map_err(|e| error_chain(&e))
                         } else {
                             Ok(true)
                         }
                     };
                     match result {
                         Ok(_) => {
+                            self.note_own_write(self.ingredient_files(&ingredient.name));
+                            if let Some(ref orig) = original {
+                                self.pantry_details.rename(orig, &ingredient.name);
+                            }
+                            self.pantry_details.set_best_before(
+                                &ingredient.name,
+                                best_before.filter(|_| in_pantry),
+                            );
+                            // Keep the default locations once one is in use, so
+                            // they don't change with the language
+                            if location.is_some() && self.pantry_details.locations.is_empty() {
+                                self.pantry_details.locations = self.storage_locations();
+                            }
+                            self.pantry_details
+                                .set_location(&ingredient.name, location.filter(|_| in_pantry));
+                            // Recorded with the details, so undo restores them too
+                            self.history.record(Change {
+                                label: crate::i18n::fmt_saved(&ingredient.name),
+                                before,
+                                after: self.ingredient_snapshot(&ingredient.name),
+                            });
+                            self.save_pantry_details(&sender);
                             self.selected_ingredient = Some(ingredient.name.clone());
                             self.pantry_dirty.set(true);
                             self.ingredient_detail_dirty.set(true);
                             self.recipes_dirty.set(true);
                         }
                         Err(e) => {
-                            sender.input(AppMsg::ShowToast(format!("Error: {}", e)));
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_save_failed(&ingredient.name),
+                                details: e,
+                            });
                         }
                     }
                 }
             }
 
+            // ── Planner ───────────────────────────────────────────────────────
+            AppMsg::PlanRecipe { date, meal, title } => {
+                self.meal_plan.add(date, meal, &title);
+                self.save_meal_plan(&sender);
+            }
+            AppMsg::UnplanRecipe { date, meal, title } => {
+                self.meal_plan.remove(date, meal, &title);
+                self.save_meal_plan(&sender);
+            }
+            AppMsg::ShiftPlannerWeek(weeks) => {
+                let this_week = week_start(chrono::Local::now().date_naive());
+                self.planner_week = match weeks {
+                    0 => Some(this_week),
+                    w if w > 0 => self.planner_week.checked_add_days(Days::new(7 * w as u64)),
+                    w => self.planner_week.checked_sub_days(Days::new(7 * w.unsigned_abs())),
+                }
+                .unwrap_or(this_week);
+                self.planner_dirty.set(true);
+            }
+            AppMsg::AddWeekToBasket => {
+                let mut titles: Vec<String> = self
+                    .meal_plan
+                    .week_recipes(self.planner_week)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+                titles.sort();
+                titles.dedup();
+                for title in titles {
+                    let servings = self.recipe_servings.get(&title).copied();
+                    self.shopping.add_recipe(&title, servings);
+                }
+                self.rebuild_shopping(&sender);
+                self.recipe_detail_dirty.set(true);
+            }
+
+            // ── Shopping ──────────────────────────────────────────────────────
+            AppMsg::ToggleBasket(title) => {
+                if self.shopping.contains(&title) {
+                    self.shopping.remove_recipe(&title);
+                } else {
+                    let servings = self.recipe_servings.get(&title).copied();
+                    self.shopping.add_recipe(&title, servings);
+                }
+                self.rebuild_shopping(&sender);
+                self.recipe_detail_dirty.set(true);
+            }
+            AppMsg::RemoveFromBasket(title) => {
+                self.shopping.remove_recipe(&title);
+                self.rebuild_shopping(&sender);
+                self.recipe_detail_dirty.set(true);
+            }
+            AppMsg::SetShoppingItemChecked(idx, checked) => {
+                if let Some(item) = self.shopping.items.get_mut(idx) {
+                    if item.checked != checked {
+                        item.checked = checked;
+                        self.save_shopping(&sender);
+                    }
+                }
+            }
+            AppMsg::RebuildShoppingList => self.rebuild_shopping(&sender),
+            AppMsg::MoveCheckedToPantry => {
+                let Some(dm) = self.dm.clone() else {
+                    return;
+                };
+                self.note_own_write(self.pantry_files());
+                // One undo step for every item moved
+                let (mut before, mut after) = (Vec::new(), Vec::new());
+                for item in self.shopping.take_checked() {
+                    let snapshot = self.ingredient_snapshot(&item.ingredient);
+                    let (qty, unit) =
+                        crate::shopping_list::pantry_after_purchase(&dm.borrow(), &item);
+                    let result = dm.borrow_mut().update_pantry_item(
+                        &item.ingredient,
+                        qty,
+                        if unit.is_empty() { None } else { Some(unit) },
+                    );
+                    match result {
+                        Ok(_) => {
+                            before.push(snapshot);
+                            after.push(self.ingredient_snapshot(&item.ingredient));
+                        }
+                        Err(e) => {
+                            sender.input(AppMsg::ShowError {
+                                summary: crate::i18n::fmt_save_failed(&item.ingredient),
+                                details: error_chain(&e),
+                            });
+                            self.shopping.items.push(item);
+                        }
+                    }
+                }
+                self.save_shopping(&sender);
+                if !before.is_empty() {
+                    let label = crate::i18n::fmt_moved_to_pantry(before.len());
+                    sender.input(AppMsg::ShowToast(label.clone()));
+                    self.history.record(Change {
+                        label,
+                        before: Snapshot::Group(before),
+                        after: Snapshot::Group(after),
+                    });
+                }
+                self.recipes_dirty.set(true);
+                self.pantry_dirty.set(true);
+                self.recipe_detail_dirty.set(true);
+                self.ingredient_detail_dirty.set(true);
+            }
+            AppMsg::ClearShoppingList => {
+                self.shopping = ShoppingList::default();
+                self.save_shopping(&sender);
+                self.recipe_detail_dirty.set(true);
+            }
+
+            // ── History ───────────────────────────────────────────────────────
+            AppMsg::Undo => self.step_history(true, &sender),
+            AppMsg::Redo => self.step_history(false, &sender),
+            AppMsg::UndoChange(id) => {
+                // A newer change, or an undo since, makes the toast stale
+                if self.history.undo_id() == Some(id) {
+                    self.step_history(true, &sender);
+                }
+            }
+
             // ── Settings ──────────────────────────────────────────────────────
             AppMsg::SetDataDir(dir) => {
                 let path = PathBuf::from(&dir);
                 self.data_dir = path.clone();
+                self.history.clear();
                 {
                     let mut s = self.settings.borrow_mut();
                     s.data_dir = Some(dir.clone());
@@ -455,33 +1428,134 @@ impl SimpleComponent for App {
                 }
                 // Load DataManager on a background thread to avoid blocking the UI
                 // (pCloud FUSE can take time for network reads).
-                let (tx, rx) = mpsc::channel();
-                self.pending_dm = Some(rx);
+                self.watcher = Some(crate::watcher::watch_data_dir(&path, sender.clone()));
+                self.own_writes.clear();
+                self.load_generation += 1;
+                let generation = self.load_generation;
                 let sender_clone = sender.clone();
-                let device_id_reload = UserSettings::effective_device_id();
-                std::thread::spawn(move || {
-                    let result = DataManager::new(&path, device_id_reload).map_err(|e| e.to_string());
-                    let _ = tx.send(result);
-                    sender_clone.input(AppMsg::DataDirReady(dir));
-                });
+                self.pending_dm = Some(spawn_load(path, move || {
+                    sender_clone.input(AppMsg::DataDirReady { dir, generation });
+                }));
             }
-            AppMsg::DataDirReady(dir) => {
-                match self.pending_dm.take().and_then(|rx| rx.recv().ok()) {
+            AppMsg::DataDirReady { dir, generation } => {
+                if generation != self.load_generation {
+                    log::debug!("Dropping the result of an outdated load of {}", dir);
+                    return;
+                }
+                match self.take_loaded() {
                     Some(Ok(new_dm)) => {
                         self.dm = Some(Rc::new(RefCell::new(new_dm)));
                     }
                     Some(Err(e)) => {
-                        sender.input(AppMsg::ShowToast(format!(
-                            "Could not load data from {dir}: {e}"
-                        )));
+                        sender.input(AppMsg::ShowError {
+                            summary: crate::i18n::fmt_load_failed(&dir),
+                            details: e,
+                        });
                     }
                     None => {}
                 }
+                self.load_shopping(&sender);
+                self.load_cooking_log(&sender);
+                self.load_meal_plan(&sender);
+                self.load_pantry_details(&sender);
                 self.recipes_dirty.set(true);
                 self.pantry_dirty.set(true);
                 self.selected_recipe = None;
                 self.selected_ingredient = None;
             }
+            AppMsg::DataChangedExternally(paths) => {
+                let now = Instant::now();
+                self.own_writes.retain(|_, until| *until > now);
+                let (own, paths): (BTreeSet<PathBuf>, BTreeSet<PathBuf>) =
+                    paths.into_iter().partition(|p| self.own_writes.contains_key(p));
+                if !own.is_empty() {
+                    log::debug!("Ignoring file events caused by our own writes: {:?}", own);
+                }
+                let summary = crate::watcher::summarize(&self.data_dir, &paths);
+                if summary.is_empty() {
+                    return;
+                }
+                if summary.only_app_files() {
+                    if summary.shopping {
+                        self.load_shopping(&sender);
+                    }
+                    if summary.cooking_log {
+                        self.load_cooking_log(&sender);
+                    }
+                    if summary.meal_plan {
+                        self.load_meal_plan(&sender);
+                    }
+                    if summary.pantry_details {
+                        self.load_pantry_details(&sender);
+                    }
+                    self.recipe_detail_dirty.set(true);
+                    sender.input(AppMsg::ShowToast(crate::i18n::fmt_external_change(&summary)));
+                    return;
+                }
+                if self.pending_dm.is_some() {
+                    // A load is already in flight and will pick up the new files.
+                    return;
+                }
+                log::info!("Data directory changed externally: {:?}", summary);
+                self.load_generation += 1;
+                let generation = self.load_generation;
+                let sender_clone = sender.clone();
+                self.pending_dm = Some(spawn_load(self.data_dir.clone(), move || {
+                    sender_clone.input(AppMsg::DataReloaded { summary, generation });
+                }));
+            }
+            AppMsg::DataReloaded { summary, generation } => {
+                if generation != self.load_generation {
+                    log::debug!("Dropping the result of an outdated reload");
+                    return;
+                }
+                match self.take_loaded() {
+                    Some(Ok(new_dm)) => {
+                        // Keep the current selection if it survived the reload
+                        if self
+                            .selected_recipe
+                            .as_deref()
+                            .is_some_and(|t| new_dm.get_recipe(t).is_none())
+                        {
+                            self.selected_recipe = None;
+                        }
+                        if self
+                            .selected_ingredient
+                            .as_deref()
+                            .is_some_and(|n| new_dm.get_ingredient(n).is_none())
+                        {
+                            self.selected_ingredient = None;
+                        }
+                        self.dm = Some(Rc::new(RefCell::new(new_dm)));
+                        // The history's snapshots may no longer match the files
+                        self.history.clear();
+                        sender.input(AppMsg::ShowToast(crate::i18n::fmt_external_change(&summary)));
+                    }
+                    Some(Err(e)) => {
+                        sender.input(AppMsg::ShowError {
+                            summary: crate::i18n::fmt_load_failed(&self.data_dir.display().to_string()),
+                            details: e,
+                        });
+                    }
+                    None => {}
+                }
+                if summary.shopping {
+                    self.load_shopping(&sender);
+                }
+                if summary.cooking_log {
+                    self.load_cooking_log(&sender);
+                }
+                if summary.meal_plan {
+                    self.load_meal_plan(&sender);
+                }
+                if summary.pantry_details {
+                    self.load_pantry_details(&sender);
+                }
+                self.recipes_dirty.set(true);
+                self.pantry_dirty.set(true);
+                self.recipe_detail_dirty.set(true);
+                self.ingredient_detail_dirty.set(true);
+            }
             AppMsg::SetTheme(theme_str) => {
                 let theme = match theme_str.as_str() {
                     "Light" => Theme::Light,
@@ -505,16 +1579,44 @@ impl SimpleComponent for App {
                 self.recipe_detail_dirty.set(true);
                 self.ingredient_detail_dirty.set(true);
             }
+            AppMsg::SetMeasurementSystem(system_str) => {
+                let system = match system_str.as_str() {
+                    "Imperial" => MeasurementSystem::Imperial,
+                    _ => MeasurementSystem::Metric,
+                };
+                crate::units::set_preferred_system(system);
+                let mut s = self.settings.borrow_mut();
+                s.measurement_system = system;
+                s.save();
+                self.recipe_detail_dirty.set(true);
+                self.ingredient_detail_dirty.set(true);
+            }
 
             // ── System ────────────────────────────────────────────────────────
             AppMsg::ShowToast(msg) => {
                 log::info!("Toast: {}", msg);
-                // Actual toast shown in update_view
+                self.pending_toasts.borrow_mut().push_back(ToastRequest::info(msg));
+            }
+            AppMsg::ShowError { summary, details } => {
+                log::error!("{}: {}", summary, details);
+                self.pending_toasts
+                    .borrow_mut()
+                    .push_back(ToastRequest::error(summary, details));
             }
             AppMsg::ReloadAll => {
+                // Results of a background load still in flight would be older
+                self.load_generation += 1;
+                self.pending_dm = None;
                 if let Ok(new_dm) = DataManager::new(&self.data_dir, UserSettings::effective_device_id()) {
                     self.dm = Some(Rc::new(RefCell::new(new_dm)));
+                    self.history.clear();
                 }
+                crate::units::set_ingredient_units(crate::units::load_ingredient_units(&self.data_dir));
+                crate::low_stock::set_thresholds(crate::low_stock::load_thresholds(&self.data_dir));
+                self.load_shopping(&sender);
+                self.load_cooking_log(&sender);
+                self.load_meal_plan(&sender);
+                self.load_pantry_details(&sender);
                 self.recipes_dirty.set(true);
                 self.pantry_dirty.set(true);
             }
@@ -528,16 +1630,50 @@ impl SimpleComponent for App {
         let tab_name = match self.tab {
             Tab::Recipes => "recipes",
             Tab::Pantry => "pantry",
+            Tab::Planner => "planner",
+            Tab::Shopping => "shopping",
             Tab::Settings => "settings",
         };
         widgets.main_stack.set_visible_child_name(tab_name);
+        // Keep the sidebar in step when a tab is opened from elsewhere
+        if widgets.nav_list.selected_row().map(|r| r.widget_name()).as_deref() != Some(tab_name) {
+            select_row_by_name(&widgets.nav_list, tab_name);
+        }
+
+        // Undo/redo buttons
+        widgets.undo_btn.set_sensitive(self.history.can_undo());
+        widgets
+            .undo_btn
+            .set_tooltip_text(Some(&crate::i18n::fmt_undo_tooltip(self.history.undo_label())));
+        widgets.redo_btn.set_sensitive(self.history.can_redo());
+        widgets
+            .redo_btn
+            .set_tooltip_text(Some(&crate::i18n::fmt_redo_tooltip(self.history.redo_label())));
+
+        // Show queued notifications
+        let queued: Vec<ToastRequest> = self.pending_toasts.borrow_mut().drain(..).collect();
+        for request in queued {
+            crate::toasts::present(&widgets.toast_overlay, &widgets.window, request, &sender);
+        }
 
         // Rebuild recipe list if dirty
         if self.recipes_dirty.get() {
             crate::recipes::populate_recipe_list(
                 &widgets.recipe_list,
                 &self.dm,
+                &self.data_dir,
                 &self.recipe_search,
+                self.recipe_sort,
+                &self.cooking_log,
+                &self.expiring_soon(),
+                &self.recipe_filter,
+                self.recipe_grouped,
+                &sender,
+            );
+            crate::recipes::sync_recipe_filters(
+                &widgets.recipe_filter_panel,
+                &self.dm,
+                &self.recipe_filter,
                 &sender,
             );
             self.recipes_dirty.set(false);
@@ -553,7 +1689,11 @@ impl SimpleComponent for App {
                 crate::recipes::update_recipe_detail(
                     &widgets.recipe_detail,
                     &self.dm,
+                    &self.data_dir,
                     title,
+                    self.recipe_servings.get(title).copied(),
+                    self.shopping.contains(title),
+                    self.cooking_log.stats(title),
                     &sender,
                 );
             } else {
@@ -569,13 +1709,32 @@ impl SimpleComponent for App {
                 &self.dm,
                 &self.ingredient_search,
                 &self.category_filter,
+                &self.tag_filter,
                 self.in_stock_only,
+                &self.pantry_details,
+                self.expiring_only,
+                self.group_by_location,
+                &sender,
+            );
+            crate::pantry::populate_pantry_filters(
+                &widgets.pantry_filter,
+                &self.dm,
+                &self.ingredient_search,
+                self.in_stock_only,
+                &self.category_filter,
+                &self.tag_filter,
                 &sender,
             );
-            // Sync in-stock switch
+            // Sync filter switches
             if widgets.in_stock_switch.is_active() != self.in_stock_only {
                 widgets.in_stock_switch.set_active(self.in_stock_only);
             }
+            if widgets.expiring_switch.is_active() != self.expiring_only {
+                widgets.expiring_switch.set_active(self.expiring_only);
+            }
+            if widgets.location_switch.is_active() != self.group_by_location {
+                widgets.location_switch.set_active(self.group_by_location);
+            }
             self.pantry_dirty.set(false);
             if let Some(ref name) = self.selected_ingredient {
                 select_row_by_name(&widgets.pantry_list, name);
@@ -589,6 +1748,7 @@ impl SimpleComponent for App {
                     &widgets.ingredient_detail,
                     &self.dm,
                     name,
+                    &self.pantry_details,
                     &sender,
                 );
             } else {
@@ -597,26 +1757,507 @@ impl SimpleComponent for App {
             self.ingredient_detail_dirty.set(false);
         }
 
+        // Rebuild the planner while it is visible, since coverage follows the pantry
+        if self.planner_dirty.get() || self.tab == Tab::Planner {
+            crate::planner::update_planner_view(
+                &widgets.planner_recipe_list,
+                &widgets.planner_content,
+                &self.dm,
+                &self.data_dir,
+                &self.meal_plan,
+                self.planner_week,
+                &sender,
+            );
+            self.planner_dirty.set(false);
+        }
+
+        // Rebuild shopping tab if dirty
+        if self.shopping_dirty.get() {
+            crate::shopping::update_shopping_view(&widgets.shopping_content, &self.shopping, &sender);
+            self.shopping_dirty.set(false);
+        }
+
         // ── Open pending dialogs (need widget references for parent window) ───
         if self.pending_add_recipe.get() {
             self.pending_add_recipe.set(false);
-            open_add_recipe_dialog(&widgets.window, &self.dm, sender.clone());
+            open_add_recipe_dialog(&widgets.window, &self.dm, None, sender.clone());
+        }
+        if self.pending_import_recipe.get() {
+            self.pending_import_recipe.set(false);
+            crate::dialogs::show_import_recipe_dialog(&widgets.window, sender.clone());
+        }
+        if let Some(imported) = self.pending_imported_recipe.borrow_mut().take() {
+            open_add_recipe_dialog(&widgets.window, &self.dm, Some(imported), sender.clone());
+        }
+        let unmatched = std::mem::take(&mut *self.pending_unmatched.borrow_mut());
+        if !unmatched.is_empty() {
+            let window = &widgets.window;
+            crate::dialogs::show_create_ingredients_dialog(window, unmatched, sender.clone());
+        }
+        if let Some(title) = self.pending_export_recipe.borrow_mut().take() {
+            if let Some(dm) = &self.dm {
+                if let Some(recipe) = dm.borrow().get_recipe(&title) {
+                    let window = &widgets.window;
+                    crate::dialogs::show_export_recipe_dialog(window, recipe, sender.clone());
+                }
+            }
+        }
+        if let Some((name, blocks)) = self.pending_export_pdf.borrow_mut().take() {
+            crate::dialogs::show_export_pdf_dialog(&widgets.window, &name, blocks, sender.clone());
+        }
+        if let Some((title, blocks)) = self.pending_print.borrow_mut().take() {
+            if let Err(e) = crate::print::print_blocks(&widgets.window, &title, blocks) {
+                sender.input(AppMsg::ShowError {
+                    summary: crate::i18n::strings().print_failed.to_string(),
+                    details: e,
+                });
+            }
+        }
+        if self.pending_export_cookbook.get() {
+            self.pending_export_cookbook.set(false);
+            let tags = match &self.dm {
+                Some(dm) => {
+                    let dm = dm.borrow();
+                    crate::tags::all_tags(dm.get_all_recipes().iter().map(|r| &r.tags))
+                }
+                None => Vec::new(),
+            };
+            crate::dialogs::show_export_cookbook_dialog(&widgets.window, tags, sender.clone());
         }
         if let Some(title) = self.pending_edit_recipe.borrow_mut().take() {
             open_edit_recipe_dialog(&widgets.window, &self.dm, &title, sender.clone());
         }
+        if let Some(title) = self.pending_cook_recipe.borrow_mut().take() {
+            open_cooked_dialog(
+                &widgets.window,
+                &self.dm,
+                &title,
+                self.recipe_servings.get(&title).copied(),
+                sender.clone(),
+            );
+        }
+        if let Some(title) = self.pending_cook_mode.borrow_mut().take() {
+            open_cook_mode(
+                &widgets.window,
+                &self.dm,
+                &title,
+                self.recipe_servings.get(&title).copied(),
+            );
+        }
+        if self.pending_cooking_history.get() {
+            self.pending_cooking_history.set(false);
+            crate::dialogs::show_cooking_history_dialog(&widgets.window, self.cooking_log.history());
+        }
         if self.pending_add_ingredient.get() {
             self.pending_add_ingredient.set(false);
-            open_add_ingredient_dialog(&widgets.window, &self.dm, sender.clone());
+            open_add_ingredient_dialog(
+                &widgets.window,
+                &self.dm,
+                self.storage_locations(),
+                sender.clone(),
+            );
         }
         if let Some(name) = self.pending_edit_ingredient.borrow_mut().take() {
-            open_edit_ingredient_dialog(&widgets.window, &self.dm, &name, sender.clone());
+            open_edit_ingredient_dialog(
+                &widgets.window,
+                &self.dm,
+                &name,
+                &self.pantry_details,
+                sender.clone(),
+            );
+        }
+        if self.pending_storage_locations.get() {
+            self.pending_storage_locations.set(false);
+            crate::dialogs::show_storage_locations_dialog(
+                &widgets.window,
+                self.storage_locations(),
+                sender.clone(),
+            );
+        }
+        if self.pending_import_pantry.get() {
+            self.pending_import_pantry.set(false);
+            crate::dialogs::show_import_pantry_file_dialog(&widgets.window, sender.clone());
+        }
+        if let Some(rows) = self.pending_pantry_preview.borrow_mut().take() {
+            crate::dialogs::show_pantry_import_dialog(&widgets.window, rows, sender.clone());
+        }
+        if let Some(rows) = self.pending_export_pantry.borrow_mut().take() {
+            crate::dialogs::show_export_pantry_dialog(&widgets.window, rows, sender.clone());
         }
     }
 }
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
+impl App {
+    /// The data of the current background load, once it has arrived, with its
+    /// unit data and thresholds made the global ones.
+    fn take_loaded(&mut self) -> Option<Result<DataManager, String>> {
+        let loaded = self.pending_dm.take()?.try_recv().ok()?;
+        crate::units::set_ingredient_units(loaded.units);
+        crate::low_stock::set_thresholds(loaded.thresholds);
+        Some(loaded.dm)
+    }
+
+    /// Record that we are writing `files` in the data directory, so the watcher
+    /// does not report our own save as an external change.
+    fn note_own_write(&mut self, files: impl IntoIterator<Item = PathBuf>) {
+        let until = Instant::now() + OWN_WRITE_GRACE;
+        self.own_writes.extend(files.into_iter().map(|file| (file, until)));
+    }
+
+    /// The pantry files the engine writes for this device.
+    fn pantry_files(&self) -> Vec<PathBuf> {
+        let device_id = UserSettings::effective_device_id();
+        vec![
+            self.data_dir.join("pantry.yaml"),
+            self.data_dir.join(format!("pantry.{}.yaml", device_id)),
+        ]
+    }
+
+    /// The file of the ingredient `name`.
+    fn ingredient_file(&self, name: &str) -> PathBuf {
+        match &self.dm {
+            Some(dm) => ingredient_path(&self.data_dir, &dm.borrow(), name),
+            None => {
+                let stem = janus_engine::slugify(name);
+                self.data_dir.join("ingredients").join(format!("{}.yaml", stem))
+            }
+        }
+    }
+
+    /// The files saving or deleting the ingredient `name` writes: its own and
+    /// the pantry files.
+    fn ingredient_files(&self, name: &str) -> Vec<PathBuf> {
+        let mut files = self.pantry_files();
+        files.push(self.ingredient_file(name));
+        files
+    }
+
+    /// The file of the recipe `title`, if it exists.
+    fn recipe_files(&self, title: &str) -> Vec<PathBuf> {
+        self.dm
+            .as_ref()
+            .and_then(|dm| crate::frontmatter::recipe_path(&self.data_dir, &dm.borrow(), title))
+            .into_iter()
+            .collect()
+    }
+
+    /// The files undoing or redoing `change` writes.
+    fn change_files(&self, change: &Change) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = [&change.before, &change.after]
+            .into_iter()
+            .flat_map(|snapshot| snapshot.files(&self.data_dir))
+            .collect();
+        if change.before.touches_pantry() {
+            files.extend(self.pantry_files());
+        }
+        files
+    }
+
+    /// `title` laid out for printing at the serving count chosen in the recipe view.
+    fn recipe_print_blocks(&self, title: &str) -> Option<Vec<PrintBlock>> {
+        let dm = self.dm.as_ref()?.borrow();
+        let recipe = dm.get_recipe(title)?;
+        let servings = self.recipe_servings.get(title).copied();
+        Some(crate::print::recipe_blocks(&dm, recipe, servings))
+    }
+
+    /// Whether the data file `file` may be written. One that failed to load is
+    /// left alone until it loads again, so its contents aren't lost.
+    fn may_save(&self, file: &str, sender: &ComponentSender<Self>) -> bool {
+        if !self.unreadable.contains(file) {
+            return true;
+        }
+        sender.input(AppMsg::ShowError {
+            summary: crate::i18n::fmt_not_overwritten(file),
+            details: crate::i18n::strings().unreadable_file_details.to_string(),
+        });
+        false
+    }
+
+    /// Follow a recipe rename in the shopping list, meal plan and cooking log.
+    /// Each is re-read first so changes synced from other devices are kept.
+    fn follow_recipe_rename(&mut self, old: &str, new: &str, sender: &ComponentSender<Self>) {
+        // A file that fails to load has been reported and is left alone
+        if self.load_shopping(sender) && self.shopping.contains(old) {
+            self.shopping.rename_recipe(old, new);
+            self.save_shopping(sender);
+        }
+        if self.load_meal_plan(sender) && self.meal_plan.contains_recipe(old) {
+            self.meal_plan.rename_recipe(old, new);
+            self.save_meal_plan(sender);
+        }
+        if self.load_cooking_log(sender) && self.cooking_log.stats(old).is_some() {
+            self.note_own_write([self.data_dir.join(crate::cooking::COOKING_LOG_FILE)]);
+            self.cooking_log.rename_recipe(old, new);
+            if let Err(e) = self.cooking_log.save(&self.data_dir) {
+                sender.input(AppMsg::ShowError {
+                    summary: crate::i18n::strings().cooking_log_save_failed.to_string(),
+                    details: e,
+                });
+            }
+        }
+    }
+
+    /// Write the shopping list to the data directory and refresh its tab.
+    fn save_shopping(&mut self, sender: &ComponentSender<Self>) {
+        self.shopping_dirty.set(true);
+        if !self.may_save(crate::shopping_list::SHOPPING_FILE, sender) {
+            return;
+        }
+        self.note_own_write([self.data_dir.join(crate::shopping_list::SHOPPING_FILE)]);
+        if let Err(e) = self.shopping.save(&self.data_dir) {
+            sender.input(AppMsg::ShowError {
+                summary: crate::i18n::strings().shopping_save_failed.to_string(),
+                details: e,
+            });
+        }
+    }
+
+    /// Regenerate the shopping list from the basket and pantry, then save it.
+    fn rebuild_shopping(&mut self, sender: &ComponentSender<Self>) {
+        if let Some(dm) = &self.dm {
+            self.shopping.regenerate(&dm.borrow());
+        }
+        self.save_shopping(sender);
+    }
+
+    /// Re-read the shopping list from the data directory; `false` if it can't be read.
+    fn load_shopping(&mut self, sender: &ComponentSender<Self>) -> bool {
+        let loaded = match ShoppingList::load(&self.data_dir) {
+            Ok(list) => {
+                self.shopping = list;
+                self.unreadable.remove(crate::shopping_list::SHOPPING_FILE);
+                true
+            }
+            Err(e) => {
+                // Keep what we had, and leave the file for the user to fix
+                self.unreadable.insert(crate::shopping_list::SHOPPING_FILE);
+                sender.input(AppMsg::ShowError {
+                    summary: crate::i18n::fmt_load_failed(crate::shopping_list::SHOPPING_FILE),
+                    details: e,
+                });
+                false
+            }
+        };
+        self.shopping_dirty.set(true);
+        loaded
+    }
+
+    /// Write the meal plan to the data directory and refresh the planner.
+    fn save_meal_plan(&mut self, sender: &ComponentSender<Self>) {
+        self.planner_dirty.set(true);
+        if !self.may_save(crate::meal_plan::MEAL_PLAN_FILE, sender) {
+            return;
+        }
+        self.note_own_write([self.data_dir.join(crate::meal_plan::MEAL_PLAN_FILE)]);
+        if let Err(e) = self.meal_plan.save(&self.data_dir) {
+            sender.input(AppMsg::ShowError {
+                summary: crate::i18n::strings().meal_plan_save_failed.to_string(),
+                details: e,
+            });
+        }
+    }
+
+    /// Re-read the meal plan from the data directory; `false` if it can't be read.
+    fn load_meal_plan(&mut self, sender: &ComponentSender<Self>) -> bool {
+        let loaded = match MealPlan::load(&self.data_dir) {
+            Ok(plan) => {
+                self.meal_plan = plan;
+                self.unreadable.remove(crate::meal_plan::MEAL_PLAN_FILE);
+                true
+            }
+            Err(e) => {
+                // Keep what we had, and leave the file for the user to fix
+                self.unreadable.insert(crate::meal_plan::MEAL_PLAN_FILE);
+                sender.input(AppMsg::ShowError {
+                    summary: crate::i18n::fmt_load_failed(crate::meal_plan::MEAL_PLAN_FILE),
+                    details: e,
+                });
+                false
+            }
+        };
+        self.planner_dirty.set(true);
+        loaded
+    }
+
+    /// Remember the Pantry tab's category and tag filters across restarts.
+    fn save_pantry_filters(&mut self) {
+        {
+            let mut s = self.settings.borrow_mut();
+            s.pantry_categories = self.category_filter.clone();
+            s.pantry_tags = self.tag_filter.clone();
+            s.save();
+        }
+        self.pantry_dirty.set(true);
+    }
+
+    /// Storage locations offered in the ingredient dialog.
+    fn storage_locations(&self) -> Vec<String> {
+        self.pantry_details.locations_or(crate::i18n::default_storage_locations())
+    }
+
+    /// Write the pantry details to the data directory, dropping those of items
+    /// that have left the pantry.
+    fn save_pantry_details(&mut self, sender: &ComponentSender<Self>) {
+        self.pantry_dirty.set(true);
+        self.recipes_dirty.set(true);
+        if let Some(dm) = &self.dm {
+            self.pantry_details.prune(&dm.borrow());
+        }
+        if !self.may_save(crate::pantry_details::PANTRY_DETAILS_FILE, sender) {
+            return;
+        }
+        self.note_own_write([self.data_dir.join(crate::pantry_details::PANTRY_DETAILS_FILE)]);
+        if let Err(e) = self.pantry_details.save(&self.data_dir) {
+            sender.input(AppMsg::ShowError {
+                summary: crate::i18n::strings().pantry_details_save_failed.to_string(),
+                details: e,
+            });
+        }
+    }
+
+    /// Re-read the pantry details from the data directory; `false` if it can't be read.
+    fn load_pantry_details(&mut self, sender: &ComponentSender<Self>) -> bool {
+        let file = crate::pantry_details::PANTRY_DETAILS_FILE;
+        let loaded = match PantryDetails::load(&self.data_dir) {
+            Ok(details) => {
+                self.pantry_details = details;
+                self.unreadable.remove(file);
+                true
+            }
+            Err(e) => {
+                // Keep what we had, and leave the file for the user to fix
+                self.unreadable.insert(file);
+                sender.input(AppMsg::ShowError {
+                    summary: crate::i18n::fmt_load_failed(file),
+                    details: e,
+                });
+                false
+            }
+        };
+        self.pantry_dirty.set(true);
+        self.recipes_dirty.set(true);
+        loaded
+    }
+
+    /// Slugs of the pantry items that expire soon, for the "use it up" sort.
+    fn expiring_soon(&self) -> BTreeSet<String> {
+        let today = chrono::Local::now().date_naive();
+        self.dm
+            .as_ref()
+            .map(|dm| self.pantry_details.expiring_soon(&dm.borrow(), today))
+            .unwrap_or_default()
+    }
+
+    /// Re-read the cooking log from the data directory; `false` if it can't be read.
+    fn load_cooking_log(&mut self, sender: &ComponentSender<Self>) -> bool {
+        let loaded = match CookingLog::load(&self.data_dir) {
+            Ok(log) => {
+                self.cooking_log = log;
+                self.unreadable.remove(crate::cooking::COOKING_LOG_FILE);
+                true
+            }
+            Err(e) => {
+                // Keep what we had, and leave the file for the user to fix
+                self.unreadable.insert(crate::cooking::COOKING_LOG_FILE);
+                sender.input(AppMsg::ShowError {
+                    summary: crate::i18n::fmt_load_failed(crate::cooking::COOKING_LOG_FILE),
+                    details: e,
+                });
+                false
+            }
+        };
+        self.recipes_dirty.set(true);
+        loaded
+    }
+
+    /// Snapshot of the ingredient `name` for the undo history.
+    fn ingredient_snapshot(&self, name: &str) -> Snapshot {
+        match &self.dm {
+            Some(dm) => {
+                Snapshot::ingredient(&dm.borrow(), &self.data_dir, &self.pantry_details, name)
+            }
+            None => Snapshot::Ingredient(None),
+        }
+    }
+
+    /// Undo (or redo) the most recent data mutation through the `DataManager`.
+    fn step_history(&mut self, undo: bool, sender: &ComponentSender<Self>) {
+        let change = if undo { self.history.undo_change() } else { self.history.redo_change() };
+        let files = change.map(|c| self.change_files(c)).unwrap_or_default();
+        self.note_own_write(files);
+        let Some(dm) = self.dm.clone() else {
+            return;
+        };
+        let details = self.pantry_details.clone();
+        let result = if undo {
+            self.history.undo(&mut dm.borrow_mut(), &self.data_dir, &mut self.pantry_details)
+        } else {
+            self.history.redo(&mut dm.borrow_mut(), &self.data_dir, &mut self.pantry_details)
+        };
+        if self.pantry_details != details {
+            self.save_pantry_details(sender);
+        }
+        match result {
+            Ok(Some(label)) => {
+                let dm = dm.borrow();
+                if self
+                    .selected_recipe
+                    .as_deref()
+                    .is_some_and(|t| dm.get_recipe(t).is_none())
+                {
+                    self.selected_recipe = None;
+                }
+                if self
+                    .selected_ingredient
+                    .as_deref()
+                    .is_some_and(|n| dm.get_ingredient(n).is_none())
+                {
+                    self.selected_ingredient = None;
+                }
+                self.recipes_dirty.set(true);
+                self.pantry_dirty.set(true);
+                self.recipe_detail_dirty.set(true);
+                self.ingredient_detail_dirty.set(true);
+                sender.input(AppMsg::ShowToast(if undo {
+                    crate::i18n::fmt_undone(&label)
+                } else {
+                    crate::i18n::fmt_redone(&label)
+                }));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                sender.input(AppMsg::ShowError {
+                    summary: crate::i18n::strings().history_failed.to_string(),
+                    details: e,
+                });
+            }
+        }
+    }
+}
+
+/// Read the data directory on a background thread. `done` runs once the result
+/// is waiting in the returned channel.
+fn spawn_load(
+    data_dir: PathBuf,
+    done: impl FnOnce() + Send + 'static,
+) -> mpsc::Receiver<LoadedData> {
+    let (tx, rx) = mpsc::channel();
+    let device_id = UserSettings::effective_device_id();
+    std::thread::spawn(move || {
+        let loaded = LoadedData {
+            dm: DataManager::new(&data_dir, device_id).map_err(|e| error_chain(&e)),
+            units: crate::units::load_ingredient_units(&data_dir),
+            thresholds: crate::low_stock::load_thresholds(&data_dir),
+        };
+        let _ = tx.send(loaded);
+        done();
+    });
+    rx
+}
+
 /// Select the list box row whose widget name matches `name`.
 fn select_row_by_name(list: &gtk::ListBox, name: &str) {
     let mut i = 0;
@@ -666,12 +2307,44 @@ fn build_ingredient_infos(dm: &DataManager) -> Vec<IngredientInfo> {
         .collect()
 }
 
-pub fn open_add_recipe_dialog(window: &adw::ApplicationWindow, dm: &Option<Rc<RefCell<DataManager>>>, sender: ComponentSender<App>) {
+/// Units already used in the data directory (recipes and pantry), accepted by the
+/// quantity parser in addition to the built-in ones.
+fn build_known_units(dm: &DataManager) -> Vec<String> {
+    let mut units: Vec<String> = dm
+        .get_all_recipes()
+        .iter()
+        .flat_map(|r| r.ingredients.iter())
+        .filter_map(|i| i.quantity_type.clone())
+        .chain(
+            dm.get_all_ingredients()
+                .into_iter()
+                .filter_map(|ing| dm.get_pantry_item(&ing.name))
+                .map(|p| p.quantity_type.clone()),
+        )
+        .filter(|u| !u.trim().is_empty())
+        .collect();
+    units.sort();
+    units.dedup();
+    units
+}
+
+/// Every tag used by a recipe, sorted and without duplicates.
+/// Open the recipe dialog for a new recipe, pre-filled from `imported` if given.
+pub fn open_add_recipe_dialog(
+    window: &adw::ApplicationWindow,
+    dm: &Option<Rc<RefCell<DataManager>>>,
+    imported: Option<ImportedRecipe>,
+    sender: ComponentSender<App>,
+) {
     let infos = dm
         .as_ref()
         .map(|d| build_ingredient_infos(&d.borrow()))
         .unwrap_or_default();
-    crate::dialogs::show_recipe_dialog(window, infos, None, sender);
+    let units = dm
+        .as_ref()
+        .map(|d| build_known_units(&d.borrow()))
+        .unwrap_or_default();
+    crate::dialogs::show_recipe_dialog(window, infos, units, None, imported, sender);
 }
 
 pub fn open_edit_recipe_dialog(
@@ -692,26 +2365,78 @@ pub fn open_edit_recipe_dialog(
             .as_ref()
             .map(|d| build_ingredient_infos(&d.borrow()))
             .unwrap_or_default();
-        crate::dialogs::show_recipe_dialog(window, infos, Some(recipe), sender);
+        let units = dm
+            .as_ref()
+            .map(|d| build_known_units(&d.borrow()))
+            .unwrap_or_default();
+        crate::dialogs::show_recipe_dialog(window, infos, units, Some(recipe), None, sender);
+    }
+}
+
+/// Open the "Cooked it" review sheet for `title` at the chosen serving count.
+pub fn open_cooked_dialog(
+    window: &adw::ApplicationWindow,
+    dm: &Option<Rc<RefCell<DataManager>>>,
+    title: &str,
+    servings: Option<u32>,
+    sender: ComponentSender<App>,
+) {
+    let Some(d) = dm else {
+        return;
+    };
+    let dm = d.borrow();
+    let Some(recipe) = dm.get_recipe(title) else {
+        return;
+    };
+    let base = recipe.servings.unwrap_or(1).max(1);
+    let target = servings.unwrap_or(base).max(1);
+    let deductions = crate::cooking::planned_deductions(&dm, recipe, target as f64 / base as f64);
+    crate::dialogs::show_cooked_dialog(
+        window,
+        title,
+        servings.or(recipe.servings),
+        deductions,
+        sender,
+    );
+}
+
+pub fn open_cook_mode(
+    window: &adw::ApplicationWindow,
+    dm: &Option<Rc<RefCell<DataManager>>>,
+    title: &str,
+    servings: Option<u32>,
+) {
+    let Some(d) = dm else {
+        return;
+    };
+    let dm = d.borrow();
+    if let Some(recipe) = dm.get_recipe(title) {
+        crate::cook_mode::show_cook_mode(window, &dm, recipe, servings);
     }
 }
 
 pub fn open_add_ingredient_dialog(
     window: &adw::ApplicationWindow,
     dm: &Option<Rc<RefCell<DataManager>>>,
+    locations: Vec<String>,
     sender: ComponentSender<App>,
 ) {
     let categories = dm
         .as_ref()
         .map(|d| d.borrow().get_all_ingredient_categories())
         .unwrap_or_default();
-    crate::dialogs::show_ingredient_dialog(window, categories, None, None, sender);
+    let extras = PantryExtras {
+        locations,
+        ..PantryExtras::default()
+    };
+    crate::dialogs::show_ingredient_dialog(window, categories, None, None, extras, sender);
 }
 
 pub fn open_edit_ingredient_dialog(
     window: &adw::ApplicationWindow,
     dm: &Option<Rc<RefCell<DataManager>>>,
     name: &str,
+    details: &PantryDetails,
     sender: ComponentSender<App>,
 ) {
     let dm_borrow;
@@ -733,6 +2458,12 @@ pub fn open_edit_ingredient_dialog(
             categories,
             Some(ingredient),
             pantry_item.as_ref(),
+            PantryExtras {
+                best_before: details.best_before(name),
+                location: details.location(name).map(str::to_string),
+                locations: details.locations_or(crate::i18n::default_storage_locations()),
+                threshold: crate::low_stock::threshold(name),
+            },
             sender,
         );
     }
@@ -9,6 +9,15 @@ pub enum Theme {
     Dark,
 }
 
+/// Which units amounts are shown in. Spoons and pieces are shown as written.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum MeasurementSystem {
+    #[default]
+    Metric,
+    /// US customary (oz, lb, cups).
+    Imperial,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserSettings {
     #[serde(default)]
@@ -17,10 +26,17 @@ pub struct UserSettings {
     pub language: String,
     #[serde(default)]
     pub theme: Theme,
+    #[serde(default)]
+    pub measurement_system: MeasurementSystem,
     /// Stable per-device identifier used for per-device pantry files.
     /// Generated once on first run and persisted.
     #[serde(default)]
     pub device_id: Option<String>,
+    /// Categories and tags the Pantry tab is filtered by.
+    #[serde(default)]
+    pub pantry_categories: Vec<String>,
+    #[serde(default)]
+    pub pantry_tags: Vec<String>,
 }
 
 fn default_language() -> String {
@@ -33,7 +49,10 @@ impl Default for UserSettings {
             data_dir: None,
             language: "system".to_string(),
             theme: Theme::default(),
+            measurement_system: MeasurementSystem::default(),
             device_id: None,
+            pantry_categories: Vec::new(),
+            pantry_tags: Vec::new(),
         }
     }
 }
@@ -129,6 +148,7 @@ mod tests {
         assert_eq!(s.language, "system");
         assert_eq!(s.theme, Theme::System);
         assert!(s.data_dir.is_none());
+        assert_eq!(s.measurement_system, MeasurementSystem::Metric);
     }
 
     #[test]
@@ -137,12 +157,18 @@ mod tests {
             data_dir: Some("/tmp/test".to_string()),
             language: "de".to_string(),
             theme: Theme::Dark,
+            measurement_system: MeasurementSystem::Imperial,
             device_id: Some("kde-testabcd".to_string()),
+            pantry_categories: vec!["dairy".to_string()],
+            pantry_tags: vec!["vegan".to_string()],
         };
         let serialized = toml::to_string(&original).expect("serialize");
         let loaded: UserSettings = toml::from_str(&serialized).expect("deserialize");
         assert_eq!(loaded.data_dir, original.data_dir);
         assert_eq!(loaded.language, original.language);
         assert_eq!(loaded.theme, original.theme);
+        assert_eq!(loaded.measurement_system, original.measurement_system);
+        assert_eq!(loaded.pantry_categories, original.pantry_categories);
+        assert_eq!(loaded.pantry_tags, original.pantry_tags);
     }
 }
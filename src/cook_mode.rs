@@ -0,0 +1,297 @@
+/// Full-screen cook mode: one instruction step at a time in large type, with a
+/// checklist of the ingredients the step uses and a countdown button for every
+/// time the step mentions.
+///
+/// Space, → and Page Down go to the next step; ←, Backspace and Page Up go back;
+/// Escape leaves. Timers keep running while you move between steps and send a
+/// desktop notification when they run out.
+use crate::i18n;
+use crate::instructions::{
+    detect_timers, format_countdown, mentioned_ingredients, split_steps, TimerSpec,
+};
+use crate::markdown::inline_markup;
+use crate::recipes::ingredient_text;
+use crate::ui_constants::*;
+use janus_engine::{DataManager, Recipe};
+use libadwaita as adw;
+use relm4::gtk;
+use relm4::gtk::prelude::*;
+use relm4::gtk::{gdk, gio, glib, pango};
+use relm4::RelmWidgetExt;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// A running countdown; `None` while the timer is stopped.
+type TimerSource = Rc<RefCell<Option<glib::SourceId>>>;
+
+pub fn show_cook_mode(
+    parent: &impl IsA<gtk::Window>,
+    dm: &DataManager,
+    recipe: &Recipe,
+    servings: Option<u32>,
+) {
+    use adw::prelude::*;
+
+    let s = i18n::strings();
+    let steps = split_steps(&recipe.instructions);
+    if steps.is_empty() {
+        return;
+    }
+    let base = recipe.servings.unwrap_or(1).max(1);
+    let factor = servings.unwrap_or(base).max(1) as f64 / base as f64;
+
+    let window = adw::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title(&recipe.title)
+        .default_width(900)
+        .default_height(700)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    let window_title = adw::WindowTitle::new(&recipe.title, &i18n::fmt_step(1, steps.len()));
+    header.set_title_widget(Some(&window_title));
+    toolbar_view.add_top_bar(&header);
+
+    // Ingredients are matched by both their stored name and the name shown in
+    // the recipe, so translated display names still find their steps.
+    let stored: Vec<String> = recipe.ingredients.iter().map(|i| i.ingredient.clone()).collect();
+    let shown: Vec<String> = recipe
+        .ingredients
+        .iter()
+        .map(|i| dm.recipe_ingredient_display_name(i))
+        .collect();
+    let lines: Vec<String> = recipe
+        .ingredients
+        .iter()
+        .map(|i| ingredient_text(dm, i, factor))
+        .collect();
+
+    // Check buttons per ingredient across all steps, kept in sync
+    let checks: Rc<RefCell<Vec<Vec<gtk::CheckButton>>>> =
+        Rc::new(RefCell::new(vec![Vec::new(); lines.len()]));
+    let timers: Rc<RefCell<Vec<TimerSource>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let stack = gtk::Stack::new();
+    stack.set_transition_type(gtk::StackTransitionType::SlideLeftRight);
+    stack.set_vexpand(true);
+
+    for (n, step) in steps.iter().enumerate() {
+        let page = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING * 2);
+        page.set_valign(gtk::Align::Center);
+        page.set_margin_all(DEFAULT_MARGIN * 3);
+
+        let counter = gtk::Label::new(Some(&i18n::fmt_step(n + 1, steps.len())));
+        counter.add_css_class("dim-label");
+        counter.set_halign(gtk::Align::Start);
+        page.append(&counter);
+
+        let text = gtk::Label::new(None);
+        text.set_markup(&inline_markup(step, &[]));
+        let attrs = pango::AttrList::new();
+        attrs.insert(pango::AttrFloat::new_scale(2.0));
+        text.set_attributes(Some(&attrs));
+        text.set_wrap(true);
+        text.set_wrap_mode(pango::WrapMode::WordChar);
+        text.set_xalign(0.0);
+        text.set_halign(gtk::Align::Start);
+        page.append(&text);
+
+        // Timers
+        let found = detect_timers(step);
+        if !found.is_empty() {
+            let timer_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+            for spec in found {
+                let (button, source) = build_timer_button(&recipe.title, spec);
+                timers.borrow_mut().push(source);
+                timer_box.append(&button);
+            }
+            page.append(&timer_box);
+        }
+
+        // Ingredient checklist
+        let mut used = mentioned_ingredients(step, &stored);
+        used.extend(mentioned_ingredients(step, &shown));
+        used.sort_unstable();
+        used.dedup();
+        if !used.is_empty() {
+            let heading = gtk::Label::new(Some(s.step_ingredients_heading));
+            heading.add_css_class("heading");
+            heading.set_halign(gtk::Align::Start);
+            page.append(&heading);
+
+            for idx in used {
+                let check = gtk::CheckButton::with_label(&lines[idx]);
+                {
+                    let checks_sync = Rc::clone(&checks);
+                    check.connect_toggled(move |c| {
+                        // Only changes state when it differs, so this doesn't recurse
+                        for other in &checks_sync.borrow()[idx] {
+                            other.set_active(c.is_active());
+                        }
+                    });
+                }
+                checks.borrow_mut()[idx].push(check.clone());
+                page.append(&check);
+            }
+        }
+
+        let clamp = adw::Clamp::new();
+        clamp.set_maximum_size(1000);
+        clamp.set_child(Some(&page));
+        let scroll = gtk::ScrolledWindow::new();
+        scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scroll.set_child(Some(&clamp));
+        stack.add_named(&scroll, Some(&format!("step-{}", n)));
+    }
+    toolbar_view.set_content(Some(&stack));
+
+    // ── Navigation ────────────────────────────────────────────────────────────
+    let prev_btn = gtk::Button::with_label(s.previous_step);
+    let next_btn = gtk::Button::with_label(s.next_step);
+    next_btn.add_css_class("suggested-action");
+    let hint = gtk::Label::new(Some(s.cook_mode_keys_hint));
+    hint.add_css_class("dim-label");
+    hint.add_css_class("caption");
+    hint.set_wrap(true);
+
+    let bottom = gtk::CenterBox::new();
+    bottom.set_margin_all(DEFAULT_MARGIN);
+    bottom.set_start_widget(Some(&prev_btn));
+    bottom.set_center_widget(Some(&hint));
+    bottom.set_end_widget(Some(&next_btn));
+    toolbar_view.add_bottom_bar(&bottom);
+
+    let total = steps.len();
+    let current = Rc::new(Cell::new(0usize));
+    let go: Rc<dyn Fn(isize)> = {
+        let stack = stack.clone();
+        let window = window.clone();
+        let window_title = window_title.clone();
+        let prev_btn = prev_btn.clone();
+        let next_btn = next_btn.clone();
+        let current = Rc::clone(&current);
+        Rc::new(move |delta: isize| {
+            let target = current.get() as isize + delta;
+            if target >= total as isize {
+                window.close();
+                return;
+            }
+            let target = target.max(0) as usize;
+            current.set(target);
+            stack.set_visible_child_name(&format!("step-{}", target));
+            window_title.set_subtitle(&i18n::fmt_step(target + 1, total));
+            prev_btn.set_sensitive(target > 0);
+            next_btn.set_label(if target + 1 == total {
+                i18n::strings().finish_cooking
+            } else {
+                i18n::strings().next_step
+            });
+        })
+    };
+    go(0);
+    {
+        let go = Rc::clone(&go);
+        prev_btn.connect_clicked(move |_| go(-1));
+    }
+    {
+        let go = Rc::clone(&go);
+        next_btn.connect_clicked(move |_| go(1));
+    }
+
+    // Captured before the focused widget sees the key, so Space always turns
+    // the page instead of activating a button.
+    let keys = gtk::EventControllerKey::new();
+    keys.set_propagation_phase(gtk::PropagationPhase::Capture);
+    {
+        let go = Rc::clone(&go);
+        let window = window.clone();
+        keys.connect_key_pressed(move |_, key, _, _| {
+            match key {
+                gdk::Key::space | gdk::Key::Right | gdk::Key::Page_Down => go(1),
+                gdk::Key::Left | gdk::Key::BackSpace | gdk::Key::Page_Up => go(-1),
+                gdk::Key::Escape => window.close(),
+                _ => return glib::Propagation::Proceed,
+            }
+            glib::Propagation::Stop
+        });
+    }
+    window.add_controller(keys);
+
+    window.connect_close_request(move |_| {
+        for source in timers.borrow().iter() {
+            if let Some(id) = source.borrow_mut().take() {
+                id.remove();
+            }
+        }
+        glib::Propagation::Proceed
+    });
+
+    window.set_content(Some(&toolbar_view));
+    window.fullscreen();
+    window.present();
+}
+
+/// A button that counts `spec` down when clicked and stops it when clicked
+/// again. Returns the button and its running source so closing cook mode can
+/// stop it.
+fn build_timer_button(recipe_title: &str, spec: TimerSpec) -> (gtk::Button, TimerSource) {
+    let s = i18n::strings();
+    let idle_label = format!("⏲ {}", spec.label);
+    let button = gtk::Button::with_label(&idle_label);
+    button.add_css_class("pill");
+    button.set_tooltip_text(Some(s.timer_start_tooltip));
+
+    let source: TimerSource = Rc::new(RefCell::new(None));
+    let recipe_title = recipe_title.to_string();
+    {
+        let source = Rc::clone(&source);
+        button.connect_clicked(move |btn| {
+            if let Some(id) = source.borrow_mut().take() {
+                id.remove();
+                btn.set_label(&idle_label);
+                btn.remove_css_class("suggested-action");
+                btn.set_tooltip_text(Some(i18n::strings().timer_start_tooltip));
+                return;
+            }
+
+            let remaining = Rc::new(Cell::new(spec.seconds));
+            btn.set_label(&format_countdown(spec.seconds));
+            btn.remove_css_class("success");
+            btn.add_css_class("suggested-action");
+            btn.set_tooltip_text(Some(i18n::strings().timer_cancel_tooltip));
+
+            let btn_tick = btn.clone();
+            let source_tick = Rc::clone(&source);
+            let title = recipe_title.clone();
+            let label = spec.label.clone();
+            let id = glib::timeout_add_seconds_local(1, move || {
+                let left = remaining.get().saturating_sub(1);
+                remaining.set(left);
+                if left > 0 {
+                    btn_tick.set_label(&format_countdown(left));
+                    return glib::ControlFlow::Continue;
+                }
+                // The source ends by returning Break; drop the handle without removing it
+                source_tick.borrow_mut().take();
+                btn_tick.set_label(i18n::strings().timer_done);
+                btn_tick.remove_css_class("suggested-action");
+                btn_tick.add_css_class("success");
+                btn_tick.set_tooltip_text(Some(i18n::strings().timer_start_tooltip));
+                notify_timer_done(&btn_tick, &title, &label);
+                glib::ControlFlow::Break
+            });
+            *source.borrow_mut() = Some(id);
+        });
+    }
+    (button, source)
+}
+
+fn notify_timer_done(widget: &gtk::Button, recipe_title: &str, timer: &str) {
+    widget.display().beep();
+    let notification = gio::Notification::new(i18n::strings().timer_done);
+    notification.set_body(Some(&i18n::fmt_timer_done(recipe_title, timer)));
+    notification.set_priority(gio::NotificationPriority::High);
+    relm4::main_application().send_notification(None, &notification);
+}
@@ -0,0 +1,233 @@
+/// "Cooked it": deducting a cooked recipe from the pantry, and the cooking log.
+///
+/// The review sheet is pre-filled from `planned_deductions`; the user can adjust
+/// each amount before the deductions are applied. Every confirmed cook is
+/// added to `cooking_log.yaml` in the data directory, which feeds the "last
+/// cooked" captions, the cook-based sort orders and the history view.
+use crate::quantity::{parse_amount, round_for_unit};
+use crate::units::{convert_with, ingredient_units};
+use chrono::NaiveDate;
+use janus_engine::{slugify, DataManager, Recipe};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// File name of the cooking log inside the data directory.
+pub const COOKING_LOG_FILE: &str = "cooking_log.yaml";
+
+/// How much of a pantry item a cook uses up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Consume {
+    /// This amount, in the pantry item's unit.
+    Amount(f64),
+    /// All of it: the item leaves the pantry.
+    All,
+}
+
+/// One line of the review sheet, pre-filled from the recipe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deduction {
+    pub ingredient: String,
+    /// Whether the ingredient is in the pantry at all; others are shown for reference.
+    pub in_pantry: bool,
+    /// Pantry amount before cooking (`None` = not tracked).
+    pub have: Option<f64>,
+    /// Pantry unit; the amount to deduct is expressed in it.
+    pub unit: String,
+    /// Scaled recipe amount converted to `unit`, when the units allow it.
+    pub amount: Option<f64>,
+    /// Pre-selected for deduction (required ingredients that are in stock).
+    pub selected: bool,
+}
+
+/// Build the review sheet for cooking `recipe` at `factor` times the written amounts.
+///
+/// Ranges ("2-3") deduct their midpoint.
+pub fn planned_deductions(dm: &DataManager, recipe: &Recipe, factor: f64) -> Vec<Deduction> {
+    recipe
+        .ingredients
+        .iter()
+        .map(|ing| {
+            let pantry = dm
+                .is_in_pantry(&ing.ingredient)
+                .then(|| dm.get_pantry_item(&ing.ingredient))
+                .flatten();
+            let (have, unit) = pantry
+                .map(|p| (p.quantity, p.quantity_type.clone()))
+                .unwrap_or_default();
+            let amount = ing
+                .quantity
+                .as_deref()
+                .and_then(parse_amount)
+                .and_then(|a| {
+                    convert_with(
+                        a.value() * factor,
+                        ing.quantity_type.as_deref().unwrap_or(""),
+                        &unit,
+                        ingredient_units(&ing.ingredient),
+                    )
+                })
+                .filter(|_| have.is_some())
+                .map(|a| round_for_unit(a, Some(&unit)));
+            Deduction {
+                ingredient: ing.ingredient.clone(),
+                in_pantry: pantry.is_some(),
+                have,
+                unit,
+                amount,
+                selected: pantry.is_some() && !ing.optional,
+            }
+        })
+        .collect()
+}
+
+/// Pantry amount of `unit` left after `consume`.
+///
+/// `None` means the item is used up and leaves the pantry; `Some(None)` keeps an
+/// item whose amount is not tracked.
+pub fn remaining_after(have: Option<f64>, unit: &str, consume: Consume) -> Option<Option<f64>> {
+    match (have, consume) {
+        (_, Consume::All) => None,
+        (Some(have), Consume::Amount(used)) => {
+            let left = have - used;
+            (left > 1e-9).then(|| Some(round_for_unit(left, Some(unit))))
+        }
+        (None, Consume::Amount(_)) => Some(None),
+    }
+}
+
+/// One confirmed cook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CookEntry {
+    /// Local date, `YYYY-MM-DD`.
+    pub date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub servings: Option<u32>,
+}
+
+/// All cooks of one recipe.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RecipeLog {
+    /// Title at the time of the last cook, for display.
+    pub title: String,
+    #[serde(default)]
+    pub cooked: Vec<CookEntry>,
+}
+
+/// How often and how recently a recipe was cooked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookStats {
+    pub count: usize,
+    /// Most recent date, `YYYY-MM-DD`.
+    pub last: String,
+}
+
+/// One line of the history view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub date: String,
+    pub title: String,
+    pub servings: Option<u32>,
+}
+
+/// The cooking log, keyed by recipe slug so that devices adding cooks of
+/// different recipes touch different parts of the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CookingLog {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub recipes: BTreeMap<String, RecipeLog>,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for CookingLog {
+    fn default() -> Self {
+        CookingLog {
+            version: default_version(),
+            recipes: BTreeMap::new(),
+        }
+    }
+}
+
+impl CookingLog {
+    /// Load the log from `data_dir`. A missing file is an empty log.
+    pub fn load(data_dir: &Path) -> Result<Self, String> {
+        let path = data_dir.join(COOKING_LOG_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                serde_yaml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CookingLog::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join(COOKING_LOG_FILE);
+        let text = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
+        crate::utils::write_atomically(&path, &text)
+    }
+
+    /// Append a cook of `title` on `date` (`YYYY-MM-DD`).
+    pub fn record(&mut self, title: &str, date: &str, servings: Option<u32>) {
+        let log = self.recipes.entry(slugify(title)).or_default();
+        log.title = title.to_string();
+        log.cooked.push(CookEntry {
+            date: date.to_string(),
+            servings,
+        });
+    }
+
+    /// Count and most recent date for `title`; `None` if it was never cooked.
+    pub fn stats(&self, title: &str) -> Option<CookStats> {
+        let log = self.recipes.get(&slugify(title))?;
+        let last = log.cooked.iter().map(|c| c.date.as_str()).max()?;
+        Some(CookStats {
+            count: log.cooked.len(),
+            last: last.to_string(),
+        })
+    }
+
+    /// Follow a recipe rename, merging into an existing entry for the new title.
+    pub fn rename_recipe(&mut self, old: &str, new: &str) {
+        let (old_slug, new_slug) = (slugify(old), slugify(new));
+        if old_slug == new_slug {
+            if let Some(log) = self.recipes.get_mut(&new_slug) {
+                log.title = new.to_string();
+            }
+            return;
+        }
+        if let Some(log) = self.recipes.remove(&old_slug) {
+            let target = self.recipes.entry(new_slug).or_default();
+            target.title = new.to_string();
+            target.cooked.extend(log.cooked);
+        }
+    }
+
+    /// Every cook, most recent first.
+    pub fn history(&self) -> Vec<HistoryEntry> {
+        let mut entries: Vec<HistoryEntry> = self
+            .recipes
+            .values()
+            .flat_map(|log| {
+                log.cooked.iter().map(|c| HistoryEntry {
+                    date: c.date.clone(),
+                    title: log.title.clone(),
+                    servings: c.servings,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.title.cmp(&b.title)));
+        entries
+    }
+}
+
+/// Whole days from `date` (`YYYY-MM-DD`) to `today`; `None` if `date` does not parse.
+pub fn days_since(date: &str, today: NaiveDate) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some((today - date).num_days())
+}
@@ -0,0 +1,480 @@
+/// Cooklang (`.cook`) import and export.
+///
+/// Cooklang marks ingredients, cookware and timers inline in the steps:
+/// `Boil @potatoes{2%kg} in a #pot{} for ~{20%minutes}`. On import the marks
+/// become plain text in the instructions and the ingredients become
+/// `RecipeIngredient`s; metadata comes from YAML frontmatter or `>> key: value`
+/// lines. Export writes the ingredients back into the first step mentioning them.
+use crate::dialogs::IngredientInfo;
+use crate::instructions::split_steps;
+use crate::quantity::{parse_amount, parse_quantity_with, Amount};
+use crate::recipe_import::{flush_steps, match_ingredient, unmatched_names, ImportedRecipe};
+use janus_engine::{Recipe, RecipeIngredient};
+use serde_yaml::{Mapping, Value};
+use std::path::Path;
+
+/// Image extensions looked for next to a `.cook` file.
+const IMAGE_EXTENSIONS: [&str; 4] = ["jpg", "jpeg", "png", "webp"];
+
+/// Import a Cooklang recipe. `title` is used when the file has no title
+/// metadata (usually the file name). Ingredients are matched against `known`
+/// and their quantities parsed with the extra `units`.
+pub fn parse_cooklang(
+    text: &str,
+    title: &str,
+    known: &[IngredientInfo],
+    units: &[String],
+) -> ImportedRecipe {
+    let text = strip_block_comments(text);
+    let (mut metadata, body) = frontmatter(&text);
+
+    let mut blocks: Vec<String> = Vec::new();
+    let mut steps: Vec<String> = Vec::new();
+    let mut step = String::new();
+    let mut marked: Vec<Mark> = Vec::new();
+
+    let flush_step = |step: &mut String, steps: &mut Vec<String>| {
+        if !step.trim().is_empty() {
+            steps.push(step.trim().to_string());
+        }
+        step.clear();
+    };
+
+    for line in body.lines() {
+        let line = strip_line_comment(line).trim();
+        if let Some(meta) = line.strip_prefix(">>") {
+            if let Some((key, value)) = meta.split_once(':') {
+                metadata.insert(
+                    Value::String(key.trim().to_lowercase()),
+                    Value::String(value.trim().to_string()),
+                );
+            }
+        } else if line.starts_with('=') {
+            flush_step(&mut step, &mut steps);
+            flush_steps(&mut blocks, &mut steps);
+            let name = line.trim_matches('=').trim();
+            if !name.is_empty() {
+                blocks.push(format!("## {}", name));
+            }
+        } else if let Some(note) = line.strip_prefix('>') {
+            flush_step(&mut step, &mut steps);
+            flush_steps(&mut blocks, &mut steps);
+            blocks.push(format!("> {}", note.trim()));
+        } else if line.is_empty() {
+            flush_step(&mut step, &mut steps);
+        } else {
+            if !step.is_empty() {
+                step.push(' ');
+            }
+            step.push_str(&parse_marks(line, &mut marked));
+        }
+    }
+    flush_step(&mut step, &mut steps);
+    flush_steps(&mut blocks, &mut steps);
+
+    let mut ingredients: Vec<RecipeIngredient> = Vec::new();
+    for mark in marked {
+        let ing = mark.into_ingredient(known, units);
+        merge_ingredient(&mut ingredients, ing);
+    }
+    let unmatched = unmatched_names(&ingredients, known);
+
+    let meta = |keys: &[&str]| keys.iter().find_map(|k| metadata.get(*k)).map(meta_text);
+    let minutes = |keys: &[&str]| meta(keys).and_then(|t| parse_minutes(&t));
+    let tags = match metadata.get("tags") {
+        Some(Value::Sequence(items)) => items.iter().map(meta_text).collect(),
+        Some(value) => meta_text(value).split(',').map(|t| t.trim().to_string()).collect(),
+        None => Vec::new(),
+    };
+    let tags: Vec<String> = tags.into_iter().filter(|t| !t.is_empty()).collect();
+
+    let recipe = Recipe {
+        title: meta(&["title"]).filter(|t| !t.is_empty()).unwrap_or_else(|| title.to_string()),
+        slug: String::new(),
+        file_stem: String::new(),
+        ingredients,
+        prep_time: minutes(&["prep time", "prep_time", "preptime"])
+            .or_else(|| minutes(&["time", "duration", "total time"])),
+        downtime: minutes(&["cook time", "cook_time", "cooktime"]),
+        servings: meta(&["servings", "serves", "yield"]).and_then(|s| {
+            s.split(|c: char| !c.is_ascii_digit()).find(|p| !p.is_empty())?.parse().ok()
+        }),
+        tags: (!tags.is_empty()).then_some(tags),
+        image: meta(&["image"]).filter(|i| !i.is_empty()),
+        instructions: blocks.join("\n\n"),
+    };
+    ImportedRecipe { recipe, image_file: None, unmatched }
+}
+
+/// Import the `.cook` file at `path`. An image with the same name next to it
+/// (Cooklang's convention) becomes the recipe image.
+pub fn import_cook_file(
+    path: &Path,
+    known: &[IngredientInfo],
+    units: &[String],
+) -> Result<ImportedRecipe, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let mut imported = parse_cooklang(&text, &stem, known, units);
+    imported.image_file =
+        IMAGE_EXTENSIONS.iter().map(|ext| path.with_extension(ext)).find(|p| p.is_file());
+    Ok(imported)
+}
+
+/// An ingredient as marked in the text.
+struct Mark {
+    name: String,
+    quantity: String,
+    unit: String,
+    note: Option<String>,
+    optional: bool,
+}
+
+impl Mark {
+    fn into_ingredient(self, known: &[IngredientInfo], units: &[String]) -> RecipeIngredient {
+        let text = format!("{} {}", self.quantity, self.unit);
+        let (quantity, quantity_type) = match parse_quantity_with(text.trim(), units) {
+            Ok(parsed) => (parsed.quantity, parsed.unit),
+            Err(_) => (non_empty(self.quantity), non_empty(self.unit)),
+        };
+        let ingredient =
+            match_ingredient(&self.name, known).map(|info| info.name.clone()).unwrap_or(self.name);
+        RecipeIngredient {
+            ingredient,
+            quantity,
+            quantity_type,
+            note: self.note,
+            optional: self.optional,
+        }
+    }
+}
+
+fn non_empty(s: String) -> Option<String> {
+    (!s.trim().is_empty()).then(|| s.trim().to_string())
+}
+
+/// Add `ing` to `ingredients`, summing it into an earlier use of the same
+/// ingredient and unit when both amounts are plain numbers.
+fn merge_ingredient(ingredients: &mut Vec<RecipeIngredient>, ing: RecipeIngredient) {
+    let earlier = ingredients.iter_mut().find(|e| {
+        e.ingredient == ing.ingredient
+            && e.quantity_type == ing.quantity_type
+            && e.optional == ing.optional
+    });
+    let Some(earlier) = earlier else {
+        ingredients.push(ing);
+        return;
+    };
+    let amount = |q: &Option<String>| q.as_deref().map(parse_amount);
+    match (amount(&earlier.quantity), amount(&ing.quantity)) {
+        (Some(Some(Amount::Single(a))), Some(Some(Amount::Single(b)))) => {
+            earlier.quantity = Some(format_number(a + b));
+        }
+        // The same ingredient without amounts ("@salt" twice) is listed once
+        (None, None) => {}
+        _ => ingredients.push(ing),
+    }
+}
+
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 {
+        format!("{}", n as u64)
+    } else {
+        format!("{}", (n * 1000.0).round() / 1000.0)
+    }
+}
+
+/// Replace the ingredient, cookware and timer marks in `line` with their
+/// plain text, collecting the ingredients.
+fn parse_marks(line: &str, marked: &mut Vec<Mark>) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(i) = rest.find(['@', '#', '~']) {
+        out.push_str(&rest[..i]);
+        let sigil = rest[i..].chars().next().unwrap_or('@');
+        let after = &rest[i + 1..];
+        let (optional, after) = match after.strip_prefix('?') {
+            Some(a) if sigil == '@' => (true, a),
+            _ => (false, after),
+        };
+        let Some((name, amount, consumed)) = component(after, sigil == '~') else {
+            out.push(sigil);
+            rest = after;
+            continue;
+        };
+        let mut after = &after[consumed..];
+        let (quantity, unit) = match amount.split_once('%') {
+            Some((q, u)) => (q.trim().to_string(), u.trim().to_string()),
+            None => (amount.trim().to_string(), String::new()),
+        };
+
+        match sigil {
+            '@' => {
+                // An optional "(note)" right after the mark
+                let mut note = None;
+                if let Some(body) = after.strip_prefix('(') {
+                    if let Some(end) = body.find(')') {
+                        note = non_empty(body[..end].to_string());
+                        after = &body[end + 1..];
+                    }
+                }
+                out.push_str(&name);
+                marked.push(Mark { name, quantity, unit, note, optional });
+            }
+            '#' => out.push_str(&name),
+            _ => {
+                // Timers read as their duration so cook mode finds them
+                let duration = [quantity, unit].join(" ");
+                match (name.is_empty(), duration.trim().is_empty()) {
+                    (_, false) => out.push_str(duration.trim()),
+                    (false, true) => out.push_str(&name),
+                    (true, true) => {}
+                }
+            }
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The name and `{…}` contents of a mark starting at `text` (just after the
+/// sigil), and how many bytes it spans. A multi-word name needs the braces;
+/// without them the name is one word. Timers may have no name.
+fn component(text: &str, nameless_ok: bool) -> Option<(String, String, usize)> {
+    let stop =
+        text.find(['@', '#', '~', '.', ',', ';', '!', '?', '(', ')', '}']).unwrap_or(text.len());
+    if let Some(open) = text[..stop].find('{') {
+        let close = open + text[open..].find('}')?;
+        let name = text[..open].trim();
+        if name.is_empty() && !nameless_ok {
+            return None;
+        }
+        return Some((name.to_string(), text[open + 1..close].to_string(), close + 1));
+    }
+    let end =
+        text.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-')).unwrap_or(text.len());
+    let name = text[..end].trim_end_matches('-');
+    (!name.is_empty()).then(|| (name.to_string(), String::new(), name.len()))
+}
+
+fn strip_block_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("[-") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-]") {
+            Some(end) => rest = &rest[start + end + 2..],
+            None => rest = "",
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_line_comment(line: &str) -> &str {
+    match line.find("--") {
+        Some(i) if i == 0 || line[..i].ends_with(char::is_whitespace) => &line[..i],
+        _ => line,
+    }
+}
+
+/// YAML frontmatter (with lowercased keys) and the rest of the file.
+fn frontmatter(text: &str) -> (Mapping, &str) {
+    let Some((yaml, rest)) = crate::frontmatter::split(text) else {
+        return (Mapping::new(), text);
+    };
+    // `rest` starts with the closing `---` line
+    let body = rest.split_once('\n').map_or("", |(_, body)| body);
+    let map = crate::frontmatter::parse(yaml)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| match k {
+            Value::String(k) => (Value::String(k.to_lowercase()), v),
+            k => (k, v),
+        })
+        .collect();
+    (map, body)
+}
+
+fn meta_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Sequence(items) => items.iter().map(meta_text).collect::<Vec<_>>().join(", "),
+        _ => String::new(),
+    }
+}
+
+/// Minutes in a time like "45", "45 minutes", "1 hour 30 min" or "1h30m".
+pub fn parse_minutes(text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    let mut total = 0.0;
+    let mut found = false;
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return None;
+        }
+        let n: f64 = rest[..end].replace(',', ".").parse().ok()?;
+        rest = rest[end..].trim_start();
+        let unit_end = rest.find(|c: char| !c.is_alphabetic()).unwrap_or(rest.len());
+        let factor = match &rest[..unit_end] {
+            "" | "m" | "min" | "mins" | "minute" | "minutes" => 1.0,
+            "h" | "hr" | "hrs" | "hour" | "hours" | "t" | "time" | "timer" => 60.0,
+            "d" | "day" | "days" | "dag" | "dage" => 1440.0,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1.0 / 60.0,
+            _ => return None,
+        };
+        total += n * factor;
+        found = true;
+        rest = rest[unit_end..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        rest = rest.strip_prefix("and ").unwrap_or(rest);
+    }
+    let minutes = total.ceil() as u32;
+    (found && minutes > 0).then_some(minutes)
+}
+
+/// `recipe` as a Cooklang file: YAML frontmatter, then the steps with each
+/// ingredient marked where it is first mentioned. Ingredients no step
+/// mentions are listed in a step of their own at the start.
+pub fn to_cooklang(recipe: &Recipe) -> String {
+    let mut meta = Mapping::new();
+    let mut put = |key: &str, value: Value| {
+        meta.insert(Value::String(key.to_string()), value);
+    };
+    put("title", Value::String(recipe.title.clone()));
+    if let Some(servings) = recipe.servings {
+        put("servings", Value::Number(servings.into()));
+    }
+    if let Some(tags) = recipe.tags.as_ref().filter(|t| !t.is_empty()) {
+        put("tags", Value::Sequence(tags.iter().cloned().map(Value::String).collect()));
+    }
+    if let Some(prep) = recipe.prep_time {
+        put("prep time", Value::String(format!("{} minutes", prep)));
+    }
+    if let Some(cook) = recipe.downtime {
+        put("cook time", Value::String(format!("{} minutes", cook)));
+    }
+    if let Some(image) = &recipe.image {
+        put("image", Value::String(image.clone()));
+    }
+    let mut out = format!("---\n{}---\n", serde_yaml::to_string(&meta).unwrap_or_default());
+
+    let mut placed = vec![false; recipe.ingredients.len()];
+    let mut sections: Vec<(Option<String>, Vec<String>)> = vec![(None, Vec::new())];
+    let mut chunk = String::new();
+    for line in recipe.instructions.lines() {
+        if line.trim_start().starts_with('#') {
+            if let Some(section) = sections.last_mut() {
+                section.1.extend(split_steps(&chunk));
+            }
+            chunk.clear();
+            let heading = line.trim().trim_start_matches('#').trim_end_matches('#').trim();
+            sections.push((Some(heading.to_string()), Vec::new()));
+        } else {
+            chunk.push_str(line);
+            chunk.push('\n');
+        }
+    }
+    if let Some(section) = sections.last_mut() {
+        section.1.extend(split_steps(&chunk));
+    }
+
+    let mut body: Vec<String> = Vec::new();
+    for (heading, steps) in &sections {
+        if let Some(heading) = heading {
+            body.push(format!("= {} =", heading));
+        }
+        for step in steps {
+            body.push(mark_ingredients(step, &recipe.ingredients, &mut placed));
+        }
+    }
+    let unplaced: Vec<String> = recipe
+        .ingredients
+        .iter()
+        .zip(&placed)
+        .filter(|(_, placed)| !**placed)
+        .map(|(ing, _)| ingredient_mark(ing))
+        .collect();
+    if !unplaced.is_empty() {
+        body.insert(0, unplaced.join(", "));
+    }
+
+    out.push('\n');
+    out.push_str(&body.join("\n\n"));
+    out.push('\n');
+    out
+}
+
+/// `step` with the first mention of each not yet placed ingredient replaced
+/// by its mark. A mention is the ingredient's name at the start of a word
+/// (case-insensitive), up to the end of that word. Mentions are found in the
+/// original step, longest name first, and never overlap, so "tomato" isn't
+/// found inside "tomato paste".
+fn mark_ingredients(step: &str, ingredients: &[RecipeIngredient], placed: &mut [bool]) -> String {
+    let lower = step.to_lowercase();
+    // Lowercasing can change byte lengths for a few characters; offsets into
+    // `lower` are only valid for `step` when it didn't
+    if lower.len() != step.len() {
+        return step.to_string();
+    }
+    let mut order: Vec<usize> = (0..ingredients.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(ingredients[i].ingredient.len()));
+
+    // (start, end, ingredient) of each mention
+    let mut mentions: Vec<(usize, usize, usize)> = Vec::new();
+    for i in order {
+        if placed[i] || ingredients[i].ingredient.is_empty() {
+            continue;
+        }
+        let name = ingredients[i].ingredient.to_lowercase();
+        let mention = lower
+            .match_indices(&name)
+            .map(|(start, _)| start)
+            .filter(|&start| {
+                !matches!(lower[..start].chars().next_back(), Some(c) if c.is_alphanumeric())
+            })
+            .map(|start| {
+                let from = start + name.len();
+                let end = lower[from..]
+                    .find(|c: char| !c.is_alphanumeric())
+                    .map_or(lower.len(), |e| from + e);
+                (start, end)
+            })
+            .find(|&(start, end)| !mentions.iter().any(|&(s, e, _)| start < e && s < end));
+        if let Some((start, end)) = mention {
+            mentions.push((start, end, i));
+            placed[i] = true;
+        }
+    }
+
+    mentions.sort_unstable();
+    let mut out = String::with_capacity(step.len());
+    let mut done = 0;
+    for (start, end, i) in mentions {
+        out.push_str(&step[done..start]);
+        out.push_str(&ingredient_mark(&ingredients[i]));
+        done = end;
+    }
+    out.push_str(&step[done..]);
+    out
+}
+
+/// `@name{quantity%unit}(note)`, with `@?` for optional ingredients.
+fn ingredient_mark(ing: &RecipeIngredient) -> String {
+    let amount = match (&ing.quantity, &ing.quantity_type) {
+        (Some(q), Some(u)) if !u.is_empty() => format!("{}%{}", q, u),
+        (Some(q), _) => q.clone(),
+        (None, _) => String::new(),
+    };
+    let mut mark =
+        format!("@{}{}{{{}}}", if ing.optional { "?" } else { "" }, ing.ingredient, amount);
+    if let Some(note) = ing.note.as_ref().filter(|n| !n.is_empty()) {
+        mark.push_str(&format!("({})", note));
+    }
+    mark
+}
@@ -0,0 +1,119 @@
+/// Quantity-aware pantry coverage for recipes.
+///
+/// `Recipe::pantry_coverage` only asks whether an ingredient is in the pantry at
+/// all. This compares the pantry amount with the recipe amount (converting units
+/// through the `units` registry), so 200 g of potatoes only partially covers a
+/// recipe that needs 2 kg.
+use crate::quantity::parse_amount;
+use crate::units::{convert_with, ingredient_units, IngredientUnits};
+use janus_engine::{DataManager, Recipe, RecipeIngredient};
+
+/// How well the pantry covers one recipe ingredient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stock {
+    /// Enough on hand, or in stock with amounts that cannot be compared.
+    Covered,
+    /// In stock, but less than the recipe needs.
+    Partial,
+    /// Not in the pantry.
+    Missing,
+}
+
+/// Coverage of a recipe's required (non-optional) ingredients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Coverage {
+    pub required_total: usize,
+    pub covered: usize,
+    pub partial: usize,
+}
+
+impl Coverage {
+    /// Required ingredients that are missing or only partially covered: what
+    /// the filters, sorts and sections count as missing.
+    pub fn not_covered(&self) -> usize {
+        self.required_total - self.covered
+    }
+
+    pub fn is_cookable(&self) -> bool {
+        self.covered == self.required_total
+    }
+
+    /// Fraction of required ingredients fully covered (1.0 for no requirements).
+    pub fn covered_ratio(&self) -> f64 {
+        if self.required_total == 0 {
+            1.0
+        } else {
+            self.covered as f64 / self.required_total as f64
+        }
+    }
+
+    /// Fraction of required ingredients only partially covered.
+    pub fn partial_ratio(&self) -> f64 {
+        if self.required_total == 0 {
+            0.0
+        } else {
+            self.partial as f64 / self.required_total as f64
+        }
+    }
+}
+
+/// Compare a pantry amount with a recipe amount scaled by `factor`.
+///
+/// `have` is `PantryItem::quantity`, `need` the raw `RecipeIngredient::quantity`;
+/// `props` lets mass, volume and pieces be compared with each other. Anything
+/// that cannot be compared (no number on either side, unrelated units) counts as
+/// covered, since the ingredient is in the pantry.
+pub fn compare_amounts(
+    have: Option<f64>,
+    have_unit: &str,
+    need: Option<&str>,
+    need_unit: &str,
+    factor: f64,
+    props: IngredientUnits,
+) -> Stock {
+    let (Some(have), Some(need)) = (have, need.and_then(parse_amount)) else {
+        return Stock::Covered;
+    };
+    let Some(have) = convert_with(have, have_unit, need_unit, props) else {
+        return Stock::Covered;
+    };
+    if have >= need.max() * factor {
+        Stock::Covered
+    } else if have > 0.0 {
+        Stock::Partial
+    } else {
+        Stock::Missing
+    }
+}
+
+/// Stock level of `ing` for `factor` times the recipe amount.
+pub fn ingredient_stock(dm: &DataManager, ing: &RecipeIngredient, factor: f64) -> Stock {
+    if !dm.is_in_pantry(&ing.ingredient) {
+        return Stock::Missing;
+    }
+    let Some(item) = dm.get_pantry_item(&ing.ingredient) else {
+        return Stock::Covered;
+    };
+    compare_amounts(
+        item.quantity,
+        &item.quantity_type,
+        ing.quantity.as_deref(),
+        ing.quantity_type.as_deref().unwrap_or(""),
+        factor,
+        ingredient_units(&ing.ingredient),
+    )
+}
+
+/// Quantity-aware coverage of `recipe`, scaled by `factor` (1.0 = as written).
+pub fn recipe_coverage(dm: &DataManager, recipe: &Recipe, factor: f64) -> Coverage {
+    let mut cov = Coverage::default();
+    for ing in recipe.ingredients.iter().filter(|i| !i.optional) {
+        cov.required_total += 1;
+        match ingredient_stock(dm, ing, factor) {
+            Stock::Covered => cov.covered += 1,
+            Stock::Partial => cov.partial += 1,
+            Stock::Missing => {}
+        }
+    }
+    cov
+}
@@ -1,14 +1,26 @@
-/// Modal dialogs for creating and editing recipes and ingredients.
+/// Modal dialogs for creating and editing recipes and ingredients, for
+/// reviewing what a cooked recipe takes from the pantry, and the cooking history.
 ///
 /// Each dialog is a `gtk::Window` shown as a transient child of the main window.
 /// When the user confirms, the dialog sends a message via the component sender.
 use crate::i18n;
+use crate::cooking::{Consume, Deduction, HistoryEntry};
+use crate::quantity::{format_amount, parse_amount, parse_quantity_with};
+use crate::low_stock::{self, Threshold};
+use crate::markdown;
+use crate::pantry_details;
+use crate::pantry_io::{self, ImportRow, PantryFormat, PantryRow, RowStatus};
+use crate::print::PrintBlock;
+use crate::recipe_form::{FormError, IngredientLine, RecipeForm};
+use crate::recipe_import::ImportedRecipe;
+use chrono::NaiveDate;
 use janus_engine::{fold_for_matching, slugify, Ingredient, PantryItem, Recipe, RecipeIngredient};
 use libadwaita as adw;
 use relm4::gtk;
 use relm4::gtk::prelude::*;
 use relm4::ComponentSender;
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use crate::app::{App, AppMsg};
@@ -25,17 +37,37 @@ pub struct IngredientInfo {
 
 // ─── Recipe dialog ────────────────────────────────────────────────────────────
 
+/// Widgets of one ingredient row in the recipe dialog.
+struct IngredientRow {
+    qty: gtk::Entry,
+    ingredient: gtk::Entry,
+    note: gtk::Entry,
+    optional: gtk::CheckButton,
+    /// The stored ingredient the row was filled from.
+    loaded: Option<RecipeIngredient>,
+}
+
+impl IngredientRow {
+    fn line(&self) -> IngredientLine {
+        IngredientLine {
+            quantity: self.qty.text().to_string(),
+            ingredient: self.ingredient.text().to_string(),
+            note: self.note.text().to_string(),
+            optional: self.optional.is_active(),
+            loaded: self.loaded.clone(),
+        }
+    }
+}
+
 /// Add a single ingredient row to the list and register it in `row_entries`.
 ///
-/// `qty`, `ing`, `note`, and `optional` are pre-filled values.
+/// `line` holds the pre-filled values.
 fn add_ingredient_row(
     list: &gtk::ListBox,
-    row_entries: &Rc<RefCell<Vec<(gtk::Entry, gtk::Entry, gtk::Entry, gtk::CheckButton)>>>,
+    row_entries: &Rc<RefCell<Vec<IngredientRow>>>,
     ingredients: &Rc<Vec<IngredientInfo>>,
-    qty: &str,
-    ing: &str,
-    note: &str,
-    optional: bool,
+    known_units: &Rc<Vec<String>>,
+    line: &IngredientLine,
 ) {
     let s = i18n::strings();
 
@@ -49,7 +81,25 @@ fn add_ingredient_row(
     qty_entry.set_placeholder_text(Some(s.qty_placeholder));
     qty_entry.set_width_chars(8);
     qty_entry.set_max_width_chars(12);
-    qty_entry.set_text(qty);
+    qty_entry.set_text(&line.quantity);
+    {
+        // Inline validation: highlight quantities the parser can't understand
+        let units = Rc::clone(known_units);
+        let validate = move |entry: &gtk::Entry| {
+            match parse_quantity_with(&entry.text(), &units) {
+                Ok(_) => {
+                    entry.remove_css_class("error");
+                    entry.set_tooltip_text(None);
+                }
+                Err(e) => {
+                    entry.add_css_class("error");
+                    entry.set_tooltip_text(Some(&i18n::fmt_quantity_error(&e)));
+                }
+            }
+        };
+        validate(&qty_entry);
+        qty_entry.connect_changed(validate);
+    }
 
     let status_label = gtk::Label::new(Some("·"));
     status_label.set_width_chars(1);
@@ -58,15 +108,15 @@ fn add_ingredient_row(
     let ing_entry = gtk::Entry::new();
     ing_entry.set_placeholder_text(Some(s.ingredient_placeholder));
     ing_entry.set_hexpand(true);
-    ing_entry.set_text(ing);
+    ing_entry.set_text(&line.ingredient);
 
     let note_entry = gtk::Entry::new();
     note_entry.set_placeholder_text(Some(s.note_placeholder));
     note_entry.set_hexpand(true);
-    note_entry.set_text(note);
+    note_entry.set_text(&line.note);
 
     let optional_check = gtk::CheckButton::new();
-    optional_check.set_active(optional);
+    optional_check.set_active(line.optional);
     optional_check.set_tooltip_text(Some(s.optional_check_tooltip));
     optional_check.set_valign(gtk::Align::Center);
 
@@ -93,9 +143,13 @@ fn add_ingredient_row(
     list_row.set_child(Some(&row_box));
 
     list.append(&list_row);
-    row_entries
-        .borrow_mut()
-        .push((qty_entry.clone(), ing_entry.clone(), note_entry.clone(), optional_check.clone()));
+    row_entries.borrow_mut().push(IngredientRow {
+        qty: qty_entry.clone(),
+        ingredient: ing_entry.clone(),
+        note: note_entry.clone(),
+        optional: optional_check.clone(),
+        loaded: line.loaded.clone(),
+    });
 
     // Live status update
     let status_clone = status_label.clone();
@@ -137,21 +191,28 @@ fn add_ingredient_row(
         list_clone.remove(&list_row);
         entries_clone
             .borrow_mut()
-            .retain(|(q, i, _, _)| q != &qty_clone || i != &ing_clone);
+            .retain(|r| r.qty != qty_clone || r.ingredient != ing_clone);
     });
 }
 
 /// Show a dialog for adding (original = None) or editing (original = Some) a recipe.
+/// A new recipe can start from an `imported` one for review.
 pub fn show_recipe_dialog(
     parent: &impl gtk::prelude::IsA<gtk::Window>,
     ingredients: Vec<IngredientInfo>,
+    known_units: Vec<String>,
     existing: Option<&Recipe>,
+    imported: Option<ImportedRecipe>,
     sender: ComponentSender<App>,
 ) {
     use adw::prelude::*;
 
     let s = i18n::strings();
     let is_edit = existing.is_some();
+    let model = existing
+        .or(imported.as_ref().map(|i| &i.recipe))
+        .map(RecipeForm::from_recipe)
+        .unwrap_or_default();
     let window = adw::Window::builder()
         .transient_for(parent)
         .modal(true)
@@ -182,51 +243,111 @@ pub fn show_recipe_dialog(
     // Title
     let title_row = adw::EntryRow::new();
     title_row.set_title(s.recipe_title_field);
-    if let Some(r) = existing {
-        title_row.set_text(&r.title);
-    }
+    title_row.set_text(&model.title);
     group.add(&title_row);
 
     // Prep time
     let prep_row = adw::EntryRow::new();
     prep_row.set_title(s.prep_time_field);
-    if let Some(r) = existing {
-        if let Some(t) = r.prep_time {
-            prep_row.set_text(&t.to_string());
-        }
-    }
+    prep_row.set_text(&model.prep_time);
     group.add(&prep_row);
 
     // Downtime (oven / resting)
     let down_row = adw::EntryRow::new();
     down_row.set_title(s.downtime_field);
-    if let Some(r) = existing {
-        if let Some(t) = r.downtime {
-            down_row.set_text(&t.to_string());
-        }
-    }
+    down_row.set_text(&model.downtime);
     group.add(&down_row);
 
     // Servings
     let servings_row = adw::EntryRow::new();
     servings_row.set_title(s.servings_field);
-    if let Some(r) = existing {
-        if let Some(sv) = r.servings {
-            servings_row.set_text(&sv.to_string());
-        }
-    }
+    servings_row.set_text(&model.servings);
     group.add(&servings_row);
 
     // Tags (comma-separated)
     let tags_row = adw::EntryRow::new();
     tags_row.set_title(s.tags_field);
-    if let Some(r) = existing {
-        if let Some(tags) = &r.tags {
-            tags_row.set_text(&tags.join(", "));
-        }
-    }
+    tags_row.set_text(&model.tags);
     group.add(&tags_row);
 
+    // Image: the kept file name, or a newly picked file that is copied into
+    // the image folder when the recipe is saved
+    let kept_image: Rc<RefCell<Option<String>>> =
+        Rc::new(RefCell::new(model.image.clone()));
+    let new_image: Rc<RefCell<Option<PathBuf>>> =
+        Rc::new(RefCell::new(imported.and_then(|i| i.image_file)));
+    let image_name = new_image
+        .borrow()
+        .as_ref()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .or_else(|| kept_image.borrow().clone());
+
+    let image_row = adw::ActionRow::new();
+    image_row.set_title(s.image_field);
+    image_row.set_subtitle(image_name.as_deref().unwrap_or(s.no_image));
+    image_row.set_subtitle_lines(1);
+
+    let remove_image_btn = gtk::Button::from_icon_name("edit-clear-symbolic");
+    remove_image_btn.set_valign(gtk::Align::Center);
+    remove_image_btn.add_css_class("flat");
+    remove_image_btn.set_tooltip_text(Some(s.remove_image));
+    remove_image_btn.set_visible(image_name.is_some());
+    {
+        let kept = Rc::clone(&kept_image);
+        let picked = Rc::clone(&new_image);
+        let row = image_row.clone();
+        remove_image_btn.connect_clicked(move |btn| {
+            kept.borrow_mut().take();
+            picked.borrow_mut().take();
+            row.set_subtitle(i18n::strings().no_image);
+            btn.set_visible(false);
+        });
+    }
+
+    let choose_image_btn = gtk::Button::with_label(s.choose_image);
+    choose_image_btn.set_valign(gtk::Align::Center);
+    choose_image_btn.add_css_class("flat");
+    {
+        let picked = Rc::clone(&new_image);
+        let row = image_row.clone();
+        let remove_btn = remove_image_btn.clone();
+        choose_image_btn.connect_clicked(move |btn| {
+            let s = i18n::strings();
+            let root = btn.root().and_then(|r| r.downcast::<gtk::Window>().ok());
+            let chooser = gtk::FileChooserNative::new(
+                Some(s.choose_image_title),
+                root.as_ref(),
+                gtk::FileChooserAction::Open,
+                Some(s.choose_image),
+                Some(s.cancel),
+            );
+            let filter = gtk::FileFilter::new();
+            filter.add_pixbuf_formats();
+            chooser.set_filter(&filter);
+
+            let picked = Rc::clone(&picked);
+            let row = row.clone();
+            let remove_btn = remove_btn.clone();
+            chooser.connect_response(move |d, response| {
+                if gtk::ResponseType::from(response) != gtk::ResponseType::Accept {
+                    return;
+                }
+                if let Some(path) = d.file().and_then(|f| f.path()) {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    row.set_subtitle(&name);
+                    *picked.borrow_mut() = Some(path);
+                    remove_btn.set_visible(true);
+                }
+            });
+            chooser.show();
+        });
+    }
+    image_row.add_suffix(&remove_image_btn);
+    image_row.add_suffix(&choose_image_btn);
+    image_row.set_activatable_widget(Some(&choose_image_btn));
+    group.add(&image_row);
+
     page.add(&group);
 
     // Ingredients group
@@ -238,27 +359,12 @@ pub fn show_recipe_dialog(
     ing_list.set_selection_mode(gtk::SelectionMode::None);
 
     let all_ings: Rc<Vec<IngredientInfo>> = Rc::new(ingredients);
-    let row_entries: Rc<RefCell<Vec<(gtk::Entry, gtk::Entry, gtk::Entry, gtk::CheckButton)>>> =
-        Rc::new(RefCell::new(Vec::new()));
+    let units: Rc<Vec<String>> = Rc::new(known_units);
+    let row_entries: Rc<RefCell<Vec<IngredientRow>>> = Rc::new(RefCell::new(Vec::new()));
 
     // Populate from existing recipe
-    if let Some(r) = existing {
-        for i in &r.ingredients {
-            let qty_display = match (&i.quantity, &i.quantity_type) {
-                (Some(q), Some(u)) if !u.is_empty() => format!("{} {}", q, u),
-                (Some(q), _) => q.clone(),
-                (None, _) => String::new(),
-            };
-            add_ingredient_row(
-                &ing_list,
-                &row_entries,
-                &all_ings,
-                &qty_display,
-                &i.ingredient,
-                i.note.as_deref().unwrap_or(""),
-                i.optional,
-            );
-        }
+    for line in &model.ingredients {
+        add_ingredient_row(&ing_list, &row_entries, &all_ings, &units, line);
     }
 
     // "Add ingredient" button
@@ -270,8 +376,15 @@ pub fn show_recipe_dialog(
         let list_clone = ing_list.clone();
         let entries_clone = Rc::clone(&row_entries);
         let ings_clone = Rc::clone(&all_ings);
+        let units_clone = Rc::clone(&units);
         add_btn.connect_clicked(move |_| {
-            add_ingredient_row(&list_clone, &entries_clone, &ings_clone, "", "", "", false);
+            add_ingredient_row(
+                &list_clone,
+                &entries_clone,
+                &ings_clone,
+                &units_clone,
+                &IngredientLine::default(),
+            );
         });
     }
 
@@ -295,9 +408,7 @@ pub fn show_recipe_dialog(
     instr_text.add_css_class("card");
     instr_text.set_size_request(-1, 150);
 
-    if let Some(r) = existing {
-        instr_text.buffer().set_text(&r.instructions);
-    }
+    instr_text.buffer().set_text(&model.instructions);
 
     let instr_scroll = gtk::ScrolledWindow::new();
     instr_scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
@@ -340,95 +451,43 @@ pub fn show_recipe_dialog(
 
     let win_save = window.clone();
     let original_title = existing.map(|r| r.title.clone());
+    let base = existing.cloned();
     let row_entries_save = Rc::clone(&row_entries);
     let all_ings_save = Rc::clone(&all_ings);
+    let units_save = Rc::clone(&units);
     save_btn.connect_clicked(move |_| {
-        let row_entries = &row_entries_save;
-        let all_ings = &all_ings_save;
-        let title = title_row.text().to_string();
-        if title.trim().is_empty() {
-            return;
-        }
-        let prep_time = prep_row.text().parse::<u32>().ok();
-        let downtime = down_row.text().parse::<u32>().ok();
-        let servings = servings_row.text().parse::<u32>().ok();
-        let tags: Option<Vec<String>> = {
-            let t = tags_row.text();
-            if t.is_empty() {
-                None
-            } else {
-                Some(
-                    t.split(',')
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect(),
-                )
-            }
+        let (start, end) = instr_text.buffer().bounds();
+        let form = RecipeForm {
+            title: title_row.text().to_string(),
+            prep_time: prep_row.text().to_string(),
+            downtime: down_row.text().to_string(),
+            servings: servings_row.text().to_string(),
+            tags: tags_row.text().to_string(),
+            image: kept_image.borrow().clone(),
+            ingredients: row_entries_save.borrow().iter().map(IngredientRow::line).collect(),
+            instructions: instr_text.buffer().text(&start, &end, false).to_string(),
         };
 
-        // Collect ingredients from row entries
-        let ingredients: Vec<RecipeIngredient> = row_entries
-            .borrow()
-            .iter()
-            .filter_map(|(qty_e, ing_e, note_e, opt_check)| {
-                let ing_text = ing_e.text().to_string();
-                if ing_text.trim().is_empty() {
-                    return None;
+        // An unparseable quantity blocks the save (the entry is already
+        // highlighted) instead of being stored verbatim.
+        let recipe = match form.to_recipe(base.as_ref(), &all_ings_save, &units_save) {
+            Ok(recipe) => recipe,
+            Err(FormError::EmptyTitle) => {
+                title_row.grab_focus();
+                return;
+            }
+            Err(FormError::Quantity(i)) => {
+                if let Some(row) = row_entries_save.borrow().get(i) {
+                    row.qty.grab_focus();
                 }
-                let canonical = all_ings
-                    .iter()
-                    .find(|i| {
-                        i.forms
-                            .iter()
-                            .any(|f| f == &ing_text.trim().to_lowercase())
-                    })
-                    .map(|i| i.name.clone())
-                    .unwrap_or_else(|| ing_text.trim().to_string());
-                let qty_text = qty_e.text().to_string();
-                let quantity = if qty_text.trim().is_empty() {
-                    None
-                } else {
-                    Some(qty_text.trim().to_string())
-                };
-                let note_text = note_e.text().to_string();
-                let note = if note_text.trim().is_empty() {
-                    None
-                } else {
-                    Some(note_text.trim().to_string())
-                };
-                Some(RecipeIngredient {
-                    ingredient: canonical,
-                    quantity,
-                    quantity_type: None,
-                    note,
-                    optional: opt_check.is_active(),
-                })
-            })
-            .collect();
-
-        // Parse instructions
-        let (start, end) = instr_text.buffer().bounds();
-        let instructions = instr_text
-            .buffer()
-            .text(&start, &end, false)
-            .to_string();
-
-        let recipe = Recipe {
-            title: title.trim().to_string(),
-            slug: String::new(),
-            file_stem: String::new(),
-            ingredients,
-            prep_time,
-            downtime,
-            servings,
-            tags,
-            image: None,
-            instructions,
+                return;
+            }
         };
 
         sender.input(AppMsg::SaveRecipe {
             original: original_title.clone(),
             recipe,
+            new_image: new_image.borrow().clone(),
         });
         win_save.close();
     });
@@ -436,14 +495,310 @@ pub fn show_recipe_dialog(
     window.present();
 }
 
+// ─── Recipe import ────────────────────────────────────────────────────────────
+
+/// Show a dialog for importing a recipe from a Cooklang file, a saved web page or
+/// pasted JSON-LD. Pasted text is sent as `AppMsg::ImportRecipe` and a chosen
+/// file as `AppMsg::ImportRecipeFiles`.
+pub fn show_import_recipe_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    sender: ComponentSender<App>,
+) {
+    use adw::prelude::*;
+
+    let s = i18n::strings();
+    let window = adw::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title(s.import_recipe_dialog_title)
+        .default_width(560)
+        .default_height(480)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    toolbar_view.add_top_bar(&adw::HeaderBar::new());
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+    content.set_margin_top(16);
+    content.set_margin_bottom(16);
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+
+    let hint = gtk::Label::new(Some(s.import_recipe_hint));
+    hint.set_wrap(true);
+    hint.set_xalign(0.0);
+    hint.add_css_class("dim-label");
+    content.append(&hint);
+
+    let text = gtk::TextView::new();
+    text.set_wrap_mode(gtk::WrapMode::Char);
+    text.set_monospace(true);
+    text.set_top_margin(8);
+    text.set_bottom_margin(8);
+    text.set_left_margin(8);
+    text.set_right_margin(8);
+    text.add_css_class("card");
+
+    let text_scroll = gtk::ScrolledWindow::new();
+    text_scroll.set_vexpand(true);
+    text_scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    text_scroll.set_child(Some(&text));
+    content.append(&text_scroll);
+
+    // ── Buttons ───────────────────────────────────────────────────────────────
+    let btn_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    btn_box.set_margin_top(8);
+
+    let open_btn = gtk::Button::with_label(s.open_saved_page);
+    open_btn.add_css_class("pill");
+
+    let spacer = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+    spacer.set_hexpand(true);
+
+    let cancel_btn = gtk::Button::with_label(s.cancel);
+    cancel_btn.add_css_class("pill");
+
+    let import_btn = gtk::Button::with_label(s.import);
+    import_btn.add_css_class("suggested-action");
+    import_btn.add_css_class("pill");
+    import_btn.set_sensitive(false);
+    {
+        let import_btn = import_btn.clone();
+        text.buffer().connect_changed(move |buffer| {
+            import_btn.set_sensitive(buffer.char_count() > 0);
+        });
+    }
+
+    btn_box.append(&open_btn);
+    btn_box.append(&spacer);
+    btn_box.append(&cancel_btn);
+    btn_box.append(&import_btn);
+    content.append(&btn_box);
+
+    toolbar_view.set_content(Some(&content));
+    window.set_content(Some(&toolbar_view));
+
+    // ── Event handlers ────────────────────────────────────────────────────────
+    let win_cancel = window.clone();
+    cancel_btn.connect_clicked(move |_| win_cancel.close());
+
+    {
+        let window = window.clone();
+        let sender = sender.clone();
+        import_btn.connect_clicked(move |_| {
+            let (start, end) = text.buffer().bounds();
+            let text = text.buffer().text(&start, &end, false).to_string();
+            sender.input(AppMsg::ImportRecipe { text, source: None });
+            window.close();
+        });
+    }
+
+    {
+        let window = window.clone();
+        open_btn.connect_clicked(move |_| {
+            let s = i18n::strings();
+            let chooser = gtk::FileChooserNative::new(
+                Some(s.open_saved_page_title),
+                Some(&window),
+                gtk::FileChooserAction::Open,
+                Some(s.open),
+                Some(s.cancel),
+            );
+            let filter = gtk::FileFilter::new();
+            filter.set_name(Some(s.web_pages_filter));
+            for ext in crate::recipe_import::IMPORT_EXTENSIONS {
+                filter.add_pattern(&format!("*.{}", ext));
+            }
+            chooser.add_filter(&filter);
+
+            let window = window.clone();
+            let sender = sender.clone();
+            chooser.connect_response(move |d, response| {
+                if gtk::ResponseType::from(response) != gtk::ResponseType::Accept {
+                    return;
+                }
+                if let Some(path) = d.file().and_then(|f| f.path()) {
+                    sender.input(AppMsg::ImportRecipeFiles(vec![path]));
+                    window.close();
+                }
+            });
+            chooser.show();
+        });
+    }
+
+    window.present();
+}
+
+/// Ask whether to create the ingredients imported recipes use that aren't in
+/// the ingredient library; "Create All" sends `AppMsg::CreateIngredients`.
+pub fn show_create_ingredients_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    names: Vec<String>,
+    sender: ComponentSender<App>,
+) {
+    use adw::prelude::*;
+    let s = i18n::strings();
+
+    let dialog = adw::MessageDialog::new(
+        Some(parent),
+        Some(&i18n::fmt_unmatched_title(&names)),
+        Some(s.unmatched_ingredients_body),
+    );
+    dialog.add_response("skip", s.not_now);
+    dialog.add_response("create", s.create_all);
+    dialog.set_response_appearance("create", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("create"));
+    dialog.set_close_response("skip");
+    dialog.connect_response(None, move |_, response| {
+        if response == "create" {
+            sender.input(AppMsg::CreateIngredients(names.clone()));
+        }
+    });
+    dialog.present();
+}
+
+/// Ask where to save `recipe` as a Cooklang file and write it there.
+pub fn show_export_recipe_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    recipe: &Recipe,
+    sender: ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    let chooser = gtk::FileChooserNative::new(
+        Some(s.export_recipe_title),
+        Some(parent),
+        gtk::FileChooserAction::Save,
+        Some(s.save),
+        Some(s.cancel),
+    );
+    let stem = match recipe.file_stem.as_str() {
+        "" => slugify(&recipe.title),
+        stem => stem.to_string(),
+    };
+    chooser.set_current_name(&format!("{}.cook", stem));
+    let filter = gtk::FileFilter::new();
+    filter.set_name(Some(s.cooklang_filter));
+    filter.add_pattern("*.cook");
+    chooser.add_filter(&filter);
+
+    let text = crate::cooklang::to_cooklang(recipe);
+    chooser.connect_response(move |d, response| {
+        if gtk::ResponseType::from(response) != gtk::ResponseType::Accept {
+            return;
+        }
+        let Some(path) = d.file().and_then(|f| f.path()) else {
+            return;
+        };
+        match std::fs::write(&path, &text) {
+            Ok(()) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                sender.input(AppMsg::ShowToast(i18n::fmt_exported(&name)));
+            }
+            Err(e) => sender.input(AppMsg::ShowError {
+                summary: i18n::strings().recipe_export_failed.to_string(),
+                details: format!("{}: {}", path.display(), e),
+            }),
+        }
+    });
+    chooser.show();
+}
+
+/// Ask where to save a PDF of `blocks` (suggesting `file_name`) and write it there.
+pub fn show_export_pdf_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    file_name: &str,
+    blocks: Vec<PrintBlock>,
+    sender: ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    let chooser = gtk::FileChooserNative::new(
+        Some(s.export_recipe_title),
+        Some(parent),
+        gtk::FileChooserAction::Save,
+        Some(s.save),
+        Some(s.cancel),
+    );
+    chooser.set_current_name(file_name);
+    let filter = gtk::FileFilter::new();
+    filter.set_name(Some(s.pdf_filter));
+    filter.add_pattern("*.pdf");
+    chooser.add_filter(&filter);
+
+    chooser.connect_response(move |d, response| {
+        if gtk::ResponseType::from(response) != gtk::ResponseType::Accept {
+            return;
+        }
+        let Some(path) = d.file().and_then(|f| f.path()) else {
+            return;
+        };
+        match crate::print::write_pdf(&path, blocks.clone()) {
+            Ok(()) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                sender.input(AppMsg::ShowToast(i18n::fmt_exported(&name)));
+            }
+            Err(e) => sender.input(AppMsg::ShowError {
+                summary: i18n::strings().recipe_export_failed.to_string(),
+                details: e,
+            }),
+        }
+    });
+    chooser.show();
+}
+
+/// Ask which recipes go into a PDF cookbook: all of them or those with one of
+/// `tags`. "Export" sends `AppMsg::ExportCookbook`.
+pub fn show_export_cookbook_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    tags: Vec<String>,
+    sender: ComponentSender<App>,
+) {
+    use adw::prelude::*;
+    let s = i18n::strings();
+
+    let dialog = adw::MessageDialog::new(
+        Some(parent),
+        Some(s.cookbook_dialog_title),
+        Some(s.cookbook_dialog_body),
+    );
+    let mut choices = vec![s.all_recipes];
+    choices.extend(tags.iter().map(String::as_str));
+    let dropdown = gtk::DropDown::from_strings(&choices);
+    dialog.set_extra_child(Some(&dropdown));
+
+    dialog.add_response("cancel", s.cancel);
+    dialog.add_response("export", s.export);
+    dialog.set_response_appearance("export", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("export"));
+    dialog.set_close_response("cancel");
+    dialog.connect_response(None, move |_, response| {
+        if response == "export" {
+            // The first choice is "All recipes"
+            let tag = (dropdown.selected() as usize).checked_sub(1).and_then(|i| tags.get(i));
+            sender.input(AppMsg::ExportCookbook(tag.cloned()));
+        }
+    });
+    dialog.present();
+}
+
 // ─── Ingredient dialog ────────────────────────────────────────────────────────
 
+/// What the ingredient dialog edits besides the ingredient and its pantry item.
+#[derive(Default)]
+pub struct PantryExtras {
+    pub best_before: Option<NaiveDate>,
+    pub location: Option<String>,
+    /// Storage locations to choose from.
+    pub locations: Vec<String>,
+    pub threshold: Option<Threshold>,
+}
+
 /// Show a dialog for adding or editing an ingredient, including pantry status.
 pub fn show_ingredient_dialog(
     parent: &impl gtk::prelude::IsA<gtk::Window>,
     categories: Vec<String>,
     existing: Option<&Ingredient>,
     pantry_item: Option<&PantryItem>,
+    extras: PantryExtras,
     sender: ComponentSender<App>,
 ) {
     use adw::prelude::*;
@@ -553,13 +908,72 @@ pub fn show_ingredient_dialog(
     }
     pantry_group.add(&unit_row);
 
-    // Toggle qty/unit sensitivity based on in_pantry switch
+    // Best-before date, highlighted while it can't be read
+    let best_before_row = adw::EntryRow::new();
+    best_before_row.set_title(s.best_before_field);
+    best_before_row.set_sensitive(in_pantry);
+    if let Some(date) = extras.best_before {
+        best_before_row.set_text(&date.format("%Y-%m-%d").to_string());
+    }
+    best_before_row.connect_changed(|row| {
+        if row.text().trim().is_empty() || pantry_details::parse_date(&row.text()).is_some() {
+            row.remove_css_class("error");
+        } else {
+            row.add_css_class("error");
+        }
+    });
+    pantry_group.add(&best_before_row);
+
+    // Storage location; the first choice is "none". A location that is no
+    // longer listed is still offered so that saving doesn't drop it silently.
+    let mut locations = extras.locations;
+    if let Some(loc) = &extras.location {
+        if !locations.contains(loc) {
+            locations.push(loc.clone());
+        }
+    }
+    let location_row = adw::ComboRow::new();
+    location_row.set_title(s.storage_location_field);
+    let location_model = gtk::StringList::new(&[s.no_location]);
+    for loc in &locations {
+        location_model.append(loc);
+    }
+    location_row.set_model(Some(&location_model));
+    let selected = extras
+        .location
+        .as_ref()
+        .and_then(|loc| locations.iter().position(|l| l == loc))
+        .map_or(0, |i| i + 1);
+    location_row.set_selected(selected as u32);
+    location_row.set_sensitive(in_pantry);
+    pantry_group.add(&location_row);
+
+    // Low-stock threshold, kept whether or not the ingredient is in stock
+    let threshold_row = adw::EntryRow::new();
+    threshold_row.set_title(s.min_quantity_field);
+    if let Some(t) = &extras.threshold {
+        threshold_row.set_text(&low_stock::threshold_text(t));
+    }
+    threshold_row.connect_changed(|row| {
+        if row.text().trim().is_empty() || low_stock::parse_threshold(&row.text()).is_some() {
+            row.remove_css_class("error");
+        } else {
+            row.add_css_class("error");
+        }
+    });
+    pantry_group.add(&threshold_row);
+
+    // Toggle qty/unit/date/location sensitivity based on in_pantry switch
     {
         let qty_clone = qty_row.clone();
         let unit_clone = unit_row.clone();
+        let best_before_clone = best_before_row.clone();
+        let location_clone = location_row.clone();
         in_pantry_row.connect_active_notify(move |row| {
             qty_clone.set_sensitive(row.is_active());
             unit_clone.set_sensitive(row.is_active());
+            best_before_clone.set_sensitive(row.is_active());
+            location_clone.set_sensitive(row.is_active());
         });
     }
 
@@ -601,6 +1015,18 @@ pub fn show_ingredient_dialog(
         if name.is_empty() {
             return;
         }
+        let best_before_text = best_before_row.text();
+        let best_before = pantry_details::parse_date(&best_before_text);
+        if best_before.is_none() && !best_before_text.trim().is_empty() {
+            best_before_row.grab_focus();
+            return;
+        }
+        let threshold_text = threshold_row.text();
+        let threshold = low_stock::parse_threshold(&threshold_text);
+        if threshold.is_none() && !threshold_text.trim().is_empty() {
+            threshold_row.grab_focus();
+            return;
+        }
         let category = cat_row.text().to_string().trim().to_string();
         let tags_raw = tags_row.text().to_string();
         let tags: Option<Vec<String>> = if tags_raw.is_empty() {
@@ -634,6 +1060,10 @@ pub fn show_ingredient_dialog(
         let in_pantry = in_pantry_row.is_active();
         let qty = qty_row.text().parse::<f64>().ok();
         let qty_type = unit_row.text().to_string();
+        let location = (location_row.selected() as usize)
+            .checked_sub(1)
+            .and_then(|i| locations.get(i))
+            .cloned();
 
         sender.input(AppMsg::SaveIngredient {
             original: original_name.clone(),
@@ -641,19 +1071,684 @@ pub fn show_ingredient_dialog(
             in_pantry,
             qty,
             qty_type,
+            best_before,
+            location,
+            threshold,
+        });
+        win_save.close();
+    });
+
+    window.present();
+}
+
+// ─── Storage locations ────────────────────────────────────────────────────────
+
+/// Edit the list of storage locations pantry items can be kept in. Saving
+/// sends `AppMsg::SaveStorageLocations` with the locations in listed order.
+pub fn show_storage_locations_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    locations: Vec<String>,
+    sender: ComponentSender<App>,
+) {
+    use adw::prelude::*;
+
+    let s = i18n::strings();
+    let window = adw::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title(s.storage_locations_title)
+        .default_width(400)
+        .default_height(460)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    toolbar_view.add_top_bar(&adw::HeaderBar::new());
+
+    let scroll = gtk::ScrolledWindow::new();
+    scroll.set_vexpand(true);
+    scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+    content.set_margin_top(16);
+    content.set_margin_bottom(16);
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+
+    let desc = gtk::Label::new(Some(s.storage_locations_desc));
+    desc.set_wrap(true);
+    desc.set_xalign(0.0);
+    desc.add_css_class("dim-label");
+    content.append(&desc);
+
+    let locations = Rc::new(RefCell::new(locations));
+    let list = gtk::ListBox::new();
+    list.add_css_class("boxed-list");
+    list.set_selection_mode(gtk::SelectionMode::None);
+    fill_location_rows(&list, &locations);
+    content.append(&list);
+
+    let add_list = gtk::ListBox::new();
+    add_list.add_css_class("boxed-list");
+    add_list.set_selection_mode(gtk::SelectionMode::None);
+    let add_row = adw::EntryRow::new();
+    add_row.set_title(s.add_storage_location);
+    add_row.set_show_apply_button(true);
+    {
+        let list = list.clone();
+        let locations = locations.clone();
+        add_row.connect_apply(move |row| {
+            let name = row.text().trim().to_string();
+            if name.is_empty() || locations.borrow().contains(&name) {
+                return;
+            }
+            locations.borrow_mut().push(name);
+            row.set_text("");
+            fill_location_rows(&list, &locations);
         });
+    }
+    add_list.append(&add_row);
+    content.append(&add_list);
+    scroll.set_child(Some(&content));
+
+    // ── Buttons ───────────────────────────────────────────────────────────────
+    let btn_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    btn_box.set_margin_top(8);
+    btn_box.set_margin_bottom(16);
+    btn_box.set_margin_start(16);
+    btn_box.set_margin_end(16);
+    btn_box.set_halign(gtk::Align::End);
+
+    let cancel_btn = gtk::Button::with_label(s.cancel);
+    cancel_btn.add_css_class("pill");
+
+    let save_btn = gtk::Button::with_label(s.save);
+    save_btn.add_css_class("suggested-action");
+    save_btn.add_css_class("pill");
+
+    btn_box.append(&cancel_btn);
+    btn_box.append(&save_btn);
+
+    let outer = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    outer.append(&scroll);
+    outer.append(&btn_box);
+    toolbar_view.set_content(Some(&outer));
+    window.set_content(Some(&toolbar_view));
+
+    let win_cancel = window.clone();
+    cancel_btn.connect_clicked(move |_| win_cancel.close());
+
+    let win_save = window.clone();
+    save_btn.connect_clicked(move |_| {
+        sender.input(AppMsg::SaveStorageLocations(locations.borrow().clone()));
         win_save.close();
     });
 
     window.present();
 }
 
-/// Show a simple error in a message dialog.
-pub fn show_error_toast(parent: &impl gtk::prelude::IsA<gtk::Window>, message: &str) {
+/// Rebuild the rows of the storage locations dialog, each with a remove button.
+fn fill_location_rows(list: &gtk::ListBox, locations: &Rc<RefCell<Vec<String>>>) {
+    use adw::prelude::*;
+
+    crate::utils::clear_list_box(list);
+    for (i, name) in locations.borrow().iter().enumerate() {
+        let row = adw::ActionRow::new();
+        row.set_title(name);
+        let remove_btn = gtk::Button::from_icon_name("list-remove-symbolic");
+        remove_btn.add_css_class("flat");
+        remove_btn.set_valign(gtk::Align::Center);
+        remove_btn.set_tooltip_text(Some(i18n::strings().delete));
+        {
+            let list = list.clone();
+            let locations = locations.clone();
+            remove_btn.connect_clicked(move |_| {
+                locations.borrow_mut().remove(i);
+                fill_location_rows(&list, &locations);
+            });
+        }
+        row.add_suffix(&remove_btn);
+        list.append(&row);
+    }
+}
+
+// ─── Cooked-it dialog ─────────────────────────────────────────────────────────
+
+/// Review what cooking `title` takes from the pantry.
+///
+/// Each row is pre-filled from `deductions`; ingredients that are not in the
+/// pantry are listed but cannot be selected. Pantry items without a tracked
+/// amount can only be marked as used up. Confirming sends `AppMsg::ConfirmCooked`.
+pub fn show_cooked_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    title: &str,
+    servings: Option<u32>,
+    deductions: Vec<Deduction>,
+    sender: ComponentSender<App>,
+) {
+    use adw::prelude::*;
+
+    let s = i18n::strings();
+    let window = adw::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title(s.cooked_dialog_title)
+        .default_width(480)
+        .default_height(480)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    toolbar_view.add_top_bar(&header);
+
+    let scroll = gtk::ScrolledWindow::new();
+    scroll.set_vexpand(true);
+    scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let form = gtk::Box::new(gtk::Orientation::Vertical, 12);
+    form.set_margin_top(16);
+    form.set_margin_bottom(16);
+    form.set_margin_start(16);
+    form.set_margin_end(16);
+
+    let heading = gtk::Label::new(Some(title));
+    heading.add_css_class("title-2");
+    heading.set_halign(gtk::Align::Start);
+    heading.set_wrap(true);
+    form.append(&heading);
+
+    let desc = gtk::Label::new(Some(s.cooked_dialog_desc));
+    desc.add_css_class("dim-label");
+    desc.set_halign(gtk::Align::Start);
+    desc.set_wrap(true);
+    form.append(&desc);
+
+    let list = gtk::ListBox::new();
+    list.add_css_class("boxed-list");
+    list.set_selection_mode(gtk::SelectionMode::None);
+
+    // (ingredient, check, amount entry — None for untracked amounts)
+    let mut rows: Vec<(String, gtk::CheckButton, Option<gtk::Entry>)> = Vec::new();
+    for d in &deductions {
+        let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+        row_box.set_margin_top(6);
+        row_box.set_margin_bottom(6);
+        row_box.set_margin_start(8);
+        row_box.set_margin_end(8);
+
+        let check = gtk::CheckButton::new();
+        check.set_active(d.selected);
+        check.set_sensitive(d.in_pantry);
+        row_box.append(&check);
+
+        let name = gtk::Label::new(Some(&d.ingredient));
+        name.set_halign(gtk::Align::Start);
+        name.set_hexpand(true);
+        row_box.append(&name);
+
+        let entry = if !d.in_pantry {
+            name.add_css_class("dim-label");
+            let note = gtk::Label::new(Some(s.not_in_pantry));
+            note.add_css_class("caption");
+            note.add_css_class("dim-label");
+            row_box.append(&note);
+            None
+        } else if d.have.is_none() {
+            let note = gtk::Label::new(Some(s.used_up));
+            note.add_css_class("caption");
+            row_box.append(&note);
+            None
+        } else {
+            let entry = gtk::Entry::new();
+            entry.set_width_chars(6);
+            entry.set_max_width_chars(8);
+            entry.set_input_purpose(gtk::InputPurpose::Number);
+            if let Some(amount) = d.amount {
+                entry.set_text(&format_amount(amount));
+            }
+            entry.connect_changed(|e| {
+                if e.text().trim().is_empty() || parse_amount(&e.text()).is_some() {
+                    e.remove_css_class("error");
+                } else {
+                    e.add_css_class("error");
+                }
+            });
+            {
+                // Typing an amount selects the row
+                let check_clone = check.clone();
+                entry.connect_changed(move |e| {
+                    if !e.text().trim().is_empty() {
+                        check_clone.set_active(true);
+                    }
+                });
+            }
+            row_box.append(&entry);
+
+            let unit = gtk::Label::new(Some(&d.unit));
+            unit.add_css_class("dim-label");
+            unit.set_width_chars(4);
+            unit.set_xalign(0.0);
+            row_box.append(&unit);
+            Some(entry)
+        };
+
+        list.append(&row_box);
+        rows.push((d.ingredient.clone(), check, entry));
+    }
+    form.append(&list);
+    scroll.set_child(Some(&form));
+
+    // ── Buttons ───────────────────────────────────────────────────────────────
+    let btn_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    btn_box.set_margin_top(8);
+    btn_box.set_margin_bottom(16);
+    btn_box.set_margin_start(16);
+    btn_box.set_margin_end(16);
+    btn_box.set_halign(gtk::Align::End);
+
+    let cancel_btn = gtk::Button::with_label(s.cancel);
+    cancel_btn.add_css_class("pill");
+
+    let confirm_btn = gtk::Button::with_label(s.update_pantry);
+    confirm_btn.add_css_class("suggested-action");
+    confirm_btn.add_css_class("pill");
+
+    btn_box.append(&cancel_btn);
+    btn_box.append(&confirm_btn);
+
+    let outer = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    outer.append(&scroll);
+    outer.append(&btn_box);
+    toolbar_view.set_content(Some(&outer));
+    window.set_content(Some(&toolbar_view));
+
+    let win_cancel = window.clone();
+    cancel_btn.connect_clicked(move |_| win_cancel.close());
+
+    let win_confirm = window.clone();
+    let title = title.to_string();
+    confirm_btn.connect_clicked(move |_| {
+        let mut consumed = Vec::new();
+        for (ingredient, check, entry) in &rows {
+            if !check.is_active() {
+                continue;
+            }
+            match entry {
+                None => consumed.push((ingredient.clone(), Consume::All)),
+                Some(entry) => {
+                    let text = entry.text();
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+                    match parse_amount(&text) {
+                        Some(a) => consumed.push((ingredient.clone(), Consume::Amount(a.value()))),
+                        None => {
+                            // Block confirming until the amount is fixed
+                            entry.add_css_class("error");
+                            entry.grab_focus();
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+        sender.input(AppMsg::ConfirmCooked {
+            title: title.clone(),
+            servings,
+            consumed,
+        });
+        win_confirm.close();
+    });
+
+    window.present();
+}
+
+// ─── Pantry import and export ────────────────────────────────────────────────
+
+/// Ask where to save the pantry; the file name's extension picks CSV or JSON.
+pub fn show_export_pantry_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    rows: Vec<PantryRow>,
+    sender: ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    let chooser = gtk::FileChooserNative::new(
+        Some(s.export_pantry_title),
+        Some(parent),
+        gtk::FileChooserAction::Save,
+        Some(s.save),
+        Some(s.cancel),
+    );
+    chooser.set_current_name("pantry.csv");
+    add_pantry_file_filters(&chooser);
+
+    chooser.connect_response(move |d, response| {
+        if gtk::ResponseType::from(response) != gtk::ResponseType::Accept {
+            return;
+        }
+        let Some(path) = d.file().and_then(|f| f.path()) else {
+            return;
+        };
+        let text = pantry_io::export(&rows, PantryFormat::from_path(&path));
+        match std::fs::write(&path, text) {
+            Ok(()) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                sender.input(AppMsg::ShowToast(i18n::fmt_exported(&name)));
+            }
+            Err(e) => sender.input(AppMsg::ShowError {
+                summary: i18n::strings().pantry_export_failed.to_string(),
+                details: format!("{}: {}", path.display(), e),
+            }),
+        }
+    });
+    chooser.show();
+}
+
+/// Ask for a CSV or JSON file to import into the pantry and send its text as
+/// `AppMsg::PreviewPantryImport`.
+pub fn show_import_pantry_file_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    sender: ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    let chooser = gtk::FileChooserNative::new(
+        Some(s.import_pantry_title),
+        Some(parent),
+        gtk::FileChooserAction::Open,
+        Some(s.open),
+        Some(s.cancel),
+    );
+    add_pantry_file_filters(&chooser);
+
+    chooser.connect_response(move |d, response| {
+        if gtk::ResponseType::from(response) != gtk::ResponseType::Accept {
+            return;
+        }
+        let Some(path) = d.file().and_then(|f| f.path()) else {
+            return;
+        };
+        match std::fs::read(&path) {
+            Ok(bytes) => sender.input(AppMsg::PreviewPantryImport {
+                text: String::from_utf8_lossy(&bytes).into_owned(),
+                path,
+            }),
+            Err(e) => sender.input(AppMsg::ShowError {
+                summary: i18n::strings().pantry_import_failed.to_string(),
+                details: format!("{}: {}", path.display(), e),
+            }),
+        }
+    });
+    chooser.show();
+}
+
+fn add_pantry_file_filters(chooser: &gtk::FileChooserNative) {
+    let filter = gtk::FileFilter::new();
+    filter.set_name(Some(i18n::strings().pantry_files_filter));
+    for pattern in ["*.csv", "*.json"] {
+        filter.add_pattern(pattern);
+    }
+    chooser.add_filter(&filter);
+}
+
+/// Show what importing `rows` would do before anything is written: new items,
+/// ingredients that will be created, conflicts with the pantry and rows that
+/// can't be read. "Import" sends `AppMsg::ImportPantry`.
+pub fn show_pantry_import_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    rows: Vec<ImportRow>,
+    sender: ComponentSender<App>,
+) {
+    use adw::prelude::*;
+
+    let s = i18n::strings();
+    let window = adw::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title(s.import_pantry_title)
+        .default_width(520)
+        .default_height(560)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    toolbar_view.add_top_bar(&header);
+
+    let scroll = gtk::ScrolledWindow::new();
+    scroll.set_vexpand(true);
+    scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let form = gtk::Box::new(gtk::Orientation::Vertical, 12);
+    form.set_margin_top(16);
+    form.set_margin_bottom(16);
+    form.set_margin_start(16);
+    form.set_margin_end(16);
+
+    let count = |f: fn(&RowStatus) -> bool| rows.iter().filter(|r| f(&r.status)).count();
+    let new = count(|st| *st == RowStatus::New);
+    let unknown = count(|st| *st == RowStatus::UnknownIngredient);
+    let conflicts = count(|st| matches!(st, RowStatus::Conflict { .. }));
+    let invalid = count(|st| matches!(st, RowStatus::Invalid(_)));
+
+    let summary = gtk::Label::new(Some(&i18n::fmt_pantry_import_summary(
+        new, unknown, conflicts, invalid,
+    )));
+    summary.set_halign(gtk::Align::Start);
+    summary.set_wrap(true);
+    form.append(&summary);
+
+    let desc = gtk::Label::new(Some(s.pantry_import_desc));
+    desc.add_css_class("dim-label");
+    desc.set_halign(gtk::Align::Start);
+    desc.set_wrap(true);
+    form.append(&desc);
+
+    let list = gtk::ListBox::new();
+    list.add_css_class("boxed-list");
+    list.set_selection_mode(gtk::SelectionMode::None);
+    for row in &rows {
+        let action_row = adw::ActionRow::new();
+        action_row.set_title(&markdown::escape(&row.ingredient));
+        let amount = match row.quantity {
+            Some(q) => format!("{} {}", format_amount(q), row.unit).trim().to_string(),
+            None => row.unit.clone(),
+        };
+        // The file's date and a category the ingredient won't take are shown,
+        // as neither is imported
+        let mut subtitle = vec![amount];
+        if !row.last_updated.is_empty() {
+            subtitle.push(i18n::fmt_file_date(&row.last_updated));
+        }
+        if let Some(kept) = &row.kept_category {
+            subtitle.push(i18n::fmt_category_kept(&row.category, kept));
+        }
+        subtitle.retain(|line| !line.is_empty());
+        action_row.set_subtitle(&markdown::escape(&subtitle.join("\n")));
+
+        let (text, class) = match &row.status {
+            RowStatus::New => (s.pantry_row_new.to_string(), "success"),
+            RowStatus::UnknownIngredient => (s.pantry_row_new_ingredient.to_string(), "accent"),
+            RowStatus::Conflict { quantity, unit } => {
+                let have = match quantity {
+                    Some(q) => format!("{} {}", format_amount(*q), unit).trim().to_string(),
+                    None => String::new(),
+                };
+                (i18n::fmt_pantry_conflict(&have), "warning")
+            }
+            RowStatus::Unchanged => (s.pantry_row_unchanged.to_string(), "dim-label"),
+            RowStatus::Invalid(reason) => (i18n::fmt_invalid_row(row.line, reason), "error"),
+        };
+        let status = gtk::Label::new(Some(&text));
+        status.add_css_class("caption");
+        status.add_css_class(class);
+        status.set_wrap(true);
+        status.set_xalign(1.0);
+        action_row.add_suffix(&status);
+        list.append(&action_row);
+    }
+    form.append(&list);
+
+    let replace_row = adw::SwitchRow::new();
+    replace_row.set_title(s.replace_pantry_amounts);
+    replace_row.set_subtitle(s.replace_pantry_amounts_subtitle);
+    replace_row.set_active(true);
+    replace_row.set_visible(conflicts > 0);
+    let replace_list = gtk::ListBox::new();
+    replace_list.add_css_class("boxed-list");
+    replace_list.set_selection_mode(gtk::SelectionMode::None);
+    replace_list.append(&replace_row);
+    replace_list.set_visible(conflicts > 0);
+    form.append(&replace_list);
+    scroll.set_child(Some(&form));
+
+    // ── Buttons ───────────────────────────────────────────────────────────────
+    let btn_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    btn_box.set_margin_top(8);
+    btn_box.set_margin_bottom(16);
+    btn_box.set_margin_start(16);
+    btn_box.set_margin_end(16);
+    btn_box.set_halign(gtk::Align::End);
+
+    let cancel_btn = gtk::Button::with_label(s.cancel);
+    cancel_btn.add_css_class("pill");
+
+    let import_btn = gtk::Button::with_label(s.import);
+    import_btn.add_css_class("suggested-action");
+    import_btn.add_css_class("pill");
+    import_btn.set_sensitive(new + unknown + conflicts > 0);
+
+    btn_box.append(&cancel_btn);
+    btn_box.append(&import_btn);
+
+    let outer = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    outer.append(&scroll);
+    outer.append(&btn_box);
+    toolbar_view.set_content(Some(&outer));
+    window.set_content(Some(&toolbar_view));
+
+    let win_cancel = window.clone();
+    cancel_btn.connect_clicked(move |_| win_cancel.close());
+
+    let win_import = window.clone();
+    import_btn.connect_clicked(move |_| {
+        sender.input(AppMsg::ImportPantry {
+            rows: rows.clone(),
+            replace: replace_row.is_active(),
+        });
+        win_import.close();
+    });
+
+    window.present();
+}
+
+// ─── Cooking history ──────────────────────────────────────────────────────────
+
+/// List the cooking log by date, most recent first.
+pub fn show_cooking_history_dialog(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    entries: Vec<HistoryEntry>,
+) {
+    use adw::prelude::*;
+
+    let s = i18n::strings();
+    let window = adw::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title(s.cooking_history)
+        .default_width(420)
+        .default_height(520)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    toolbar_view.add_top_bar(&adw::HeaderBar::new());
+
+    if entries.is_empty() {
+        let status = adw::StatusPage::new();
+        status.set_icon_name(Some("document-open-recent-symbolic"));
+        status.set_title(s.history_empty_title);
+        status.set_description(Some(s.history_empty_desc));
+        toolbar_view.set_content(Some(&status));
+        window.set_content(Some(&toolbar_view));
+        window.present();
+        return;
+    }
+
+    let scroll = gtk::ScrolledWindow::new();
+    scroll.set_vexpand(true);
+    scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+    content.set_margin_top(16);
+    content.set_margin_bottom(16);
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+
+    let mut current: Option<(String, gtk::ListBox)> = None;
+    for entry in entries {
+        if !matches!(&current, Some((date, _)) if *date == entry.date) {
+            let date_label = gtk::Label::new(Some(&entry.date));
+            date_label.add_css_class("heading");
+            date_label.set_halign(gtk::Align::Start);
+            content.append(&date_label);
+
+            let list = gtk::ListBox::new();
+            list.add_css_class("boxed-list");
+            list.set_selection_mode(gtk::SelectionMode::None);
+            content.append(&list);
+            current = Some((entry.date.clone(), list));
+        }
+        let Some((_, list)) = &current else {
+            continue;
+        };
+
+        let row = adw::ActionRow::new();
+        row.set_title(&entry.title);
+        if let Some(servings) = entry.servings {
+            row.set_subtitle(&i18n::fmt_servings(servings));
+        }
+        list.append(&row);
+    }
+
+    scroll.set_child(Some(&content));
+    toolbar_view.set_content(Some(&scroll));
+    window.set_content(Some(&toolbar_view));
+    window.present();
+}
+
+/// Show an error summary with the full error chain in a selectable, scrollable box.
+///
+/// Opened from the "Details" button on error toasts.
+pub fn show_error_details(
+    parent: &impl gtk::prelude::IsA<gtk::Window>,
+    summary: &str,
+    details: &str,
+) {
     use adw::prelude::*;
     let s = i18n::strings();
-    let dialog = adw::MessageDialog::new(Some(parent), Some("Error"), Some(message));
+    let dialog = adw::MessageDialog::new(Some(parent), Some(s.error_dialog_title), Some(summary));
+
+    let details_label = gtk::Label::new(Some(details));
+    details_label.set_selectable(true);
+    details_label.set_wrap(true);
+    details_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+    details_label.set_xalign(0.0);
+    details_label.set_valign(gtk::Align::Start);
+    details_label.add_css_class("monospace");
+
+    let details_scroll = gtk::ScrolledWindow::new();
+    details_scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    details_scroll.set_min_content_height(120);
+    details_scroll.set_max_content_height(320);
+    details_scroll.set_propagate_natural_height(true);
+    details_scroll.set_child(Some(&details_label));
+    dialog.set_extra_child(Some(&details_scroll));
+
+    dialog.add_response("copy", s.copy_details);
     dialog.add_response("ok", s.ok);
     dialog.set_default_response(Some("ok"));
+    dialog.set_close_response("ok");
+
+    let text = format!("{}\n\n{}", summary, details);
+    dialog.connect_response(Some("copy"), move |d, _| {
+        d.clipboard().set_text(&text);
+    });
     dialog.present();
 }
@@ -0,0 +1,131 @@
+/// Keeps recipe frontmatter that the engine doesn't know about.
+///
+/// Other frontends may add their own keys to a recipe's YAML frontmatter. The
+/// engine rewrites the whole file from its `Recipe` on save, so every engine
+/// write of a recipe goes through [`keep_unknown_keys`], which reads the unknown
+/// keys from the old file and writes them back into the new one.
+use crate::toasts::error_chain;
+use janus_engine::{DataManager, Recipe};
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+/// Frontmatter keys the engine writes itself.
+pub const ENGINE_KEYS: [&str; 7] =
+    ["Title", "Ingredients", "PrepTime", "Downtime", "Servings", "Tags", "image"];
+
+/// Split a recipe file into its frontmatter YAML and the rest (the closing
+/// `---` line and the body). `None` if the file has no frontmatter.
+pub fn split(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix("---")?;
+    let rest = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n'))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some(rest.split_at(offset));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// The frontmatter YAML as a mapping; `None` if it isn't one.
+pub fn parse(yaml: &str) -> Option<Mapping> {
+    match serde_yaml::from_str(yaml) {
+        Ok(Value::Mapping(map)) => Some(map),
+        _ => None,
+    }
+}
+
+/// Frontmatter entries of a recipe file that aren't [`ENGINE_KEYS`].
+pub fn unknown_keys(text: &str) -> Mapping {
+    let Some(map) = split(text).and_then(|(yaml, _)| parse(yaml)) else {
+        return Mapping::new();
+    };
+    map.into_iter()
+        .filter(|(k, _)| !k.as_str().is_some_and(|k| ENGINE_KEYS.contains(&k)))
+        .collect()
+}
+
+/// `text` with the `unknown` entries it lacks added to the end of its
+/// frontmatter. `None` if nothing needed adding (or there is no frontmatter).
+pub fn restore_unknown_keys(text: &str, unknown: &Mapping) -> Option<String> {
+    let (yaml, rest) = split(text)?;
+    let present = parse(yaml).unwrap_or_default();
+    let missing: Mapping = unknown
+        .iter()
+        .filter(|(k, _)| !present.contains_key(*k))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+    let extra = serde_yaml::to_string(&missing).ok()?;
+    let mut out = format!("---\n{}", yaml);
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&extra);
+    out.push_str(rest);
+    Some(out)
+}
+
+/// Write the `unknown` entries back into the recipe file at `path`.
+pub fn restore_unknown_keys_in_file(path: &Path, unknown: &Mapping) -> Result<(), String> {
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    match restore_unknown_keys(&text, unknown) {
+        Some(updated) => crate::utils::write_atomically(path, &updated),
+        None => Ok(()),
+    }
+}
+
+/// Path of the Markdown file the recipe `title` is stored in.
+pub fn recipe_path(data_dir: &Path, dm: &DataManager, title: &str) -> Option<PathBuf> {
+    Some(file_of(data_dir, dm.get_recipe(title)?))
+}
+
+/// Path of the Markdown file `recipe` is stored in.
+pub fn file_of(data_dir: &Path, recipe: &Recipe) -> PathBuf {
+    data_dir.join("recipes").join(format!("{}.md", recipe.file_stem))
+}
+
+/// Unknown frontmatter of the recipe `title`; none if its file can't be read.
+pub fn read_unknown_keys(data_dir: &Path, dm: &DataManager, title: &str) -> Mapping {
+    recipe_path(data_dir, dm, title)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| unknown_keys(&text))
+        .unwrap_or_default()
+}
+
+/// Run `write`, an engine write that saves the recipe `title` as `new_title`,
+/// and put the unknown frontmatter of its file back afterwards. Engine errors
+/// are returned with their cause chain.
+pub fn keep_unknown_keys<T, E: std::error::Error>(
+    dm: &mut DataManager,
+    data_dir: &Path,
+    title: &str,
+    new_title: &str,
+    write: impl FnOnce(&mut DataManager) -> Result<T, E>,
+) -> Result<T, String> {
+    let unknown = read_unknown_keys(data_dir, dm, title);
+    write_with_unknown_keys(dm, data_dir, new_title, &unknown, write)
+}
+
+/// Like [`keep_unknown_keys`], but put `unknown` into the saved recipe `title`.
+pub fn write_with_unknown_keys<T, E: std::error::Error>(
+    dm: &mut DataManager,
+    data_dir: &Path,
+    title: &str,
+    unknown: &Mapping,
+    write: impl FnOnce(&mut DataManager) -> Result<T, E>,
+) -> Result<T, String> {
+    let result = write(dm).map_err(|e| error_chain(&e))?;
+    if let Some(path) = recipe_path(data_dir, dm, title) {
+        if let Err(e) = restore_unknown_keys_in_file(&path, unknown) {
+            log::warn!("Could not keep extra frontmatter: {}", e);
+        }
+    }
+    Ok(result)
+}
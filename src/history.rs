@@ -0,0 +1,319 @@
+/// Undo/redo history for data-mutating messages.
+///
+/// Each `Change` stores the state of the affected recipe or ingredient (or of
+/// several, for a pantry import) before and after the mutation. Undo and redo
+/// replay the opposite state through the `DataManager`, so the files on disk end
+/// up exactly as the engine would write them, plus the recipe frontmatter and
+/// ingredient file keys the engine doesn't know (see `frontmatter` and
+/// `ingredient_file`).
+/// An ingredient's best-before date and location are restored in the caller's
+/// `PantryDetails`, which the caller saves. A recipe's snapshot holds its image
+/// file too, since importing a new image removes the old one.
+use crate::frontmatter::{read_unknown_keys, write_with_unknown_keys};
+use crate::images::{read_image, restore_image};
+use crate::ingredient_file::{
+    ingredient_path, read_extra_keys, remember_extra_keys, write_with_extra_keys,
+};
+use crate::pantry_details::{ItemDetails, PantryDetails};
+use crate::toasts::error_chain;
+use janus_engine::{DataManager, Ingredient, PantryItem, Recipe};
+use serde_yaml::Mapping;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of changes kept on the undo stack.
+const HISTORY_LIMIT: usize = 50;
+
+/// State of one recipe or ingredient at a point in time (`None` = did not exist).
+#[derive(Clone)]
+pub enum Snapshot {
+    Recipe(Option<RecipeState>),
+    Ingredient(Option<IngredientState>),
+    /// Several snapshots changed together; undone and redone as one.
+    Group(Vec<Snapshot>),
+}
+
+/// A recipe as saved: the engine's data, the frontmatter it doesn't know and
+/// its image file, which importing a new image may replace.
+#[derive(Clone)]
+pub struct RecipeState {
+    pub recipe: Recipe,
+    pub frontmatter: Mapping,
+    pub image: Option<(PathBuf, Vec<u8>)>,
+}
+
+/// An ingredient as saved: the engine's data, its pantry entry and details, and
+/// the entries of its file the engine doesn't know (threshold, conversion data, …).
+#[derive(Clone)]
+pub struct IngredientState {
+    pub ingredient: Ingredient,
+    pub pantry: Option<PantryItem>,
+    pub details: ItemDetails,
+    pub extra: Mapping,
+}
+
+impl Snapshot {
+    /// The files the recipes or ingredients were stored in, if they existed.
+    pub fn files(&self, data_dir: &Path) -> Vec<PathBuf> {
+        match self {
+            Snapshot::Recipe(state) => state
+                .iter()
+                .map(|s| crate::frontmatter::file_of(data_dir, &s.recipe))
+                .collect(),
+            Snapshot::Ingredient(state) => state
+                .iter()
+                .map(|s| crate::ingredient_file::file_of(data_dir, &s.ingredient))
+                .collect(),
+            Snapshot::Group(snapshots) => {
+                snapshots.iter().flat_map(|s| s.files(data_dir)).collect()
+            }
+        }
+    }
+
+    /// Whether restoring the snapshot may change the pantry.
+    pub fn touches_pantry(&self) -> bool {
+        match self {
+            Snapshot::Recipe(_) => false,
+            Snapshot::Ingredient(_) => true,
+            Snapshot::Group(snapshots) => snapshots.iter().any(Snapshot::touches_pantry),
+        }
+    }
+
+    /// Capture the current state of the recipe with `title`, the unknown
+    /// frontmatter of its file in `data_dir` and its image.
+    pub fn recipe(dm: &DataManager, data_dir: &Path, title: &str) -> Self {
+        Snapshot::Recipe(dm.get_recipe(title).cloned().map(|recipe| RecipeState {
+            image: read_image(data_dir, &recipe),
+            frontmatter: read_unknown_keys(data_dir, dm, title),
+            recipe,
+        }))
+    }
+
+    /// Capture the current state of the ingredient `name`, its pantry entry and
+    /// details, and the extra keys of its file in `data_dir`.
+    pub fn ingredient(
+        dm: &DataManager,
+        data_dir: &Path,
+        details: &PantryDetails,
+        name: &str,
+    ) -> Self {
+        Snapshot::Ingredient(
+            dm.get_ingredient(name)
+                .cloned()
+                .map(|ingredient| IngredientState {
+                    ingredient,
+                    pantry: dm.get_pantry_item(name).cloned(),
+                    details: details.item(name),
+                    extra: read_extra_keys(&ingredient_path(data_dir, dm, name)),
+                }),
+        )
+    }
+}
+
+/// One undoable mutation.
+#[derive(Clone)]
+pub struct Change {
+    /// Human-readable description, e.g. `Deleted "Lasagna"`.
+    pub label: String,
+    pub before: Snapshot,
+    pub after: Snapshot,
+}
+
+/// A recorded change and the id it keeps while it moves between the stacks.
+struct Entry {
+    id: u64,
+    change: Change,
+}
+
+#[derive(Default)]
+pub struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    next_id: u64,
+}
+
+impl History {
+    /// Record a completed mutation. Clears the redo stack. Returns the change's
+    /// id, which [`History::undo_id`] reports while it is the next to undo.
+    pub fn record(&mut self, change: Change) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.undo.push(Entry { id, change });
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        id
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// The change the next undo would revert.
+    pub fn undo_change(&self) -> Option<&Change> {
+        self.undo.last().map(|e| &e.change)
+    }
+
+    /// The change the next redo would re-apply.
+    pub fn redo_change(&self) -> Option<&Change> {
+        self.redo.last().map(|e| &e.change)
+    }
+
+    /// Id of the change the next undo would revert.
+    pub fn undo_id(&self) -> Option<u64> {
+        self.undo.last().map(|e| e.id)
+    }
+
+    /// Label of the change the next undo would revert.
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo_change().map(|c| c.label.as_str())
+    }
+
+    /// Label of the change the next redo would re-apply.
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo_change().map(|c| c.label.as_str())
+    }
+
+    /// Revert the most recent change. Returns its label, or `None` if there was
+    /// nothing to undo. On error the change stays on the undo stack.
+    pub fn undo(
+        &mut self,
+        dm: &mut DataManager,
+        data_dir: &Path,
+        details: &mut PantryDetails,
+    ) -> Result<Option<String>, String> {
+        let Some(entry) = self.undo.pop() else {
+            return Ok(None);
+        };
+        let change = &entry.change;
+        if let Err(e) = apply(dm, data_dir, details, &change.after, &change.before) {
+            self.undo.push(entry);
+            return Err(e);
+        }
+        let label = change.label.clone();
+        self.redo.push(entry);
+        Ok(Some(label))
+    }
+
+    /// Re-apply the most recently undone change.
+    pub fn redo(
+        &mut self,
+        dm: &mut DataManager,
+        data_dir: &Path,
+        details: &mut PantryDetails,
+    ) -> Result<Option<String>, String> {
+        let Some(entry) = self.redo.pop() else {
+            return Ok(None);
+        };
+        let change = &entry.change;
+        if let Err(e) = apply(dm, data_dir, details, &change.before, &change.after) {
+            self.redo.push(entry);
+            return Err(e);
+        }
+        let label = change.label.clone();
+        self.undo.push(entry);
+        Ok(Some(label))
+    }
+}
+
+/// Put back the image file `state` refers to, in case a newer image replaced it.
+fn restore_recipe_image(state: &RecipeState) -> Result<(), String> {
+    match &state.image {
+        Some((path, bytes)) => restore_image(path, bytes),
+        None => Ok(()),
+    }
+}
+
+/// Move the data in `data_dir` and `details` from state `from` (what is on disk
+/// now) to state `to`.
+fn apply(
+    dm: &mut DataManager,
+    data_dir: &Path,
+    details: &mut PantryDetails,
+    from: &Snapshot,
+    to: &Snapshot,
+) -> Result<(), String> {
+    match (from, to) {
+        (Snapshot::Recipe(current), Snapshot::Recipe(target)) => match (current, target) {
+            (Some(cur), None) => dm
+                .delete_recipe(&cur.recipe.title)
+                .map(|_| ())
+                .map_err(|e| error_chain(&e)),
+            (None, Some(state)) => {
+                let r = &state.recipe;
+                restore_recipe_image(state)?;
+                write_with_unknown_keys(dm, data_dir, &r.title, &state.frontmatter, |dm| {
+                    dm.create_recipe(r.clone())
+                })
+                .map(|_| ())
+            }
+            (Some(cur), Some(state)) => {
+                let r = &state.recipe;
+                restore_recipe_image(state)?;
+                write_with_unknown_keys(dm, data_dir, &r.title, &state.frontmatter, |dm| {
+                    dm.update_recipe(&cur.recipe.title, r.clone())
+                })
+                .map(|_| ())
+            }
+            (None, None) => Ok(()),
+        },
+        (Snapshot::Ingredient(current), Snapshot::Ingredient(target)) => match (current, target) {
+            (Some(cur), None) => {
+                dm.delete_ingredient(&cur.ingredient.name)
+                    .map_err(|e| error_chain(&e))?;
+                remember_extra_keys(&cur.ingredient.name, None);
+                details.set_item(&cur.ingredient.name, ItemDetails::default());
+                Ok(())
+            }
+            (None, Some(state)) => {
+                let name = &state.ingredient.name;
+                write_with_extra_keys(dm, data_dir, name, name, &state.extra, |dm| {
+                    dm.create_ingredient(state.ingredient.clone())
+                })?;
+                if let Some(p) = &state.pantry {
+                    dm.update_pantry_item(name, p.quantity, Some(p.quantity_type.clone()))
+                        .map_err(|e| error_chain(&e))?;
+                }
+                details.set_item(name, state.details.clone());
+                Ok(())
+            }
+            (Some(cur), Some(state)) => {
+                let (qty, qty_type, remove) = match &state.pantry {
+                    Some(p) => (p.quantity, Some(p.quantity_type.clone()), false),
+                    None => (None, None, true),
+                };
+                let (name, ing) = (&cur.ingredient.name, &state.ingredient);
+                write_with_extra_keys(dm, data_dir, name, &ing.name, &state.extra, |dm| {
+                    dm.update_ingredient_with_pantry(name, ing.clone(), qty, qty_type, remove)
+                })?;
+                details.set_item(name, ItemDetails::default());
+                details.set_item(&ing.name, state.details.clone());
+                Ok(())
+            }
+            (None, None) => Ok(()),
+        },
+        // A failing member doesn't stop the others
+        (Snapshot::Group(current), Snapshot::Group(target)) if current.len() == target.len() => {
+            let errors: Vec<String> = current
+                .iter()
+                .zip(target)
+                .filter_map(|(from, to)| apply(dm, data_dir, details, from, to).err())
+                .collect();
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors.join("\n"))
+            }
+        }
+        _ => Err("mismatched undo snapshot".to_string()),
+    }
+}
@@ -79,6 +79,8 @@ pub struct Strings {
     pub nav_recipes: &'static str,
     pub nav_pantry: &'static str,
     pub nav_settings: &'static str,
+    pub nav_shopping: &'static str,
+    pub nav_planner: &'static str,
 
     // Common actions
     pub edit: &'static str,
@@ -86,20 +88,111 @@ pub struct Strings {
     pub cancel: &'static str,
     pub save: &'static str,
     pub add: &'static str,
+    pub open: &'static str,
+    pub import: &'static str,
     pub ok: &'static str,
     pub browse: &'static str,
+    pub undo: &'static str,
+    pub redo: &'static str,
+
+    // Notifications
+    pub details: &'static str,
+    pub error_dialog_title: &'static str,
+    pub copy_details: &'static str,
+    pub history_failed: &'static str,
 
     // Recipes tab
     pub search_recipes: &'static str,
     pub add_recipe: &'static str,
+    pub import_recipe: &'static str,
     pub no_recipes_found: &'static str,
+    pub no_recipes_match: &'static str,
+    pub recipe_filter_tooltip: &'static str,
+    pub cookable_now_label: &'static str,
+    pub missing_at_most_label: &'static str,
+    pub max_prep_label: &'static str,
+    pub max_total_label: &'static str,
+    pub servings_range_label: &'static str,
+    pub any_choice: &'static str,
     pub recipe_placeholder_title: &'static str,
     pub recipe_placeholder_desc: &'static str,
     pub all_required_available: &'static str,
     pub ingredients_heading: &'static str,
     pub instructions_heading: &'static str,
+    pub show_in_pantry: &'static str,
     pub optional_suffix: &'static str,
+    pub servings_label: &'static str,
+    pub batches_label: &'static str,
+    pub scale_tooltip: &'static str,
     pub no_data_dir: &'static str,
+    pub add_to_basket: &'static str,
+    pub remove_from_basket: &'static str,
+    pub cooked_it: &'static str,
+    pub cooked_it_tooltip: &'static str,
+
+    // Shopping tab
+    pub basket_heading: &'static str,
+    pub basket_empty_title: &'static str,
+    pub basket_empty_desc: &'static str,
+    pub clear_basket: &'static str,
+    pub shopping_list_heading: &'static str,
+    pub shopping_all_covered: &'static str,
+    pub rebuild_list: &'static str,
+    pub move_to_pantry: &'static str,
+    pub shopping_save_failed: &'static str,
+
+    // Cooked-it dialog
+    pub cooked_dialog_title: &'static str,
+    pub cooked_dialog_desc: &'static str,
+    pub used_up: &'static str,
+    pub not_in_pantry: &'static str,
+    pub update_pantry: &'static str,
+    pub cooking_log_save_failed: &'static str,
+
+    // Cooking history
+    pub cooking_history: &'static str,
+    pub history_empty_title: &'static str,
+    pub history_empty_desc: &'static str,
+    pub sort_tooltip: &'static str,
+    pub sort_by_name: &'static str,
+    pub sort_best_coverage: &'static str,
+    pub sort_fewest_missing: &'static str,
+    pub sort_shortest_time: &'static str,
+    pub sort_recently_modified: &'static str,
+    pub sort_least_recently_cooked: &'static str,
+    pub sort_most_cooked: &'static str,
+    pub sort_use_it_up: &'static str,
+    pub group_by_cookability_tooltip: &'static str,
+    pub group_cookable_now: &'static str,
+    pub group_almost: &'static str,
+    pub group_needs_shopping: &'static str,
+
+    // Planner tab
+    pub meal_breakfast: &'static str,
+    pub meal_lunch: &'static str,
+    pub meal_dinner: &'static str,
+    pub previous_week: &'static str,
+    pub next_week: &'static str,
+    pub this_week: &'static str,
+    pub add_to_plan: &'static str,
+    pub remove_from_plan: &'static str,
+    pub planner_recipes_hint: &'static str,
+    pub day_cookable: &'static str,
+    pub missing_this_week: &'static str,
+    pub week_all_covered: &'static str,
+    pub add_week_to_basket: &'static str,
+    pub meal_plan_save_failed: &'static str,
+
+    // Cook mode
+    pub cook_mode_tooltip: &'static str,
+    pub previous_step: &'static str,
+    pub next_step: &'static str,
+    pub finish_cooking: &'static str,
+    pub cook_mode_keys_hint: &'static str,
+    pub step_ingredients_heading: &'static str,
+    pub timer_start_tooltip: &'static str,
+    pub timer_cancel_tooltip: &'static str,
+    pub timer_done: &'static str,
 
     // Recipe dialog
     pub add_recipe_dialog_title: &'static str,
@@ -110,6 +203,12 @@ pub struct Strings {
     pub downtime_field: &'static str,
     pub servings_field: &'static str,
     pub tags_field: &'static str,
+    pub image_field: &'static str,
+    pub no_image: &'static str,
+    pub choose_image: &'static str,
+    pub choose_image_title: &'static str,
+    pub remove_image: &'static str,
+    pub image_import_failed: &'static str,
     pub ingredients_group: &'static str,
     pub qty_placeholder: &'static str,
     pub ingredient_placeholder: &'static str,
@@ -120,12 +219,54 @@ pub struct Strings {
     pub instructions_group: &'static str,
     pub delete_recipe_body: &'static str,
 
+    // Recipe import and export
+    pub import_recipe_dialog_title: &'static str,
+    pub import_recipe_hint: &'static str,
+    pub open_saved_page: &'static str,
+    pub open_saved_page_title: &'static str,
+    pub web_pages_filter: &'static str,
+    pub recipe_import_failed: &'static str,
+    pub export_cooklang_tooltip: &'static str,
+    pub export_recipe_title: &'static str,
+    pub cooklang_filter: &'static str,
+    pub recipe_export_failed: &'static str,
+    pub unmatched_ingredients_body: &'static str,
+    pub create_all: &'static str,
+    pub not_now: &'static str,
+    pub export_menu_tooltip: &'static str,
+    pub export_pdf: &'static str,
+    pub print_tooltip: &'static str,
+    pub print_failed: &'static str,
+    pub pdf_filter: &'static str,
+    pub export_cookbook_tooltip: &'static str,
+    pub cookbook_dialog_title: &'static str,
+    pub cookbook_dialog_body: &'static str,
+    pub all_recipes: &'static str,
+    pub contents_heading: &'static str,
+    pub export: &'static str,
+
     // Pantry tab
     pub search_pantry: &'static str,
     pub add_ingredient_btn: &'static str,
     pub no_ingredients_found: &'static str,
+    pub no_ingredients_match_filters: &'static str,
+    pub filter_label: &'static str,
+    pub filter_tooltip: &'static str,
+    pub categories_heading: &'static str,
+    pub tags_heading: &'static str,
+    pub clear_filters: &'static str,
     pub uncategorised: &'static str,
     pub in_stock_only_label: &'static str,
+    pub expiring_soon_label: &'static str,
+    pub group_by_location_label: &'static str,
+    pub edit_storage_locations: &'static str,
+    pub no_location: &'static str,
+    pub running_low_heading: &'static str,
+    pub running_low_tooltip: &'static str,
+    pub restock_all: &'static str,
+    pub restock_caption: &'static str,
+    pub pantry_details_save_failed: &'static str,
+    pub unreadable_file_details: &'static str,
     pub used_in_recipes: &'static str,
     pub pantry_heading: &'static str,
     pub in_stock_status: &'static str,
@@ -134,6 +275,21 @@ pub struct Strings {
     pub ingredient_placeholder_desc: &'static str,
     pub delete_ingredient_body: &'static str,
 
+    // Pantry import and export
+    pub import_pantry_title: &'static str,
+    pub export_pantry_title: &'static str,
+    pub pantry_files_filter: &'static str,
+    pub pantry_import_failed: &'static str,
+    pub pantry_export_failed: &'static str,
+    pub pantry_import_desc: &'static str,
+    pub pantry_row_new: &'static str,
+    pub pantry_row_new_ingredient: &'static str,
+    pub pantry_row_unchanged: &'static str,
+    pub replace_pantry_amounts: &'static str,
+    pub replace_pantry_amounts_subtitle: &'static str,
+    pub missing_ingredient: &'static str,
+    pub duplicate_pantry_row: &'static str,
+
     // Ingredient dialog
     pub add_ingredient_dialog_title: &'static str,
     pub edit_ingredient_dialog_title: &'static str,
@@ -144,8 +300,16 @@ pub struct Strings {
     pub pantry_group: &'static str,
     pub in_pantry_field: &'static str,
     pub quantity_field: &'static str,
+    pub best_before_field: &'static str,
+    pub storage_location_field: &'static str,
+    pub min_quantity_field: &'static str,
     pub unit_field: &'static str,
 
+    // Storage locations dialog
+    pub storage_locations_title: &'static str,
+    pub storage_locations_desc: &'static str,
+    pub add_storage_location: &'static str,
+
     // Settings
     pub settings_group_data: &'static str,
     pub settings_data_desc: &'static str,
@@ -160,6 +324,10 @@ pub struct Strings {
     pub lang_system: &'static str,
     pub lang_en: &'static str,
     pub lang_da: &'static str,
+    pub settings_group_units: &'static str,
+    pub settings_measurement_system: &'static str,
+    pub system_metric: &'static str,
+    pub system_imperial: &'static str,
 }
 
 // ── English ───────────────────────────────────────────────────────────────────
@@ -168,18 +336,38 @@ pub static EN: Strings = Strings {
     nav_recipes: "Recipes",
     nav_pantry: "Pantry",
     nav_settings: "Settings",
+    nav_shopping: "Shopping",
+    nav_planner: "Planner",
 
     edit: "Edit",
     delete: "Delete",
     cancel: "Cancel",
     save: "Save",
     add: "Add",
+    open: "Open",
+    import: "Import",
     ok: "OK",
     browse: "Browse…",
+    undo: "Undo",
+    redo: "Redo",
+
+    details: "Details",
+    error_dialog_title: "Error",
+    copy_details: "Copy details",
+    history_failed: "Could not undo/redo the last change",
 
     search_recipes: "Search recipes…",
     add_recipe: "Add Recipe",
+    import_recipe: "Import Recipe…",
     no_recipes_found: "No recipes found",
+    no_recipes_match: "No recipes match all filters",
+    recipe_filter_tooltip: "Filter by tag, time, servings or pantry",
+    cookable_now_label: "Cookable now",
+    missing_at_most_label: "Missing at most",
+    max_prep_label: "Max prep time",
+    max_total_label: "Max total time",
+    servings_range_label: "Servings",
+    any_choice: "Any",
     recipe_placeholder_title: "Recipes",
     recipe_placeholder_desc:
         "Select a recipe to view it, or add a new one.\n\
@@ -187,8 +375,72 @@ pub static EN: Strings = Strings {
     all_required_available: "✓ All required ingredients available — ready to cook!",
     ingredients_heading: "Ingredients",
     instructions_heading: "Instructions",
+    show_in_pantry: "Show in Pantry",
     optional_suffix: " (optional)",
+    servings_label: "Servings",
+    batches_label: "Batches",
+    scale_tooltip: "Scale ingredient amounts for this session",
     no_data_dir: "No data directory set",
+    add_to_basket: "Add to shopping list",
+    remove_from_basket: "Remove from shopping list",
+    cooked_it: "Cooked it",
+    cooked_it_tooltip: "Take the ingredients used from the pantry",
+
+    basket_heading: "Cooking this week",
+    basket_empty_title: "No recipes planned",
+    basket_empty_desc: "Add recipes from the Recipes tab to build a shopping list",
+    clear_basket: "Clear",
+    shopping_list_heading: "Shopping list",
+    shopping_all_covered: "✓ The pantry covers everything",
+    rebuild_list: "Rebuild from pantry and recipes",
+    move_to_pantry: "Move checked to pantry",
+    shopping_save_failed: "Could not save the shopping list",
+    cooked_dialog_title: "Cooked it",
+    cooked_dialog_desc: "Amounts to take from the pantry. Items that run out are removed.",
+    used_up: "Used up",
+    not_in_pantry: "Not in pantry",
+    update_pantry: "Update pantry",
+    cooking_log_save_failed: "Could not save the cooking log",
+    cooking_history: "Cooking history",
+    history_empty_title: "Nothing cooked yet",
+    history_empty_desc: "Use \"Cooked it\" on a recipe to start the log",
+    sort_tooltip: "Sort recipes",
+    sort_by_name: "Name",
+    sort_best_coverage: "Best pantry coverage",
+    sort_fewest_missing: "Fewest missing",
+    sort_shortest_time: "Shortest total time",
+    sort_recently_modified: "Recently added or changed",
+    sort_least_recently_cooked: "Least recently cooked",
+    sort_most_cooked: "Most cooked",
+    sort_use_it_up: "Use it up",
+    group_by_cookability_tooltip: "Group by what you can cook",
+    group_cookable_now: "Cookable now",
+    group_almost: "Almost (1–2 missing)",
+    group_needs_shopping: "Needs shopping",
+    meal_breakfast: "Breakfast",
+    meal_lunch: "Lunch",
+    meal_dinner: "Dinner",
+    previous_week: "Previous week",
+    next_week: "Next week",
+    this_week: "This week",
+    add_to_plan: "Add a recipe",
+    remove_from_plan: "Remove from the plan",
+    planner_recipes_hint: "Drag a recipe onto a day",
+    day_cookable: "Everything planned for this day is in the pantry",
+    missing_this_week: "Missing for this week",
+    week_all_covered: "The pantry covers everything planned this week",
+    add_week_to_basket: "Add week to shopping list",
+    meal_plan_save_failed: "Could not save the meal plan",
+
+    cook_mode_tooltip: "Follow the recipe step by step in full screen",
+    previous_step: "Previous",
+    next_step: "Next",
+    finish_cooking: "Done",
+    cook_mode_keys_hint: "Space or → for the next step, ← to go back, Esc to leave",
+    step_ingredients_heading: "Ingredients in this step",
+    timer_start_tooltip: "Start the timer",
+    timer_cancel_tooltip: "Stop the timer",
+    timer_done: "Time's up",
 
     add_recipe_dialog_title: "Add Recipe",
     edit_recipe_dialog_title: "Edit Recipe",
@@ -198,6 +450,12 @@ pub static EN: Strings = Strings {
     downtime_field: "Oven / resting time (minutes)",
     servings_field: "Servings",
     tags_field: "Tags (comma-separated)",
+    image_field: "Image",
+    no_image: "No image",
+    choose_image: "Choose…",
+    choose_image_title: "Choose Recipe Image",
+    remove_image: "Remove the image",
+    image_import_failed: "Could not copy the recipe image",
     ingredients_group: "Ingredients",
     qty_placeholder: "qty",
     ingredient_placeholder: "ingredient",
@@ -206,13 +464,54 @@ pub static EN: Strings = Strings {
     optional_check_tooltip: "Optional ingredient",
     add_ingredient_row_btn: "+ Add ingredient",
     instructions_group: "Instructions",
-    delete_recipe_body: "This recipe will be permanently removed. This cannot be undone.",
+    delete_recipe_body: "This recipe will be removed. You can undo this with Ctrl+Z.",
+
+    import_recipe_dialog_title: "Import Recipe",
+    import_recipe_hint: "Open a Cooklang file or a recipe page saved from a website, or paste the page's HTML or schema.org JSON-LD below. Web recipes open in the editor for review before they are added.",
+    open_saved_page: "Open Saved Page…",
+    open_saved_page_title: "Open Saved Recipe Page",
+    web_pages_filter: "Cooklang files and web pages",
+    recipe_import_failed: "Could not import the recipe",
+    export_cooklang_tooltip: "Export as Cooklang…",
+    export_recipe_title: "Export Recipe",
+    cooklang_filter: "Cooklang files",
+    recipe_export_failed: "Could not export the recipe",
+    unmatched_ingredients_body: "The imported recipes use ingredients that aren't in your ingredient library yet. Create them now?",
+    create_all: "Create All",
+    not_now: "Not Now",
+    export_menu_tooltip: "Export",
+    export_pdf: "Export as PDF…",
+    print_tooltip: "Print…",
+    print_failed: "Could not print",
+    pdf_filter: "PDF documents",
+    export_cookbook_tooltip: "Export Cookbook…",
+    cookbook_dialog_title: "Export Cookbook",
+    cookbook_dialog_body: "Collect recipes into a PDF cookbook with a table of contents.",
+    all_recipes: "All recipes",
+    contents_heading: "Contents",
+    export: "Export",
 
     search_pantry: "Search ingredients…",
     add_ingredient_btn: "Add Ingredient",
     no_ingredients_found: "No ingredients found",
+    no_ingredients_match_filters: "No ingredients match the filters",
+    filter_label: "Filter",
+    filter_tooltip: "Filter by category or tag",
+    categories_heading: "Categories",
+    tags_heading: "Tags",
+    clear_filters: "Clear filters",
     uncategorised: "Uncategorised",
     in_stock_only_label: "In stock only",
+    expiring_soon_label: "Expiring soon",
+    group_by_location_label: "Group by location",
+    edit_storage_locations: "Edit storage locations",
+    no_location: "No location",
+    running_low_heading: "Running low",
+    running_low_tooltip: "Below the amount to keep in stock",
+    restock_all: "Add all to shopping list",
+    restock_caption: "Restock",
+    pantry_details_save_failed: "Could not save pantry details",
+    unreadable_file_details: "Fix or remove the file, then reload the data.",
     used_in_recipes: "Used in recipes",
     pantry_heading: "Pantry",
     in_stock_status: "✓ In stock",
@@ -220,7 +519,21 @@ pub static EN: Strings = Strings {
     ingredient_placeholder_title: "Pantry",
     ingredient_placeholder_desc: "Select an ingredient to view details, or add a new one.",
     delete_ingredient_body:
-        "This will remove the ingredient and its pantry entry. This cannot be undone.",
+        "This will remove the ingredient and its pantry entry. You can undo this with Ctrl+Z.",
+
+    import_pantry_title: "Import Pantry",
+    export_pantry_title: "Export Pantry",
+    pantry_files_filter: "CSV and JSON files",
+    pantry_import_failed: "Could not import the pantry",
+    pantry_export_failed: "Could not export the pantry",
+    pantry_import_desc: "Nothing is changed until you press Import. Unknown ingredients are added to the ingredient library. Imported items are dated today, not with the file's dates, and the file's categories are only used for new ingredients.",
+    pantry_row_new: "Add",
+    pantry_row_new_ingredient: "New ingredient",
+    pantry_row_unchanged: "Unchanged",
+    replace_pantry_amounts: "Replace existing amounts",
+    replace_pantry_amounts_subtitle: "When off, items already in the pantry are left as they are",
+    missing_ingredient: "no ingredient name",
+    duplicate_pantry_row: "listed more than once",
 
     add_ingredient_dialog_title: "Add Ingredient",
     edit_ingredient_dialog_title: "Edit Ingredient",
@@ -231,8 +544,15 @@ pub static EN: Strings = Strings {
     pantry_group: "Pantry",
     in_pantry_field: "In pantry",
     quantity_field: "Quantity",
+    best_before_field: "Best before (YYYY-MM-DD)",
+    storage_location_field: "Storage location",
+    min_quantity_field: "Keep at least (e.g. 2 l)",
     unit_field: "Unit",
 
+    storage_locations_title: "Storage Locations",
+    storage_locations_desc: "Where pantry items are kept. Removing a location clears it from the items stored there.",
+    add_storage_location: "Add location",
+
     settings_group_data: "Data",
     settings_data_desc: "Location of your recipes, ingredients and pantry files.",
     settings_data_dir: "Data Directory",
@@ -246,6 +566,10 @@ pub static EN: Strings = Strings {
     lang_system: "System Default",
     lang_en: "English",
     lang_da: "Danish",
+    settings_group_units: "Units",
+    settings_measurement_system: "Measurement system",
+    system_metric: "Metric (g, dl)",
+    system_imperial: "US customary (oz, cups)",
 };
 
 // ── Danish ────────────────────────────────────────────────────────────────────
@@ -254,18 +578,38 @@ pub static DA: Strings = Strings {
     nav_recipes: "Opskrifter",
     nav_pantry: "Spisekammer",
     nav_settings: "Indstillinger",
+    nav_shopping: "Indkøb",
+    nav_planner: "Madplan",
 
     edit: "Rediger",
     delete: "Slet",
     cancel: "Annuller",
     save: "Gem",
     add: "Tilføj",
+    open: "Åbn",
+    import: "Importér",
     ok: "OK",
     browse: "Gennemse…",
+    undo: "Fortryd",
+    redo: "Gentag",
+
+    details: "Detaljer",
+    error_dialog_title: "Fejl",
+    copy_details: "Kopiér detaljer",
+    history_failed: "Kunne ikke fortryde/gentage den seneste ændring",
 
     search_recipes: "Søg i opskrifter…",
     add_recipe: "Tilføj opskrift",
+    import_recipe: "Importér opskrift…",
     no_recipes_found: "Ingen opskrifter fundet",
+    no_recipes_match: "Ingen opskrifter passer til alle filtre",
+    recipe_filter_tooltip: "Filtrér efter tag, tid, portioner eller spisekammer",
+    cookable_now_label: "Kan laves nu",
+    missing_at_most_label: "Mangler højst",
+    max_prep_label: "Maks. forberedelse",
+    max_total_label: "Maks. samlet tid",
+    servings_range_label: "Portioner",
+    any_choice: "Alle",
     recipe_placeholder_title: "Opskrifter",
     recipe_placeholder_desc:
         "Vælg en opskrift for at se den, eller tilføj en ny.\n\
@@ -273,8 +617,72 @@ pub static DA: Strings = Strings {
     all_required_available: "✓ Alle nødvendige ingredienser er tilgængelige — klar til madlavning!",
     ingredients_heading: "Ingredienser",
     instructions_heading: "Fremgangsmåde",
+    show_in_pantry: "Vis i spisekammeret",
     optional_suffix: " (valgfri)",
+    servings_label: "Portioner",
+    batches_label: "Gange opskriften",
+    scale_tooltip: "Skalér ingrediensmængderne i denne session",
     no_data_dir: "Ingen datamappe valgt",
+    add_to_basket: "Føj til indkøbslisten",
+    remove_from_basket: "Fjern fra indkøbslisten",
+    cooked_it: "Lavet",
+    cooked_it_tooltip: "Træk de brugte ingredienser fra spisekammeret",
+
+    basket_heading: "Skal laves i denne uge",
+    basket_empty_title: "Ingen opskrifter planlagt",
+    basket_empty_desc: "Tilføj opskrifter fra fanen Opskrifter for at lave en indkøbsliste",
+    clear_basket: "Ryd",
+    shopping_list_heading: "Indkøbsliste",
+    shopping_all_covered: "✓ Spisekammeret dækker det hele",
+    rebuild_list: "Opdater ud fra spisekammer og opskrifter",
+    move_to_pantry: "Flyt afkrydsede til spisekammeret",
+    shopping_save_failed: "Kunne ikke gemme indkøbslisten",
+    cooked_dialog_title: "Lavet",
+    cooked_dialog_desc: "Mængder der trækkes fra spisekammeret. Varer der slipper op fjernes.",
+    used_up: "Brugt op",
+    not_in_pantry: "Ikke i spisekammeret",
+    update_pantry: "Opdater spisekammeret",
+    cooking_log_save_failed: "Kunne ikke gemme madloggen",
+    cooking_history: "Madlog",
+    history_empty_title: "Intet lavet endnu",
+    history_empty_desc: "Brug \"Lavet\" på en opskrift for at starte loggen",
+    sort_tooltip: "Sortér opskrifter",
+    sort_by_name: "Navn",
+    sort_best_coverage: "Bedst dækket af spisekammeret",
+    sort_fewest_missing: "Færrest manglende",
+    sort_shortest_time: "Kortest samlet tid",
+    sort_recently_modified: "Senest tilføjet eller ændret",
+    sort_least_recently_cooked: "Længst siden lavet",
+    sort_most_cooked: "Oftest lavet",
+    sort_use_it_up: "Brug det op",
+    group_by_cookability_tooltip: "Gruppér efter hvad du kan lave",
+    group_cookable_now: "Kan laves nu",
+    group_almost: "Næsten (1–2 mangler)",
+    group_needs_shopping: "Kræver indkøb",
+    meal_breakfast: "Morgenmad",
+    meal_lunch: "Frokost",
+    meal_dinner: "Aftensmad",
+    previous_week: "Forrige uge",
+    next_week: "Næste uge",
+    this_week: "Denne uge",
+    add_to_plan: "Tilføj en opskrift",
+    remove_from_plan: "Fjern fra madplanen",
+    planner_recipes_hint: "Træk en opskrift over på en dag",
+    day_cookable: "Alt til denne dag er i spisekammeret",
+    missing_this_week: "Mangler til denne uge",
+    week_all_covered: "Spisekammeret dækker alt på ugens madplan",
+    add_week_to_basket: "Føj ugen til indkøbslisten",
+    meal_plan_save_failed: "Kunne ikke gemme madplanen",
+
+    cook_mode_tooltip: "Følg opskriften trin for trin i fuld skærm",
+    previous_step: "Forrige",
+    next_step: "Næste",
+    finish_cooking: "Færdig",
+    cook_mode_keys_hint: "Mellemrum eller → for næste trin, ← for at gå tilbage, Esc for at afslutte",
+    step_ingredients_heading: "Ingredienser i dette trin",
+    timer_start_tooltip: "Start uret",
+    timer_cancel_tooltip: "Stop uret",
+    timer_done: "Tiden er gået",
 
     add_recipe_dialog_title: "Tilføj opskrift",
     edit_recipe_dialog_title: "Rediger opskrift",
@@ -284,6 +692,12 @@ pub static DA: Strings = Strings {
     downtime_field: "Ovn-/hviletid (minutter)",
     servings_field: "Portioner",
     tags_field: "Tags (kommasepareret)",
+    image_field: "Billede",
+    no_image: "Intet billede",
+    choose_image: "Vælg…",
+    choose_image_title: "Vælg billede til opskriften",
+    remove_image: "Fjern billedet",
+    image_import_failed: "Kunne ikke kopiere billedet til opskriften",
     ingredients_group: "Ingredienser",
     qty_placeholder: "antal",
     ingredient_placeholder: "ingrediens",
@@ -292,13 +706,54 @@ pub static DA: Strings = Strings {
     optional_check_tooltip: "Valgfri ingrediens",
     add_ingredient_row_btn: "+ Tilføj ingrediens",
     instructions_group: "Fremgangsmåde",
-    delete_recipe_body: "Denne opskrift fjernes permanent. Dette kan ikke fortrydes.",
+    delete_recipe_body: "Denne opskrift fjernes. Du kan fortryde med Ctrl+Z.",
+
+    import_recipe_dialog_title: "Importér opskrift",
+    import_recipe_hint: "Åbn en Cooklang-fil eller en opskriftsside gemt fra et websted, eller indsæt sidens HTML eller schema.org JSON-LD herunder. Opskrifter fra nettet åbnes i editoren, så du kan gennemse dem, før de tilføjes.",
+    open_saved_page: "Åbn gemt side…",
+    open_saved_page_title: "Åbn gemt opskriftsside",
+    web_pages_filter: "Cooklang-filer og websider",
+    recipe_import_failed: "Kunne ikke importere opskriften",
+    export_cooklang_tooltip: "Eksportér som Cooklang…",
+    export_recipe_title: "Eksportér opskrift",
+    cooklang_filter: "Cooklang-filer",
+    recipe_export_failed: "Kunne ikke eksportere opskriften",
+    unmatched_ingredients_body: "De importerede opskrifter bruger ingredienser, som endnu ikke er i dit ingrediensbibliotek. Vil du oprette dem nu?",
+    create_all: "Opret alle",
+    not_now: "Ikke nu",
+    export_menu_tooltip: "Eksportér",
+    export_pdf: "Eksportér som PDF…",
+    print_tooltip: "Udskriv…",
+    print_failed: "Kunne ikke udskrive",
+    pdf_filter: "PDF-dokumenter",
+    export_cookbook_tooltip: "Eksportér kogebog…",
+    cookbook_dialog_title: "Eksportér kogebog",
+    cookbook_dialog_body: "Saml opskrifter i en PDF-kogebog med indholdsfortegnelse.",
+    all_recipes: "Alle opskrifter",
+    contents_heading: "Indhold",
+    export: "Eksportér",
 
     search_pantry: "Søg i ingredienser…",
     add_ingredient_btn: "Tilføj ingrediens",
     no_ingredients_found: "Ingen ingredienser fundet",
+    no_ingredients_match_filters: "Ingen ingredienser passer til filtrene",
+    filter_label: "Filtrér",
+    filter_tooltip: "Filtrér efter kategori eller tag",
+    categories_heading: "Kategorier",
+    tags_heading: "Tags",
+    clear_filters: "Ryd filtre",
     uncategorised: "Ukategoriseret",
     in_stock_only_label: "Kun på lager",
+    expiring_soon_label: "Udløber snart",
+    group_by_location_label: "Gruppér efter opbevaringssted",
+    edit_storage_locations: "Redigér opbevaringssteder",
+    no_location: "Intet opbevaringssted",
+    running_low_heading: "Ved at løbe tør",
+    running_low_tooltip: "Under den mængde, der skal være på lager",
+    restock_all: "Tilføj alle til indkøbslisten",
+    restock_caption: "Genopfyldning",
+    pantry_details_save_failed: "Kunne ikke gemme spisekammerdetaljer",
+    unreadable_file_details: "Ret eller fjern filen, og indlæs derefter data igen.",
     used_in_recipes: "Bruges i opskrifter",
     pantry_heading: "Spisekammer",
     in_stock_status: "✓ På lager",
@@ -306,7 +761,21 @@ pub static DA: Strings = Strings {
     ingredient_placeholder_title: "Spisekammer",
     ingredient_placeholder_desc: "Vælg en ingrediens for at se detaljer, eller tilføj en ny.",
     delete_ingredient_body:
-        "Dette fjerner ingrediensen og dens opbevaring permanent. Dette kan ikke fortrydes.",
+        "Dette fjerner ingrediensen og dens opbevaring. Du kan fortryde med Ctrl+Z.",
+
+    import_pantry_title: "Importér spisekammer",
+    export_pantry_title: "Eksportér spisekammer",
+    pantry_files_filter: "CSV- og JSON-filer",
+    pantry_import_failed: "Kunne ikke importere spisekammeret",
+    pantry_export_failed: "Kunne ikke eksportere spisekammeret",
+    pantry_import_desc: "Intet ændres, før du trykker Importér. Ukendte ingredienser føjes til ingrediensbiblioteket. Importerede varer dateres i dag, ikke med filens datoer, og filens kategorier bruges kun til nye ingredienser.",
+    pantry_row_new: "Tilføj",
+    pantry_row_new_ingredient: "Ny ingrediens",
+    pantry_row_unchanged: "Uændret",
+    replace_pantry_amounts: "Erstat eksisterende mængder",
+    replace_pantry_amounts_subtitle: "Når slået fra, røres varer der allerede er i spisekammeret ikke",
+    missing_ingredient: "intet ingrediensnavn",
+    duplicate_pantry_row: "angivet mere end én gang",
 
     add_ingredient_dialog_title: "Tilføj ingrediens",
     edit_ingredient_dialog_title: "Rediger ingrediens",
@@ -317,8 +786,15 @@ pub static DA: Strings = Strings {
     pantry_group: "Spisekammer",
     in_pantry_field: "På lager",
     quantity_field: "Mængde",
+    best_before_field: "Bedst før (ÅÅÅÅ-MM-DD)",
+    storage_location_field: "Opbevaringssted",
+    min_quantity_field: "Hav mindst (f.eks. 2 l)",
     unit_field: "Enhed",
 
+    storage_locations_title: "Opbevaringssteder",
+    storage_locations_desc: "Hvor varerne i spisekammeret opbevares. Fjernes et sted, mister varerne dér deres opbevaringssted.",
+    add_storage_location: "Tilføj sted",
+
     settings_group_data: "Data",
     settings_data_desc: "Placering af dine opskrifter, ingredienser og spisekammerfiler.",
     settings_data_dir: "Datamappe",
@@ -332,6 +808,10 @@ pub static DA: Strings = Strings {
     lang_system: "Systemstandard",
     lang_en: "Engelsk",
     lang_da: "Dansk",
+    settings_group_units: "Enheder",
+    settings_measurement_system: "Målesystem",
+    system_metric: "Metrisk (g, dl)",
+    system_imperial: "Amerikansk (oz, cups)",
 };
 
 // ── Accessor ──────────────────────────────────────────────────────────────────
@@ -346,17 +826,47 @@ pub fn strings() -> &'static Strings {
 
 // ── Parameterised strings ─────────────────────────────────────────────────────
 
-pub fn fmt_missing_required(n: usize) -> String {
-    match active() {
-        Language::Danish => format!("⚠ Mangler {} nødvendig(e) ingrediens(er)", n),
-        _ => format!("⚠ Missing {} required ingredient(s)", n),
+/// `missing` counts the `partial` ingredients too, as the recipe filters do.
+pub fn fmt_missing_required(missing: usize, partial: usize) -> String {
+    match (active(), missing, partial) {
+        (Language::Danish, m, 0) => format!("⚠ Mangler {} nødvendig(e) ingrediens(er)", m),
+        (Language::Danish, m, p) if m == p => {
+            format!("⚠ For lidt af {} nødvendig(e) ingrediens(er)", p)
+        }
+        (Language::Danish, m, p) => {
+            format!("⚠ Mangler {} nødvendig(e) ingrediens(er), heraf {} delvist på lager", m, p)
+        }
+        (_, m, 0) => format!("⚠ Missing {} required ingredient(s)", m),
+        (_, m, p) if m == p => format!("⚠ Not enough of {} required ingredient(s)", p),
+        (_, m, p) => {
+            format!("⚠ Missing {} required ingredient(s), {} of them partly in stock", m, p)
+        }
     }
 }
 
-pub fn fmt_required_tooltip(in_stock: usize, total: usize) -> String {
+pub fn fmt_required_tooltip(in_stock: usize, partial: usize, total: usize) -> String {
+    match (active(), partial) {
+        (Language::Danish, 0) => {
+            format!("{}/{} nødvendige ingredienser på lager", in_stock, total)
+        }
+        (Language::Danish, p) => format!(
+            "{}/{} nødvendige ingredienser på lager, {} delvist",
+            in_stock, total, p
+        ),
+        (_, 0) => format!("{}/{} required ingredients in pantry", in_stock, total),
+        (_, p) => format!(
+            "{}/{} required ingredients in pantry, {} partially",
+            in_stock, total, p
+        ),
+    }
+}
+
+/// Tooltip for a partially covered ingredient: what the pantry has vs. what the
+/// recipe needs.
+pub fn fmt_partial_stock(have: &str, need: &str) -> String {
     match active() {
-        Language::Danish => format!("{}/{} nødvendige ingredienser på lager", in_stock, total),
-        _ => format!("{}/{} required ingredients in pantry", in_stock, total),
+        Language::Danish => format!("Delvist på lager: {} af {}", have, need),
+        _ => format!("Partially in pantry: {} of {}", have, need),
     }
 }
 
@@ -378,6 +888,54 @@ pub fn fmt_servings(n: u32) -> String {
     }
 }
 
+pub fn fmt_reset_servings(n: u32) -> String {
+    match active() {
+        Language::Danish => format!("Nulstil til {} (som skrevet)", n),
+        _ => format!("Reset to {} (as written)", n),
+    }
+}
+
+/// Label of the Pantry tab's filter button while `n` filters are active.
+pub fn fmt_active_filters(n: usize) -> String {
+    match active() {
+        Language::Danish => format!("Filtrér ({})", n),
+        _ => format!("Filter ({})", n),
+    }
+}
+
+/// An active recipe filter and how many recipes it allows on its own, for the
+/// empty recipe list.
+pub fn fmt_filter_reason(criterion: &crate::recipe_filter::Criterion, count: usize) -> String {
+    use crate::recipe_filter::Criterion;
+    let danish = active() == Language::Danish;
+    let label = match (criterion, danish) {
+        (Criterion::Search(text), true) => format!("Søgning \"{}\"", text),
+        (Criterion::Search(text), false) => format!("Search \"{}\"", text),
+        (Criterion::Tags(tags), true) => format!("Tag: {}", tags.join(" eller ")),
+        (Criterion::Tags(tags), false) => format!("Tagged {}", tags.join(" or ")),
+        (Criterion::MaxPrep(m), true) => format!("Forberedelse højst {} min", m),
+        (Criterion::MaxPrep(m), false) => format!("Prep at most {} min", m),
+        (Criterion::MaxTotal(m), true) => format!("Samlet tid højst {} min", m),
+        (Criterion::MaxTotal(m), false) => format!("Total time at most {} min", m),
+        (Criterion::Servings(Some(a), Some(b)), true) => format!("{}–{} portioner", a, b),
+        (Criterion::Servings(Some(a), Some(b)), false) => format!("{}–{} servings", a, b),
+        (Criterion::Servings(Some(a), None), true) => format!("Mindst {} portioner", a),
+        (Criterion::Servings(Some(a), None), false) => format!("At least {} servings", a),
+        (Criterion::Servings(None, Some(b)), true) => format!("Højst {} portioner", b),
+        (Criterion::Servings(None, Some(b)), false) => format!("At most {} servings", b),
+        (Criterion::Servings(None, None), _) => String::new(),
+        (Criterion::Cookable, true) => "Kan laves nu".to_string(),
+        (Criterion::Cookable, false) => "Cookable now".to_string(),
+        (Criterion::MaxMissing(n), true) => format!("Højst {} mangler", n),
+        (Criterion::MaxMissing(n), false) => format!("At most {} missing", n),
+    };
+    if danish {
+        format!("{}: {} opskrift(er)", label, count)
+    } else {
+        format!("{}: {} recipe(s)", label, count)
+    }
+}
+
 pub fn fmt_tags(tags: &str) -> String {
     match active() {
         Language::Danish => format!("Tags: {}", tags),
@@ -418,6 +976,62 @@ pub fn fmt_last_updated(date: &str) -> String {
     }
 }
 
+/// Expiry badge in the pantry list: days left until the best-before date.
+pub fn fmt_days_left(days: i64) -> String {
+    match active() {
+        Language::Danish => match days {
+            d if d < 0 => "Udløbet".to_string(),
+            0 => "I dag".to_string(),
+            1 => "I morgen".to_string(),
+            n => format!("{} dage", n),
+        },
+        _ => match days {
+            d if d < 0 => "Expired".to_string(),
+            0 => "Today".to_string(),
+            1 => "Tomorrow".to_string(),
+            n => format!("{} days", n),
+        },
+    }
+}
+
+pub fn fmt_best_before(date: &str, days: i64) -> String {
+    match active() {
+        Language::Danish => format!("Bedst før: {} ({})", date, fmt_days_left(days).to_lowercase()),
+        _ => format!("Best before: {} ({})", date, fmt_days_left(days).to_lowercase()),
+    }
+}
+
+/// Caption of a "Running low" row; `have` is `None` when none is left.
+pub fn fmt_low_stock(have: Option<&str>, min: &str) -> String {
+    match (active(), have) {
+        (Language::Danish, Some(have)) => format!("{} af {}", have, min),
+        (Language::Danish, None) => format!("Udsolgt · hav {}", min),
+        (_, Some(have)) => format!("{} of {}", have, min),
+        (_, None) => format!("Out of stock · keep {}", min),
+    }
+}
+
+pub fn fmt_stored_in(location: &str) -> String {
+    match active() {
+        Language::Danish => format!("Opbevares: {}", location),
+        _ => format!("Stored in: {}", location),
+    }
+}
+
+pub fn fmt_min_quantity(min: &str) -> String {
+    match active() {
+        Language::Danish => format!("Hav mindst: {}", min),
+        _ => format!("Keep at least: {}", min),
+    }
+}
+
+pub fn fmt_restocked(n: usize) -> String {
+    match active() {
+        Language::Danish => format!("{} vare(r) tilføjet til indkøbslisten", n),
+        _ => format!("Added {} item(s) to the shopping list", n),
+    }
+}
+
 pub fn fmt_delete_recipe_title(title: &str) -> String {
     match active() {
         Language::Danish => format!("Slet \"{}\"?", title),
@@ -439,6 +1053,365 @@ pub fn fmt_category_hint(example: &str) -> String {
     }
 }
 
+pub fn fmt_deleted(name: &str) -> String {
+    match active() {
+        Language::Danish => format!("Slettede \"{}\"", name),
+        _ => format!("Deleted \"{}\"", name),
+    }
+}
+
+pub fn fmt_saved(name: &str) -> String {
+    match active() {
+        Language::Danish => format!("Gemte \"{}\"", name),
+        _ => format!("Saved \"{}\"", name),
+    }
+}
+
+pub fn fmt_save_failed(name: &str) -> String {
+    match active() {
+        Language::Danish => format!("Kunne ikke gemme \"{}\"", name),
+        _ => format!("Could not save \"{}\"", name),
+    }
+}
+
+pub fn fmt_delete_failed(name: &str) -> String {
+    match active() {
+        Language::Danish => format!("Kunne ikke slette \"{}\"", name),
+        _ => format!("Could not delete \"{}\"", name),
+    }
+}
+
+pub fn fmt_load_failed(dir: &str) -> String {
+    match active() {
+        Language::Danish => format!("Kunne ikke indlæse data fra {}", dir),
+        _ => format!("Could not load data from {}", dir),
+    }
+}
+
+pub fn fmt_not_overwritten(file: &str) -> String {
+    match active() {
+        Language::Danish => format!("{} kunne ikke læses og blev ikke overskrevet", file),
+        _ => format!("{} could not be read and was not overwritten", file),
+    }
+}
+
+pub fn fmt_undone(label: &str) -> String {
+    match active() {
+        Language::Danish => format!("Fortrudt: {}", label),
+        _ => format!("Undone: {}", label),
+    }
+}
+
+pub fn fmt_redone(label: &str) -> String {
+    match active() {
+        Language::Danish => format!("Gentaget: {}", label),
+        _ => format!("Redone: {}", label),
+    }
+}
+
+pub fn fmt_undo_tooltip(label: Option<&str>) -> String {
+    let s = strings();
+    match label {
+        Some(l) => format!("{} ({}) — Ctrl+Z", s.undo, l),
+        None => format!("{} — Ctrl+Z", s.undo),
+    }
+}
+
+pub fn fmt_redo_tooltip(label: Option<&str>) -> String {
+    let s = strings();
+    match label {
+        Some(l) => format!("{} ({}) — Ctrl+Shift+Z", s.redo, l),
+        None => format!("{} — Ctrl+Shift+Z", s.redo),
+    }
+}
+
+/// Toast text after the data directory was reloaded because another device
+/// (or a sync client) changed files in it.
+pub fn fmt_external_change(summary: &crate::watcher::ChangeSummary) -> String {
+    let mut parts = Vec::new();
+    match active() {
+        Language::Danish => {
+            if summary.pantry {
+                parts.push("spisekammer".to_string());
+            }
+            if summary.ingredients > 0 {
+                parts.push(format!("{} ingrediens(er)", summary.ingredients));
+            }
+            if summary.recipes > 0 {
+                parts.push(format!("{} opskrift(er)", summary.recipes));
+            }
+            if summary.shopping {
+                parts.push("indkøbsliste".to_string());
+            }
+            if summary.cooking_log {
+                parts.push("madlog".to_string());
+            }
+            if summary.meal_plan {
+                parts.push("madplan".to_string());
+            }
+            if summary.pantry_details {
+                parts.push("spisekammerdetaljer".to_string());
+            }
+            format!("Genindlæst — ændret udefra: {}", parts.join(", "))
+        }
+        _ => {
+            if summary.pantry {
+                parts.push("pantry".to_string());
+            }
+            if summary.ingredients > 0 {
+                parts.push(format!("{} ingredient(s)", summary.ingredients));
+            }
+            if summary.recipes > 0 {
+                parts.push(format!("{} recipe(s)", summary.recipes));
+            }
+            if summary.shopping {
+                parts.push("shopping list".to_string());
+            }
+            if summary.cooking_log {
+                parts.push("cooking log".to_string());
+            }
+            if summary.meal_plan {
+                parts.push("meal plan".to_string());
+            }
+            if summary.pantry_details {
+                parts.push("pantry details".to_string());
+            }
+            format!("Reloaded — changed elsewhere: {}", parts.join(", "))
+        }
+    }
+}
+
+pub fn fmt_moved_to_pantry(n: usize) -> String {
+    match active() {
+        Language::Danish => format!("{} vare(r) flyttet til spisekammeret", n),
+        _ => format!("Moved {} item(s) to the pantry", n),
+    }
+}
+
+pub fn fmt_imported_recipes(n: usize) -> String {
+    match active() {
+        Language::Danish => format!("{} opskrift(er) importeret", n),
+        _ => format!("Imported {} recipe(s)", n),
+    }
+}
+
+pub fn fmt_exported(file: &str) -> String {
+    match active() {
+        Language::Danish => format!("Eksporteret til \"{}\"", file),
+        _ => format!("Exported to \"{}\"", file),
+    }
+}
+
+/// Title page of a PDF cookbook, of the whole library or of one tag.
+pub fn fmt_cookbook_title(tag: Option<&str>) -> String {
+    match (active(), tag) {
+        (Language::Danish, Some(tag)) => format!("Kogebog: {}", tag),
+        (Language::Danish, None) => "Kogebog".to_string(),
+        (_, Some(tag)) => format!("Cookbook: {}", tag),
+        (_, None) => "Cookbook".to_string(),
+    }
+}
+
+/// Title of the prompt to create ingredients an import didn't recognise.
+pub fn fmt_unmatched_title(names: &[String]) -> String {
+    match active() {
+        Language::Danish => format!("Nye ingredienser: {}", names.join(", ")),
+        _ => format!("New ingredients: {}", names.join(", ")),
+    }
+}
+
+pub fn fmt_created_ingredients(n: usize) -> String {
+    match active() {
+        Language::Danish => format!("{} ingrediens(er) oprettet", n),
+        _ => format!("Created {} ingredient(s)", n),
+    }
+}
+
+pub fn fmt_pantry_import_summary(
+    new: usize,
+    new_ingredients: usize,
+    conflicts: usize,
+    invalid: usize,
+) -> String {
+    match active() {
+        Language::Danish => format!(
+            "{} nye varer, {} nye ingredienser, {} konflikter, {} ugyldige rækker",
+            new, new_ingredients, conflicts, invalid
+        ),
+        _ => format!(
+            "{} new items, {} new ingredients, {} conflicts, {} invalid rows",
+            new, new_ingredients, conflicts, invalid
+        ),
+    }
+}
+
+/// Status of an import row that clashes with what is in the pantry (`have`).
+pub fn fmt_pantry_conflict(have: &str) -> String {
+    match (active(), have.is_empty()) {
+        (Language::Danish, true) => "Allerede i spisekammeret".to_string(),
+        (Language::Danish, false) => format!("I spisekammeret: {}", have),
+        (_, true) => "Already in the pantry".to_string(),
+        (_, false) => format!("In pantry: {}", have),
+    }
+}
+
+pub fn fmt_invalid_row(line: usize, reason: &str) -> String {
+    match active() {
+        Language::Danish => format!("Række {}: {}", line, reason),
+        _ => format!("Row {}: {}", line, reason),
+    }
+}
+
+pub fn fmt_invalid_quantity(quantity: &str) -> String {
+    match active() {
+        Language::Danish => format!("ugyldig mængde \"{}\"", quantity),
+        _ => format!("invalid quantity \"{}\"", quantity),
+    }
+}
+
+/// Date an import row carries, which the imported item doesn't keep.
+pub fn fmt_file_date(date: &str) -> String {
+    match active() {
+        Language::Danish => format!("Dato i filen: {} (ikke importeret)", date),
+        _ => format!("Dated {} in the file (not imported)", date),
+    }
+}
+
+/// Category of an import row that a known ingredient ignores, keeping `kept`.
+pub fn fmt_category_kept(category: &str, kept: &str) -> String {
+    match (active(), kept.is_empty()) {
+        (Language::Danish, true) => format!("Kategorien \"{}\" ignoreres", category),
+        (Language::Danish, false) => {
+            format!("Kategorien \"{}\" ignoreres; ingrediensen beholder \"{}\"", category, kept)
+        }
+        (_, true) => format!("Category \"{}\" is ignored", category),
+        (_, false) => {
+            format!("Category \"{}\" is ignored; the ingredient keeps \"{}\"", category, kept)
+        }
+    }
+}
+
+pub fn fmt_imported_pantry(n: usize) -> String {
+    match active() {
+        Language::Danish => format!("{} vare(r) importeret til spisekammeret", n),
+        _ => format!("Imported {} pantry item(s)", n),
+    }
+}
+
+pub fn fmt_cooked(title: &str) -> String {
+    match active() {
+        Language::Danish => format!("Lavede \"{}\"", title),
+        _ => format!("Cooked \"{}\"", title),
+    }
+}
+
+/// Label of the pantry deductions for cooking `title`. Undoing them leaves
+/// the cooking log alone, which the label says.
+pub fn fmt_used_for(title: &str) -> String {
+    match active() {
+        Language::Danish => {
+            format!("Brugte ingredienserne til \"{}\" (står stadig i madloggen)", title)
+        }
+        _ => format!("Used the ingredients for \"{}\" (still in the cooking log)", title),
+    }
+}
+
+/// "Cooked 3 days ago · 5×"; `days` is counted from today.
+pub fn fmt_last_cooked(days: i64, count: usize) -> String {
+    match active() {
+        Language::Danish => {
+            let when = match days {
+                d if d <= 0 => "Lavet i dag".to_string(),
+                1 => "Lavet i går".to_string(),
+                d => format!("Lavet for {} dage siden", d),
+            };
+            format!("{} · {}×", when, count)
+        }
+        _ => {
+            let when = match days {
+                d if d <= 0 => "Cooked today".to_string(),
+                1 => "Cooked yesterday".to_string(),
+                d => format!("Cooked {} days ago", d),
+            };
+            format!("{} · {}×", when, count)
+        }
+    }
+}
+
+pub fn meal_label(meal: crate::meal_plan::Meal) -> &'static str {
+    use crate::meal_plan::Meal;
+    let s = strings();
+    match meal {
+        Meal::Breakfast => s.meal_breakfast,
+        Meal::Lunch => s.meal_lunch,
+        Meal::Dinner => s.meal_dinner,
+    }
+}
+
+/// Short weekday and day/month, e.g. "Mon 21/7".
+pub fn fmt_day(date: chrono::NaiveDate) -> String {
+    use chrono::Datelike;
+    const EN_DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    const DA_DAYS: [&str; 7] = ["Man", "Tir", "Ons", "Tor", "Fre", "Lør", "Søn"];
+    let idx = date.weekday().num_days_from_monday() as usize;
+    let name = match active() {
+        Language::Danish => DA_DAYS[idx],
+        _ => EN_DAYS[idx],
+    };
+    format!("{} {}/{}", name, date.day(), date.month())
+}
+
+/// "Week 30 · 21/7 – 27/7" for the week starting at `monday`.
+pub fn fmt_week(monday: chrono::NaiveDate) -> String {
+    use chrono::Datelike;
+    let sunday = monday + chrono::Days::new(6);
+    let range = format!("{}/{} – {}/{}", monday.day(), monday.month(), sunday.day(), sunday.month());
+    match active() {
+        Language::Danish => format!("Uge {} · {}", monday.iso_week().week(), range),
+        _ => format!("Week {} · {}", monday.iso_week().week(), range),
+    }
+}
+
+/// "Step 2 of 6".
+pub fn fmt_step(n: usize, total: usize) -> String {
+    match active() {
+        Language::Danish => format!("Trin {} af {}", n, total),
+        _ => format!("Step {} of {}", n, total),
+    }
+}
+
+/// Notification body when a cook mode timer runs out.
+pub fn fmt_timer_done(recipe: &str, timer: &str) -> String {
+    match active() {
+        Language::Danish => format!("{}: uret på {} er gået", recipe, timer),
+        _ => format!("{}: the {} timer is done", recipe, timer),
+    }
+}
+
+pub fn fmt_quantity_error(err: &crate::quantity::QuantityError) -> String {
+    use crate::quantity::QuantityError;
+    match (active(), err) {
+        (Language::Danish, QuantityError::InvalidNumber(t)) => {
+            format!("Ugyldigt tal: \"{}\" (f.eks. 2, 1,5, 1/2, 2-3)", t)
+        }
+        (Language::Danish, QuantityError::UnknownUnit(u)) => format!("Ukendt enhed: \"{}\"", u),
+        (_, QuantityError::InvalidNumber(t)) => {
+            format!("Invalid number: \"{}\" (e.g. 2, 1.5, 1/2, 2-3)", t)
+        }
+        (_, QuantityError::UnknownUnit(u)) => format!("Unknown unit: \"{}\"", u),
+    }
+}
+
+// ── Storage locations ─────────────────────────────────────────────────────────
+
+/// Storage locations offered until the user sets up their own.
+pub fn default_storage_locations() -> &'static [&'static str] {
+    match active() {
+        Language::Danish => &["Køleskab", "Fryser", "Skab"],
+        _ => &["Fridge", "Freezer", "Cupboard"],
+    }
+}
+
 // ── Unit suggestions ──────────────────────────────────────────────────────────
 
 /// Localised common unit names for the ingredient editor.
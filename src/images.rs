@@ -0,0 +1,173 @@
+/// Recipe images: importing them into `recipes/img` and cached thumbnails.
+///
+/// Images are stored next to the recipes in the data directory, named after the
+/// recipe slug, and `Recipe::image` holds the file name. Downscaled copies for
+/// the recipe list and the detail view are kept in the user's cache directory
+/// (outside the synced data directory) and generated on a background thread.
+use crate::markdown::resolve_image;
+use janus_engine::Recipe;
+use relm4::gtk::gdk_pixbuf::Pixbuf;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Image folder inside the data directory.
+pub const IMAGE_DIR: &str = "recipes/img";
+
+/// Edge length of recipe list thumbnails (shown at half size, sharp on HiDPI).
+pub const THUMBNAIL_SIZE: i32 = 64;
+
+/// Edge length of the image at the top of the recipe detail.
+pub const HERO_SIZE: i32 = 960;
+
+/// Thumbnails being generated, so repeated list rebuilds don't queue them twice.
+static IN_FLIGHT: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Thumbnails that could not be made. Their names cover the source's
+/// modification time, so an image is only tried again once it changes.
+static FAILED: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Where cached thumbnails are kept.
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("pantryman/thumbnails")
+}
+
+/// The image file of `recipe`, if it has one and the file exists.
+pub fn recipe_image_path(data_dir: &Path, recipe: &Recipe) -> Option<PathBuf> {
+    let image = recipe.image.as_deref()?;
+    resolve_image(&data_dir.join("recipes"), image)
+}
+
+/// Copy `source` into the image folder as `<slug>.<ext>` and return the file
+/// name to store in the recipe. Older images of the same recipe with another
+/// extension are removed; the undo history keeps their contents (see
+/// [`read_image`]).
+pub fn import_image(data_dir: &Path, source: &Path, slug: &str) -> Result<String, String> {
+    let ext = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .filter(|e| !e.is_empty())
+        .unwrap_or_else(|| "jpg".to_string());
+    let dir = data_dir.join(IMAGE_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+
+    let name = format!("{}.{}", slug, ext);
+    let dest = dir.join(&name);
+    if source != dest {
+        fs::copy(source, &dest).map_err(|e| format!("{}: {}", source.display(), e))?;
+    }
+
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let same_recipe = path.file_stem().and_then(|s| s.to_str()) == Some(slug);
+            if same_recipe && path != dest {
+                let _ = fs::remove_file(&path);
+            }
+        }
+    }
+    Ok(name)
+}
+
+/// The image file of `recipe` and its contents, for the undo history to put
+/// back with [`restore_image`] after a newer image replaced it.
+pub fn read_image(data_dir: &Path, recipe: &Recipe) -> Option<(PathBuf, Vec<u8>)> {
+    let path = recipe_image_path(data_dir, recipe)?;
+    let bytes = fs::read(&path).ok()?;
+    Some((path, bytes))
+}
+
+/// Write an image read by [`read_image`] back, unless the file still holds it.
+pub fn restore_image(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if fs::read(path).is_ok_and(|current| current == bytes) {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    crate::utils::write_atomically(path, bytes)
+}
+
+/// Cache file for `source` scaled to fit `size`. The name covers the source's
+/// path, size and modification time, so a replaced image gets a new thumbnail.
+pub fn thumbnail_path(cache_dir: &Path, source: &Path, size: i32) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    if let Ok(meta) = fs::metadata(source) {
+        meta.len().hash(&mut hasher);
+        if let Ok(modified) = meta.modified() {
+            modified.hash(&mut hasher);
+        }
+    }
+    cache_dir.join(format!("{:016x}-{}.png", hasher.finish(), size))
+}
+
+/// The cached thumbnail of `source` at `size`, if it has been generated.
+pub fn cached_thumbnail(source: &Path, size: i32) -> Option<PathBuf> {
+    Some(thumbnail_path(&cache_dir(), source, size)).filter(|p| p.is_file())
+}
+
+/// Generate thumbnails for `(source, size)` pairs on a background thread.
+/// `done` runs on that thread once at least one was written. Thumbnails that
+/// failed before are skipped until their source changes.
+pub fn generate_thumbnails(jobs: Vec<(PathBuf, i32)>, done: impl FnOnce() + Send + 'static) {
+    let cache = cache_dir();
+    let jobs: Vec<(PathBuf, PathBuf, i32)> = {
+        let failed = FAILED.lock().unwrap_or_else(|e| e.into_inner());
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+        jobs.into_iter()
+            .filter_map(|(source, size)| {
+                let dest = thumbnail_path(&cache, &source, size);
+                if failed.contains(&dest) {
+                    return None;
+                }
+                in_flight.insert(dest.clone()).then_some((source, dest, size))
+            })
+            .collect()
+    };
+    if jobs.is_empty() {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let (mut wrote, mut failed) = (false, Vec::new());
+        for (source, dest, size) in &jobs {
+            match write_thumbnail(source, dest, *size) {
+                Ok(()) => wrote = true,
+                Err(e) => {
+                    log::warn!("Could not make a thumbnail of {}: {}", source.display(), e);
+                    failed.push(dest.clone());
+                }
+            }
+        }
+        FAILED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(failed);
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+        for (_, dest, _) in &jobs {
+            in_flight.remove(dest);
+        }
+        drop(in_flight);
+        if wrote {
+            done();
+        }
+    });
+}
+
+fn write_thumbnail(source: &Path, dest: &Path, size: i32) -> Result<(), String> {
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    let pixbuf = Pixbuf::from_file_at_scale(source, size, size, true).map_err(|e| e.to_string())?;
+    // Write under a temporary name so a half-written file is never shown
+    let tmp = dest.with_extension("png.part");
+    pixbuf.savev(&tmp, "png", &[]).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, dest).map_err(|e| format!("{}: {}", dest.display(), e))
+}
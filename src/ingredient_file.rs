@@ -0,0 +1,124 @@
+/// Keeps ingredient file keys that the engine doesn't know about.
+///
+/// Low-stock thresholds (`low_stock`), conversion data (`units`) and keys added
+/// by other frontends live in the ingredient's YAML file next to the engine's
+/// own keys. The engine rewrites the whole file from its `Ingredient` on save
+/// and drops them, so every engine write of an ingredient goes through
+/// [`keep_extra_keys`], which reads them from the old file and writes them back
+/// into the new one, and updates the thresholds and conversion data kept in
+/// memory to match.
+use crate::toasts::error_chain;
+use crate::units::IngredientUnits;
+use janus_engine::{slugify, DataManager, Ingredient};
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+/// Keys the engine writes itself.
+pub const ENGINE_KEYS: [&str; 6] = ["name", "slug", "category", "tags", "translations", "plural"];
+
+fn is_engine_key(key: &Value) -> bool {
+    key.as_str().is_some_and(|k| ENGINE_KEYS.contains(&k))
+}
+
+/// Path of the YAML file the ingredient `name` is stored in.
+pub fn ingredient_path(data_dir: &Path, dm: &DataManager, name: &str) -> PathBuf {
+    match dm.get_ingredient(name) {
+        Some(ingredient) => file_of(data_dir, ingredient),
+        None => data_dir
+            .join("ingredients")
+            .join(format!("{}.yaml", slugify(name))),
+    }
+}
+
+/// Path of the YAML file `ingredient` is stored in.
+pub fn file_of(data_dir: &Path, ingredient: &Ingredient) -> PathBuf {
+    let stem = match ingredient.file_stem.as_str() {
+        "" => slugify(&ingredient.name),
+        stem => stem.to_string(),
+    };
+    data_dir.join("ingredients").join(format!("{}.yaml", stem))
+}
+
+/// Entries of an ingredient file that aren't [`ENGINE_KEYS`].
+pub fn extra_keys(text: &str) -> Mapping {
+    match serde_yaml::from_str(text) {
+        Ok(Value::Mapping(map)) => map.into_iter().filter(|(k, _)| !is_engine_key(k)).collect(),
+        _ => Mapping::new(),
+    }
+}
+
+/// `text` (an ingredient file) with `extra` as its entries besides the engine's.
+/// Entries that stay keep their place; new ones are added at the end.
+pub fn with_extra_keys(text: &str, extra: &Mapping) -> Result<String, String> {
+    let map: Mapping = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+    let mut map: Mapping = map
+        .into_iter()
+        .filter(|(k, _)| is_engine_key(k) || extra.contains_key(k))
+        .collect();
+    for (k, v) in extra {
+        map.insert(k.clone(), v.clone());
+    }
+    serde_yaml::to_string(&map).map_err(|e| e.to_string())
+}
+
+/// Extra entries of the ingredient file at `path`; none if it can't be read.
+pub fn read_extra_keys(path: &Path) -> Mapping {
+    std::fs::read_to_string(path)
+        .map(|text| extra_keys(&text))
+        .unwrap_or_default()
+}
+
+/// Make `extra` the entries besides the engine's of the ingredient file at `path`.
+pub fn write_extra_keys(path: &Path, extra: &Mapping) -> Result<(), String> {
+    if extra.is_empty() && !path.exists() {
+        return Ok(());
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let updated =
+        with_extra_keys(&text, extra).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if updated != text {
+        crate::utils::write_atomically(path, &updated)?;
+    }
+    Ok(())
+}
+
+/// Run `write`, an engine write that saves the ingredient `name` as `new_name`,
+/// and put the extra entries of its file back afterwards. Engine errors are
+/// returned with their cause chain.
+pub fn keep_extra_keys<T, E: std::error::Error>(
+    dm: &mut DataManager,
+    data_dir: &Path,
+    name: &str,
+    new_name: &str,
+    write: impl FnOnce(&mut DataManager) -> Result<T, E>,
+) -> Result<T, String> {
+    let extra = read_extra_keys(&ingredient_path(data_dir, dm, name));
+    write_with_extra_keys(dm, data_dir, name, new_name, &extra, write)
+}
+
+/// Like [`keep_extra_keys`], but make `extra` the extra entries of the saved file.
+pub fn write_with_extra_keys<T, E: std::error::Error>(
+    dm: &mut DataManager,
+    data_dir: &Path,
+    name: &str,
+    new_name: &str,
+    extra: &Mapping,
+    write: impl FnOnce(&mut DataManager) -> Result<T, E>,
+) -> Result<T, String> {
+    let result = write(dm).map_err(|e| error_chain(&e))?;
+    write_extra_keys(&ingredient_path(data_dir, dm, new_name), extra)?;
+    remember_extra_keys(name, Some((new_name, extra)));
+    Ok(result)
+}
+
+/// Update the thresholds and conversion data kept in memory for the ingredient
+/// `name`, now saved under the given name with the given extra entries, or
+/// deleted (`None`).
+pub fn remember_extra_keys(name: &str, saved: Option<(&str, &Mapping)>) {
+    crate::low_stock::set_threshold(name, None);
+    crate::units::set_units_of(name, IngredientUnits::default());
+    if let Some((new_name, extra)) = saved {
+        crate::low_stock::set_threshold(new_name, crate::low_stock::threshold_in(extra));
+        crate::units::set_units_of(new_name, crate::units::units_in(extra));
+    }
+}
@@ -0,0 +1,162 @@
+/// Recipe instructions split into steps, with the timers and ingredients each
+/// step mentions. Used by cook mode.
+use crate::quantity::parse_amount;
+use janus_engine::fold_for_matching;
+
+/// A countdown suggested by a step, e.g. "Bake for 60 minutes".
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerSpec {
+    /// The matched text ("60 minutes", "10-15 min").
+    pub label: String,
+    pub seconds: u64,
+}
+
+/// Split Markdown instructions into steps.
+///
+/// Numbered ("1." / "1)") or bulleted lines start a new step and following
+/// lines continue it; without any list markers each paragraph is a step, and a
+/// single paragraph is split into sentences. Headings are dropped.
+pub fn split_steps(instructions: &str) -> Vec<String> {
+    let lines: Vec<&str> = instructions.lines().map(str::trim).collect();
+    let has_list = lines.iter().any(|l| list_item(l).is_some());
+
+    let mut steps: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let flush = |current: &mut String, steps: &mut Vec<String>| {
+        let step = current.trim().to_string();
+        if !step.is_empty() {
+            steps.push(step);
+        }
+        current.clear();
+    };
+
+    for line in lines {
+        if line.starts_with('#') {
+            flush(&mut current, &mut steps);
+            continue;
+        }
+        if has_list {
+            if let Some(rest) = list_item(line) {
+                flush(&mut current, &mut steps);
+                current.push_str(rest);
+                continue;
+            }
+        } else if line.is_empty() {
+            flush(&mut current, &mut steps);
+            continue;
+        }
+        if !line.is_empty() {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(line);
+        }
+    }
+    flush(&mut current, &mut steps);
+
+    if !has_list && steps.len() == 1 {
+        return split_sentences(&steps[0]);
+    }
+    steps
+}
+
+/// Text of a numbered or bulleted list line, without the marker.
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(rest.trim_start());
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let rest = &line[digits..];
+    rest.strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))
+        .map(str::trim_start)
+}
+
+/// Split a paragraph after each '.', '!' or '?' that is followed by a capital letter.
+fn split_sentences(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for i in 0..chars.len() {
+        let ends = matches!(chars[i], '.' | '!' | '?')
+            && chars.get(i + 1) == Some(&' ')
+            && chars.get(i + 2).is_some_and(|c| c.is_uppercase());
+        if ends {
+            sentences.push(chars[start..=i].iter().collect::<String>().trim().to_string());
+            start = i + 2;
+        }
+    }
+    let last: String = chars[start..].iter().collect();
+    if !last.trim().is_empty() {
+        sentences.push(last.trim().to_string());
+    }
+    sentences
+}
+
+/// Seconds per unit for the time words timers are detected from (English and Danish).
+fn seconds_per(word: &str) -> Option<u64> {
+    match word {
+        "second" | "seconds" | "sec" | "secs" | "sekund" | "sekunder" => Some(1),
+        "minute" | "minutes" | "min" | "mins" | "minut" | "minutter" => Some(60),
+        "hour" | "hours" | "hr" | "hrs" | "time" | "timer" => Some(3600),
+        _ => None,
+    }
+}
+
+/// Timers mentioned in a step: a number or range followed by a time unit
+/// ("60 minutes", "10-15 min", "1½ timer"). Ranges use the upper bound.
+pub fn detect_timers(step: &str) -> Vec<TimerSpec> {
+    let words: Vec<&str> = step
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && !"½¼¾⅓⅔/.,-–".contains(c)))
+        .collect();
+    let mut timers = Vec::new();
+    for pair in words.windows(2) {
+        let unit = pair[1].trim_matches(|c: char| !c.is_alphanumeric());
+        let Some(per) = seconds_per(&unit.to_lowercase()) else {
+            continue;
+        };
+        let Some(amount) = parse_amount(pair[0]) else {
+            continue;
+        };
+        let seconds = (amount.max() * per as f64).round() as u64;
+        if seconds > 0 {
+            timers.push(TimerSpec {
+                label: format!("{} {}", pair[0], unit),
+                seconds,
+            });
+        }
+    }
+    timers
+}
+
+/// Indices of the `ingredients` a step mentions (case- and accent-insensitive,
+/// matching at the start of a word so "potato" also finds "potatoes").
+pub fn mentioned_ingredients(step: &str, ingredients: &[String]) -> Vec<usize> {
+    let text = fold_for_matching(step);
+    ingredients
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| {
+            let name = fold_for_matching(name);
+            !name.is_empty()
+                && text.match_indices(&name).any(|(i, _)| {
+                    !matches!(text[..i].chars().next_back(), Some(c) if c.is_alphanumeric())
+                })
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Remaining time on a countdown: "4:05", or "1:05:00" from an hour up.
+pub fn format_countdown(seconds: u64) -> String {
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
@@ -1,9 +1,36 @@
 pub mod app;
 pub mod config;
+pub mod cook_mode;
+pub mod cooking;
+pub mod cooklang;
+pub mod coverage;
 pub mod dialogs;
+pub mod frontmatter;
+pub mod history;
 pub mod i18n;
+pub mod images;
+pub mod ingredient_file;
+pub mod instructions;
+pub mod low_stock;
+pub mod markdown;
+pub mod meal_plan;
 pub mod pantry;
+pub mod pantry_details;
+pub mod pantry_filter;
+pub mod pantry_io;
+pub mod planner;
+pub mod print;
+pub mod quantity;
+pub mod recipe_filter;
+pub mod recipe_form;
+pub mod recipe_import;
 pub mod recipes;
 pub mod settings;
+pub mod shopping;
+pub mod shopping_list;
+pub mod tags;
+pub mod toasts;
 pub mod ui_constants;
+pub mod units;
 pub mod utils;
+pub mod watcher;
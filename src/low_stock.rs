@@ -0,0 +1,219 @@
+/// Low-stock thresholds: the least of an ingredient to keep in the pantry.
+///
+/// The threshold lives in the ingredient's YAML file, so other frontends can
+/// read it too:
+///
+/// ```yaml
+/// name: milk
+/// min_quantity: 2
+/// min_quantity_type: l
+/// ```
+///
+/// The engine doesn't know these keys; `ingredient_file` carries them over when
+/// the engine saves the ingredient. Like the unit data in `units`, the
+/// thresholds are read once per data directory load and kept in a global map.
+use crate::ingredient_file::{extra_keys, with_extra_keys};
+use crate::quantity::{format_amount, parse_quantity, round_for_unit, Amount};
+use crate::shopping_list::ShoppingItem;
+use crate::units::{convert_with, ingredient_units};
+use janus_engine::DataManager;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+const QUANTITY_KEY: &str = "min_quantity";
+const UNIT_KEY: &str = "min_quantity_type";
+
+/// Keep at least `quantity` `unit` of an ingredient.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Threshold {
+    #[serde(rename = "min_quantity")]
+    pub quantity: f64,
+    #[serde(rename = "min_quantity_type", default)]
+    pub unit: String,
+}
+
+#[derive(Deserialize)]
+struct IngredientFile {
+    name: String,
+    #[serde(flatten)]
+    threshold: Option<Threshold>,
+}
+
+/// Read the thresholds of every ingredient file in `data_dir`, keyed by
+/// lowercase name. Unreadable files are skipped.
+pub fn load_thresholds(data_dir: &Path) -> BTreeMap<String, Threshold> {
+    let mut map = BTreeMap::new();
+    let Ok(entries) = std::fs::read_dir(data_dir.join("ingredients")) else {
+        return map;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("yaml") {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        match serde_yaml::from_str::<IngredientFile>(&text) {
+            Ok(IngredientFile { name, threshold: Some(threshold) }) => {
+                map.insert(name.to_lowercase(), threshold);
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("Skipping threshold in {}: {}", path.display(), e),
+        }
+    }
+    map
+}
+
+/// Set the threshold keys among an ingredient file's extra entries to
+/// `threshold`, or remove them when it is `None`.
+pub fn set_threshold_keys(extra: &mut Mapping, threshold: Option<&Threshold>) {
+    match threshold {
+        Some(t) => {
+            extra.insert(QUANTITY_KEY.into(), Value::from(t.quantity));
+            extra.insert(UNIT_KEY.into(), Value::from(t.unit.clone()));
+        }
+        None => {
+            *extra = std::mem::take(extra)
+                .into_iter()
+                .filter(|(k, _)| !matches!(k.as_str(), Some(QUANTITY_KEY | UNIT_KEY)))
+                .collect();
+        }
+    }
+}
+
+/// `text` (an ingredient file) with its threshold keys set to `threshold`, or
+/// removed when it is `None`. Other keys are kept in place.
+pub fn with_threshold(text: &str, threshold: Option<&Threshold>) -> Result<String, String> {
+    let mut extra = extra_keys(text);
+    set_threshold_keys(&mut extra, threshold);
+    with_extra_keys(text, &extra)
+}
+
+/// Threshold among an ingredient file's extra entries, if it has one.
+pub fn threshold_in(extra: &Mapping) -> Option<Threshold> {
+    serde_yaml::from_value(Value::Mapping(extra.clone())).ok()
+}
+
+/// Parse a threshold typed in the ingredient dialog ("2 l", "500 g", "3").
+/// `None` for text without a single positive amount or with an unknown unit.
+pub fn parse_threshold(text: &str) -> Option<Threshold> {
+    let parsed = parse_quantity(text).ok()?;
+    match parsed.amount? {
+        Amount::Single(quantity) if quantity > 0.0 => Some(Threshold {
+            quantity,
+            unit: parsed.unit.unwrap_or_default(),
+        }),
+        _ => None,
+    }
+}
+
+/// `threshold` as the ingredient dialog shows it.
+pub fn threshold_text(threshold: &Threshold) -> String {
+    if threshold.unit.is_empty() {
+        format_amount(threshold.quantity)
+    } else {
+        format!("{} {}", format_amount(threshold.quantity), threshold.unit)
+    }
+}
+
+/// An ingredient with a threshold that the pantry has fallen below.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LowItem {
+    pub ingredient: String,
+    /// What the pantry holds, in the threshold's unit; `None` when the
+    /// ingredient is not in the pantry at all.
+    pub have: Option<f64>,
+    pub threshold: Threshold,
+}
+
+/// Pantry amount of `ingredient` in the threshold's unit: `Some(0.0)` when it
+/// isn't in the pantry, `None` when the amount isn't tracked or doesn't convert.
+fn pantry_amount(dm: &DataManager, ingredient: &str, threshold: &Threshold) -> Option<f64> {
+    if !dm.is_in_pantry(ingredient) {
+        return Some(0.0);
+    }
+    let item = dm.get_pantry_item(ingredient)?;
+    convert_with(
+        item.quantity?,
+        &item.quantity_type,
+        &threshold.unit,
+        ingredient_units(ingredient),
+    )
+}
+
+/// Whether the pantry holds less of `ingredient` than `threshold`. Items whose
+/// amount isn't tracked, or is in a unit that doesn't convert, are never low.
+pub fn is_low(dm: &DataManager, ingredient: &str, threshold: &Threshold) -> bool {
+    pantry_amount(dm, ingredient, threshold).is_some_and(|have| have < threshold.quantity)
+}
+
+/// Every ingredient below its threshold, by name.
+pub fn running_low(dm: &DataManager, thresholds: &BTreeMap<String, Threshold>) -> Vec<LowItem> {
+    let mut low: Vec<LowItem> = dm
+        .get_all_ingredients()
+        .into_iter()
+        .filter_map(|ing| {
+            let threshold = thresholds.get(&ing.name.to_lowercase())?;
+            let have = pantry_amount(dm, &ing.name, threshold)?;
+            (have < threshold.quantity).then(|| LowItem {
+                ingredient: ing.name.clone(),
+                have: dm.is_in_pantry(&ing.name).then_some(have),
+                threshold: threshold.clone(),
+            })
+        })
+        .collect();
+    low.sort_by_key(|l| l.ingredient.to_lowercase());
+    low
+}
+
+/// Shopping list line that brings `low` back up to its threshold.
+pub fn restock_item(dm: &DataManager, low: &LowItem) -> ShoppingItem {
+    let unit = &low.threshold.unit;
+    let missing = low.threshold.quantity - low.have.unwrap_or(0.0);
+    ShoppingItem {
+        ingredient: low.ingredient.clone(),
+        quantity: Some(round_for_unit(missing, Some(unit.as_str()))),
+        quantity_type: unit.clone(),
+        category: dm
+            .get_ingredient(&low.ingredient)
+            .map(|i| i.category.clone())
+            .unwrap_or_default(),
+        recipes: Vec::new(),
+        checked: false,
+    }
+}
+
+// ── Global state ──────────────────────────────────────────────────────────────
+
+static THRESHOLDS: RwLock<BTreeMap<String, Threshold>> = RwLock::new(BTreeMap::new());
+
+/// Replace the thresholds (after a data directory load).
+pub fn set_thresholds(thresholds: BTreeMap<String, Threshold>) {
+    if let Ok(mut w) = THRESHOLDS.write() {
+        *w = thresholds;
+    }
+}
+
+/// Replace the threshold of the ingredient `name` (after it was saved).
+pub fn set_threshold(name: &str, threshold: Option<Threshold>) {
+    if let Ok(mut w) = THRESHOLDS.write() {
+        match threshold {
+            Some(t) => w.insert(name.to_lowercase(), t),
+            None => w.remove(&name.to_lowercase()),
+        };
+    }
+}
+
+/// All thresholds, keyed by lowercase ingredient name.
+pub fn thresholds() -> BTreeMap<String, Threshold> {
+    THRESHOLDS.read().map(|m| m.clone()).unwrap_or_default()
+}
+
+/// Threshold of the ingredient `name`, if it has one.
+pub fn threshold(name: &str) -> Option<Threshold> {
+    THRESHOLDS.read().ok()?.get(&name.to_lowercase()).cloned()
+}
@@ -0,0 +1,549 @@
+/// Markdown rendering for recipe instructions.
+///
+/// Covers what recipe files use in practice: ATX headings, ordered and
+/// unordered lists, emphasis, inline and fenced code, links, block quotes,
+/// rules and images (looked up next to the recipe files, e.g. in
+/// `recipes/img`). Parsing is plain string work so it can be tested without
+/// GTK; [`render_markdown`] turns the blocks into selectable labels.
+///
+/// Ingredient names in the text become `ingredient:` links, which the caller
+/// handles (the recipe view jumps to the ingredient in the Pantry tab).
+use crate::i18n;
+use crate::ui_constants::*;
+use relm4::gtk;
+use relm4::gtk::prelude::*;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// URI scheme of the links made from ingredient names.
+pub const INGREDIENT_LINK_SCHEME: &str = "ingredient:";
+
+/// One block-level element.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// `level` 1–6; `text` is inline Markdown.
+    Heading { level: u8, text: String },
+    /// Inline Markdown; hard line breaks are kept as `\n`.
+    Paragraph(String),
+    /// Items are inline Markdown. `start` is the first number of ordered lists.
+    List { ordered: bool, start: u32, items: Vec<String> },
+    Quote(Vec<Block>),
+    /// Fenced code, as written.
+    Code(String),
+    /// An image on a line of its own.
+    Image { alt: String, src: String },
+    Rule,
+}
+
+/// Split Markdown into blocks.
+pub fn parse_blocks(text: &str) -> Vec<Block> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut blocks = Vec::new();
+    let mut para = String::new();
+    let mut list: Option<(bool, u32, Vec<String>)> = None;
+    let mut i = 0;
+
+    fn flush_para(para: &mut String, blocks: &mut Vec<Block>) {
+        let text = para.trim_end().to_string();
+        if !text.is_empty() {
+            blocks.push(Block::Paragraph(text));
+        }
+        para.clear();
+    }
+    fn flush_list(list: &mut Option<(bool, u32, Vec<String>)>, blocks: &mut Vec<Block>) {
+        if let Some((ordered, start, items)) = list.take() {
+            blocks.push(Block::List { ordered, start, items });
+        }
+    }
+
+    while i < lines.len() {
+        let raw = lines[i];
+        let line = raw.trim();
+        i += 1;
+
+        // Fenced code runs to the closing fence
+        if line.starts_with("```") || line.starts_with("~~~") {
+            flush_para(&mut para, &mut blocks);
+            flush_list(&mut list, &mut blocks);
+            let fence = &line[..3];
+            let mut code = Vec::new();
+            while i < lines.len() && !lines[i].trim().starts_with(fence) {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1;
+            blocks.push(Block::Code(code.join("\n")));
+            continue;
+        }
+
+        if line.is_empty() {
+            // A blank line ends a paragraph; lists may continue after one
+            flush_para(&mut para, &mut blocks);
+            continue;
+        }
+
+        if let Some((level, text)) = heading(line) {
+            flush_para(&mut para, &mut blocks);
+            flush_list(&mut list, &mut blocks);
+            blocks.push(Block::Heading { level, text: text.to_string() });
+            continue;
+        }
+
+        if is_rule(line) {
+            flush_para(&mut para, &mut blocks);
+            flush_list(&mut list, &mut blocks);
+            blocks.push(Block::Rule);
+            continue;
+        }
+
+        if line.starts_with('>') {
+            flush_para(&mut para, &mut blocks);
+            flush_list(&mut list, &mut blocks);
+            let mut quoted = vec![strip_quote(line)];
+            while i < lines.len() && lines[i].trim().starts_with('>') {
+                quoted.push(strip_quote(lines[i].trim()));
+                i += 1;
+            }
+            blocks.push(Block::Quote(parse_blocks(&quoted.join("\n"))));
+            continue;
+        }
+
+        if let Some((ordered, number, text)) = list_marker(line) {
+            flush_para(&mut para, &mut blocks);
+            match &mut list {
+                Some((o, _, items)) if *o == ordered => items.push(text.trim_end().to_string()),
+                _ => {
+                    flush_list(&mut list, &mut blocks);
+                    list = Some((ordered, number, vec![text.trim_end().to_string()]));
+                }
+            }
+            continue;
+        }
+
+        // Indented lines, or lines right after an item, continue the last item
+        if let Some((_, _, items)) = &mut list {
+            let follows_item = i >= 2 && !lines[i - 2].trim().is_empty();
+            if raw.starts_with([' ', '\t']) || follows_item {
+                if let Some(last) = items.last_mut() {
+                    last.push(' ');
+                    last.push_str(line);
+                }
+                continue;
+            }
+            flush_list(&mut list, &mut blocks);
+        }
+
+        if para.is_empty() {
+            if let Some((alt, src)) = image(line) {
+                blocks.push(Block::Image { alt, src });
+                continue;
+            }
+        }
+
+        if !para.is_empty() && !para.ends_with('\n') {
+            para.push(' ');
+        }
+        // Two trailing spaces or a backslash force a line break
+        if raw.ends_with("  ") || line.ends_with('\\') {
+            para.push_str(line.trim_end_matches('\\'));
+            para.push('\n');
+        } else {
+            para.push_str(line);
+        }
+    }
+    flush_para(&mut para, &mut blocks);
+    flush_list(&mut list, &mut blocks);
+    blocks
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level as u8, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|&m| compact.chars().all(|c| c == m))
+}
+
+fn strip_quote(line: &str) -> &str {
+    let rest = line.strip_prefix('>').unwrap_or(line);
+    rest.strip_prefix(' ').unwrap_or(rest)
+}
+
+/// `(ordered, number, text)` for "- item", "* item", "+ item", "3. item", "3) item".
+fn list_marker(line: &str) -> Option<(bool, u32, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            return Some((false, 1, rest.trim_start()));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || digits > 9 {
+        return None;
+    }
+    let number = line[..digits].parse().ok()?;
+    let rest = &line[digits..];
+    rest.strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))
+        .map(|text| (true, number, text.trim_start()))
+}
+
+/// `![alt](src)` filling the whole line.
+fn image(line: &str) -> Option<(String, String)> {
+    let rest = line.strip_prefix("![")?;
+    let (alt, rest) = rest.split_once("](")?;
+    let src = rest.strip_suffix(')')?;
+    // Drop an optional title: ![alt](img/a.jpg "Title")
+    let src = src.split_whitespace().next().unwrap_or_default();
+    Some((alt.to_string(), src.to_string()))
+}
+
+/// Escape text for Pango markup.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Pango markup for a piece of inline Markdown.
+///
+/// `ingredients` pairs a name to look for with the ingredient it links to;
+/// matches are case-insensitive whole words, so plural forms need their own
+/// entry and "egg" leaves "eggplant" alone. Names inside links and code are
+/// left alone.
+pub fn inline_markup(text: &str, ingredients: &[(String, String)]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut plain = String::new();
+    // Open emphasis tags, innermost last
+    let mut open: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    let flush = |plain: &mut String, out: &mut String| {
+        out.push_str(&link_ingredients(plain, ingredients));
+        plain.clear();
+    };
+    let closes_later = |from: usize, marker: &[char]| {
+        (from..chars.len().saturating_sub(marker.len() - 1))
+            .any(|j| chars[j..j + marker.len()] == *marker)
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\\' if next.is_some_and(|n| n.is_ascii_punctuation()) => {
+                plain.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                let close = (i + 1..chars.len()).find(|&j| chars[j] == '`');
+                match close {
+                    Some(end) => {
+                        flush(&mut plain, &mut out);
+                        let code: String = chars[i + 1..end].iter().collect();
+                        out.push_str(&format!("<tt>{}</tt>", escape(&code)));
+                        i = end + 1;
+                    }
+                    None => {
+                        plain.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            '*' | '_' if next == Some(c) && (open.contains(&"b") || closes_later(i + 2, &[c, c])) => {
+                flush(&mut plain, &mut out);
+                toggle_tag(&mut open, "b", &mut out);
+                i += 2;
+            }
+            '*' | '_'
+                if (open.contains(&"i") || closes_later(i + 1, &[c]))
+                    && (c == '*'
+                        || open.contains(&"i")
+                        || !i.checked_sub(1).is_some_and(|p| chars[p].is_alphanumeric())) =>
+            {
+                flush(&mut plain, &mut out);
+                toggle_tag(&mut open, "i", &mut out);
+                i += 1;
+            }
+            '!' | '[' => match link_at(&chars, i) {
+                Some((label, href, end)) => {
+                    flush(&mut plain, &mut out);
+                    if c == '!' {
+                        // Inline images show their description
+                        out.push_str(&format!("<i>{}</i>", escape(&label)));
+                    } else {
+                        out.push_str(&format!(
+                            "<a href=\"{}\">{}</a>",
+                            escape(&href),
+                            inline_markup(&label, &[])
+                        ));
+                    }
+                    i = end;
+                }
+                None => {
+                    plain.push(c);
+                    i += 1;
+                }
+            },
+            _ => {
+                plain.push(c);
+                i += 1;
+            }
+        }
+    }
+    flush(&mut plain, &mut out);
+    for tag in open.iter().rev() {
+        out.push_str(&format!("</{}>", tag));
+    }
+    out
+}
+
+/// Open `tag`, or close it if it is open. Tags opened inside it are closed
+/// first and reopened after, so the markup stays well nested.
+fn toggle_tag<'a>(open: &mut Vec<&'a str>, tag: &'a str, out: &mut String) {
+    let Some(pos) = open.iter().position(|t| *t == tag) else {
+        out.push_str(&format!("<{}>", tag));
+        open.push(tag);
+        return;
+    };
+    let inner = open.split_off(pos + 1);
+    for t in inner.iter().rev() {
+        out.push_str(&format!("</{}>", t));
+    }
+    out.push_str(&format!("</{}>", tag));
+    open.pop();
+    for t in inner {
+        out.push_str(&format!("<{}>", t));
+        open.push(t);
+    }
+}
+
+/// `[label](href)` (or `![alt](src)`) starting at `start`: label, href and the
+/// index just past it.
+fn link_at(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    let open = if chars[start] == '!' { start + 1 } else { start };
+    if chars.get(open) != Some(&'[') {
+        return None;
+    }
+    let close = (open + 1..chars.len()).find(|&j| chars[j] == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = (close + 2..chars.len()).find(|&j| chars[j] == ')')?;
+    let label: String = chars[open + 1..close].iter().collect();
+    let href: String = chars[close + 2..end].iter().collect();
+    let href = href.split_whitespace().next().unwrap_or_default().to_string();
+    Some((label, href, end + 1))
+}
+
+/// Escape `text` and wrap ingredient names in `ingredient:` links.
+fn link_ingredients(text: &str, ingredients: &[(String, String)]) -> String {
+    let s = i18n::strings();
+    let chars: Vec<char> = text.chars().collect();
+    let mut names: Vec<(Vec<char>, &str)> = ingredients
+        .iter()
+        .filter(|(name, _)| !name.trim().is_empty())
+        .map(|(name, target)| (name.chars().collect(), target.as_str()))
+        .collect();
+    // Longest first, so "olive oil" wins over "oil"
+    names.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let word_start = i == 0 || !chars[i - 1].is_alphanumeric();
+        let hit = word_start
+            .then(|| {
+                names.iter().find(|(name, _)| {
+                    chars.len() - i >= name.len()
+                        && name
+                            .iter()
+                            .zip(&chars[i..])
+                            .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                        && !chars
+                            .get(i + name.len())
+                            .is_some_and(|c| c.is_alphanumeric())
+                })
+            })
+            .flatten();
+        match hit {
+            Some((name, target)) => {
+                let end = i + name.len();
+                let word: String = chars[i..end].iter().collect();
+                out.push_str(&format!(
+                    "<a href=\"{}{}\" title=\"{}\">{}</a>",
+                    INGREDIENT_LINK_SCHEME,
+                    escape(target),
+                    escape(s.show_in_pantry),
+                    escape(&word)
+                ));
+                i = end;
+            }
+            None => {
+                out.push_str(&escape(&chars[i].to_string()));
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// File an image reference points at. Relative paths are looked up in
+/// `recipes_dir` and then in its `img` folder; remote images are not loaded.
+pub fn resolve_image(recipes_dir: &Path, src: &str) -> Option<PathBuf> {
+    if src.is_empty() || src.contains("://") {
+        return None;
+    }
+    let path = Path::new(src);
+    let candidates = if path.is_absolute() {
+        vec![path.to_path_buf()]
+    } else {
+        let mut c = vec![recipes_dir.join(path)];
+        if let Some(name) = path.file_name() {
+            c.push(recipes_dir.join("img").join(name));
+        }
+        c
+    };
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// Render Markdown into a vertical box of selectable labels.
+///
+/// `on_ingredient` is called with the ingredient when one of its links is
+/// clicked; other links open in the browser.
+pub fn render_markdown(
+    text: &str,
+    ingredients: &[(String, String)],
+    recipes_dir: &Path,
+    on_ingredient: Rc<dyn Fn(String)>,
+) -> gtk::Box {
+    let container = gtk::Box::new(gtk::Orientation::Vertical, ROW_SPACING);
+    for block in parse_blocks(text) {
+        container.append(&render_block(&block, ingredients, recipes_dir, &on_ingredient));
+    }
+    container
+}
+
+fn render_block(
+    block: &Block,
+    ingredients: &[(String, String)],
+    recipes_dir: &Path,
+    on_ingredient: &Rc<dyn Fn(String)>,
+) -> gtk::Widget {
+    match block {
+        Block::Heading { level, text } => {
+            let label = markup_label(&inline_markup(text, ingredients), on_ingredient);
+            label.add_css_class(match *level {
+                1 => "title-2",
+                2 => "title-3",
+                _ => "heading",
+            });
+            label.set_margin_top(ROW_SPACING);
+            label.upcast()
+        }
+        Block::Paragraph(text) => {
+            markup_label(&inline_markup(text, ingredients), on_ingredient).upcast()
+        }
+        Block::List { ordered, start, items } => {
+            let list = gtk::Box::new(gtk::Orientation::Vertical, ROW_SPACING / 2);
+            for (n, item) in items.iter().enumerate() {
+                let row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+                let marker = if *ordered {
+                    format!("{}.", *start as usize + n)
+                } else {
+                    "•".to_string()
+                };
+                let marker = gtk::Label::new(Some(&marker));
+                marker.set_valign(gtk::Align::Start);
+                marker.set_xalign(1.0);
+                marker.set_width_chars(3);
+                row.append(&marker);
+                let label = markup_label(&inline_markup(item, ingredients), on_ingredient);
+                label.set_hexpand(true);
+                row.append(&label);
+                list.append(&row);
+            }
+            list.upcast()
+        }
+        Block::Quote(inner) => {
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, DEFAULT_MARGIN);
+            row.append(&gtk::Separator::new(gtk::Orientation::Vertical));
+            let body = gtk::Box::new(gtk::Orientation::Vertical, ROW_SPACING);
+            body.add_css_class("dim-label");
+            for block in inner {
+                body.append(&render_block(block, ingredients, recipes_dir, on_ingredient));
+            }
+            row.append(&body);
+            row.upcast()
+        }
+        Block::Code(code) => {
+            let label = gtk::Label::new(Some(code));
+            label.add_css_class("monospace");
+            label.set_selectable(true);
+            label.set_xalign(0.0);
+            label.set_wrap(true);
+            label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+            label.set_margin_start(DEFAULT_MARGIN);
+            label.upcast()
+        }
+        Block::Image { alt, src } => match resolve_image(recipes_dir, src) {
+            Some(path) => {
+                let picture = gtk::Picture::for_filename(&path);
+                picture.set_can_shrink(true);
+                picture.set_halign(gtk::Align::Start);
+                picture.set_size_request(-1, 240);
+                picture.upcast()
+            }
+            None => {
+                let label = gtk::Label::new(Some(if alt.is_empty() { src } else { alt }));
+                label.add_css_class("dim-label");
+                label.add_css_class("caption");
+                label.set_halign(gtk::Align::Start);
+                label.upcast()
+            }
+        },
+        Block::Rule => gtk::Separator::new(gtk::Orientation::Horizontal).upcast(),
+    }
+}
+
+/// A wrapping, selectable label showing `markup`, handing ingredient links to
+/// `on_ingredient`.
+fn markup_label(markup: &str, on_ingredient: &Rc<dyn Fn(String)>) -> gtk::Label {
+    let label = gtk::Label::new(None);
+    label.set_markup(markup);
+    label.set_halign(gtk::Align::Fill);
+    label.set_xalign(0.0);
+    label.set_wrap(true);
+    label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+    label.set_selectable(true);
+    let on_ingredient = Rc::clone(on_ingredient);
+    label.connect_activate_link(move |_, uri| match uri.strip_prefix(INGREDIENT_LINK_SCHEME) {
+        Some(name) => {
+            on_ingredient(name.to_string());
+            gtk::glib::Propagation::Stop
+        }
+        None => gtk::glib::Propagation::Proceed,
+    });
+    label
+}
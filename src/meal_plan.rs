@@ -0,0 +1,175 @@
+/// Weekly meal plan: recipes placed on days and meals.
+///
+/// Stored as `meal_plan.yaml` in the data directory. Entries refer to recipes by
+/// title and dates are plain `YYYY-MM-DD` strings, so the file stays readable and
+/// merges cleanly when synced.
+use crate::coverage::recipe_coverage;
+use crate::shopping_list::{BasketEntry, ShoppingItem, ShoppingList};
+use chrono::{Datelike, Days, NaiveDate};
+use janus_engine::DataManager;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File name of the meal plan inside the data directory.
+pub const MEAL_PLAN_FILE: &str = "meal_plan.yaml";
+
+/// Meal slot within a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Meal {
+    Breakfast,
+    Lunch,
+    Dinner,
+}
+
+impl Meal {
+    pub const ALL: [Meal; 3] = [Meal::Breakfast, Meal::Lunch, Meal::Dinner];
+}
+
+/// One recipe placed on a day and meal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedMeal {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub meal: Meal,
+    pub recipe: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MealPlan {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub meals: Vec<PlannedMeal>,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for MealPlan {
+    fn default() -> Self {
+        MealPlan {
+            version: default_version(),
+            meals: Vec::new(),
+        }
+    }
+}
+
+/// Monday of the week containing `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+/// The seven days of the week starting at `monday`.
+pub fn week_days(monday: NaiveDate) -> Vec<NaiveDate> {
+    (0..7).map(|i| monday + Days::new(i)).collect()
+}
+
+fn date_key(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+impl MealPlan {
+    /// Load the plan from `data_dir`. A missing file is an empty plan.
+    pub fn load(data_dir: &Path) -> Result<Self, String> {
+        let path = data_dir.join(MEAL_PLAN_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                serde_yaml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(MealPlan::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join(MEAL_PLAN_FILE);
+        let text = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
+        crate::utils::write_atomically(&path, &text)
+    }
+
+    /// Place `title` on `date`/`meal`. A recipe is only placed once per slot.
+    pub fn add(&mut self, date: NaiveDate, meal: Meal, title: &str) {
+        let date = date_key(date);
+        if self
+            .meals
+            .iter()
+            .any(|m| m.date == date && m.meal == meal && m.recipe == title)
+        {
+            return;
+        }
+        self.meals.push(PlannedMeal {
+            date,
+            meal,
+            recipe: title.to_string(),
+        });
+        self.meals.sort_by(|a, b| (&a.date, a.meal).cmp(&(&b.date, b.meal)));
+    }
+
+    pub fn remove(&mut self, date: NaiveDate, meal: Meal, title: &str) {
+        let date = date_key(date);
+        self.meals
+            .retain(|m| !(m.date == date && m.meal == meal && m.recipe == title));
+    }
+
+    /// Recipes planned for `date`/`meal`, in the order they were placed.
+    pub fn recipes_for(&self, date: NaiveDate, meal: Meal) -> Vec<&str> {
+        let date = date_key(date);
+        self.meals
+            .iter()
+            .filter(|m| m.date == date && m.meal == meal)
+            .map(|m| m.recipe.as_str())
+            .collect()
+    }
+
+    /// Every recipe planned in the week starting at `monday` (repeats included).
+    pub fn week_recipes(&self, monday: NaiveDate) -> Vec<&str> {
+        let days: Vec<String> = week_days(monday).into_iter().map(date_key).collect();
+        self.meals
+            .iter()
+            .filter(|m| days.contains(&m.date))
+            .map(|m| m.recipe.as_str())
+            .collect()
+    }
+
+    /// Follow a recipe rename.
+    pub fn rename_recipe(&mut self, old: &str, new: &str) {
+        for m in self.meals.iter_mut().filter(|m| m.recipe == old) {
+            m.recipe = new.to_string();
+        }
+    }
+
+    pub fn contains_recipe(&self, title: &str) -> bool {
+        self.meals.iter().any(|m| m.recipe == title)
+    }
+
+    /// Whether something is planned on `date` and the pantry covers all of it.
+    pub fn day_cookable(&self, dm: &DataManager, date: NaiveDate) -> bool {
+        let date = date_key(date);
+        let mut planned = self.meals.iter().filter(|m| m.date == date).peekable();
+        planned.peek().is_some()
+            && planned.all(|m| {
+                dm.get_recipe(&m.recipe)
+                    .is_some_and(|r| recipe_coverage(dm, r, 1.0).is_cookable())
+            })
+    }
+
+    /// What the pantry lacks for the week starting at `monday`, with amounts
+    /// summed across every planned meal (a recipe planned twice counts twice).
+    pub fn missing_for_week(&self, dm: &DataManager, monday: NaiveDate) -> Vec<ShoppingItem> {
+        let mut list = ShoppingList {
+            basket: self
+                .week_recipes(monday)
+                .into_iter()
+                .map(|title| BasketEntry {
+                    title: title.to_string(),
+                    servings: None,
+                })
+                .collect(),
+            ..ShoppingList::default()
+        };
+        list.regenerate(dm);
+        list.items
+    }
+}
@@ -1,6 +1,10 @@
-/// Pantry tab: ingredient list with in-stock toggle and detail view.
+/// Pantry tab: ingredient list with in-stock toggle, grouping and detail view.
 use crate::app::{App, AppMsg};
+use crate::coverage::recipe_coverage;
 use crate::i18n;
+use crate::low_stock::{is_low, running_low, threshold_text, LowItem};
+use crate::pantry_details::{days_left, is_expiring_soon, PantryDetails};
+use crate::pantry_filter::{all_tags, category_options, matches_tags, tag_options, FilterOption};
 use crate::ui_constants::*;
 use janus_engine::DataManager;
 use libadwaita as adw;
@@ -9,14 +13,31 @@ use relm4::{gtk::prelude::*, ComponentSender, RelmWidgetExt};
 use std::cell::RefCell;
 use std::rc::Rc;
 
+/// The category and tag filter menu above the pantry list. Its popover content
+/// is rebuilt by [`populate_pantry_filters`] whenever the list is.
+#[derive(Clone)]
+pub struct FilterMenu {
+    pub button: gtk::MenuButton,
+    pub content: gtk::Box,
+}
+
 /// Build the full Pantry tab widget.
 ///
-/// Returns `(tab_widget, ingredient_list_box, ingredient_detail_box, in_stock_switch)`.
+/// Returns `(tab_widget, ingredient_list_box, ingredient_detail_box, in_stock_switch,
+/// expiring_switch, location_switch, filter_menu)`.
 pub fn build_pantry_tab(
     dm: &Option<Rc<RefCell<DataManager>>>,
     in_stock_only: bool,
     sender: ComponentSender<App>,
-) -> (gtk::Widget, gtk::ListBox, gtk::Box, gtk::Switch) {
+) -> (
+    gtk::Widget,
+    gtk::ListBox,
+    gtk::Box,
+    gtk::Switch,
+    gtk::Switch,
+    gtk::Switch,
+    FilterMenu,
+) {
     let s = i18n::strings();
 
     let paned = gtk::Paned::new(gtk::Orientation::Horizontal);
@@ -28,14 +49,30 @@ pub fn build_pantry_tab(
     let left = gtk::Box::new(gtk::Orientation::Vertical, 0);
     left.set_width_request(200);
 
-    // Search bar
+    // Search bar and filter menu
+    let search_row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    search_row.set_margin_top(DEFAULT_MARGIN);
+    search_row.set_margin_bottom(ROW_SPACING);
+    search_row.set_margin_start(DEFAULT_MARGIN);
+    search_row.set_margin_end(DEFAULT_MARGIN);
+
     let search = gtk::SearchEntry::new();
     search.set_placeholder_text(Some(s.search_pantry));
-    search.set_margin_top(DEFAULT_MARGIN);
-    search.set_margin_bottom(ROW_SPACING);
-    search.set_margin_start(DEFAULT_MARGIN);
-    search.set_margin_end(DEFAULT_MARGIN);
-    left.append(&search);
+    search.set_hexpand(true);
+    search_row.append(&search);
+
+    let filter_menu = FilterMenu {
+        button: gtk::MenuButton::new(),
+        content: gtk::Box::new(gtk::Orientation::Vertical, ROW_SPACING),
+    };
+    filter_menu.button.set_label(s.filter_label);
+    filter_menu.button.set_tooltip_text(Some(s.filter_tooltip));
+    filter_menu.content.set_margin_all(ROW_SPACING);
+    let filter_popover = gtk::Popover::new();
+    filter_popover.set_child(Some(&filter_menu.content));
+    filter_menu.button.set_popover(Some(&filter_popover));
+    search_row.append(&filter_menu.button);
+    left.append(&search_row);
 
     // Filter controls
     let filter_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
@@ -55,6 +92,50 @@ pub fn build_pantry_tab(
     filter_box.append(&in_stock_switch);
     left.append(&filter_box);
 
+    let expiring_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    expiring_box.set_margin_start(DEFAULT_MARGIN);
+    expiring_box.set_margin_end(DEFAULT_MARGIN);
+    expiring_box.set_margin_bottom(ROW_SPACING);
+
+    let expiring_label = gtk::Label::new(Some(s.expiring_soon_label));
+    expiring_label.set_hexpand(true);
+    expiring_label.set_halign(gtk::Align::Start);
+
+    let expiring_switch = gtk::Switch::new();
+    expiring_switch.set_valign(gtk::Align::Center);
+
+    expiring_box.append(&expiring_label);
+    expiring_box.append(&expiring_switch);
+    left.append(&expiring_box);
+
+    // Grouping: by category, or by storage location
+    let location_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    location_box.set_margin_start(DEFAULT_MARGIN);
+    location_box.set_margin_end(DEFAULT_MARGIN);
+    location_box.set_margin_bottom(ROW_SPACING);
+
+    let location_label = gtk::Label::new(Some(s.group_by_location_label));
+    location_label.set_hexpand(true);
+    location_label.set_halign(gtk::Align::Start);
+
+    let locations_btn = gtk::Button::from_icon_name("document-edit-symbolic");
+    locations_btn.add_css_class("flat");
+    locations_btn.set_valign(gtk::Align::Center);
+    locations_btn.set_tooltip_text(Some(s.edit_storage_locations));
+    {
+        let sender_locations = sender.clone();
+        locations_btn
+            .connect_clicked(move |_| sender_locations.input(AppMsg::EditStorageLocations));
+    }
+
+    let location_switch = gtk::Switch::new();
+    location_switch.set_valign(gtk::Align::Center);
+
+    location_box.append(&location_label);
+    location_box.append(&locations_btn);
+    location_box.append(&location_switch);
+    left.append(&location_box);
+
     left.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
 
     // Ingredient list
@@ -68,15 +149,37 @@ pub fn build_pantry_tab(
     list_scroll.set_child(Some(&pantry_list));
     left.append(&list_scroll);
 
-    // Add ingredient button
+    // Add ingredient, import and export buttons
+    let btn_row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    btn_row.set_margin_all(DEFAULT_MARGIN);
+
     let add_btn = gtk::Button::with_label(s.add_ingredient_btn);
     add_btn.add_css_class("flat");
-    add_btn.set_margin_all(DEFAULT_MARGIN);
+    add_btn.set_hexpand(true);
     {
         let sender_add = sender.clone();
         add_btn.connect_clicked(move |_| sender_add.input(AppMsg::AddIngredient));
     }
-    left.append(&add_btn);
+    btn_row.append(&add_btn);
+
+    let import_btn = gtk::Button::from_icon_name("document-open-symbolic");
+    import_btn.add_css_class("flat");
+    import_btn.set_tooltip_text(Some(s.import_pantry_title));
+    {
+        let sender_import = sender.clone();
+        import_btn.connect_clicked(move |_| sender_import.input(AppMsg::ShowImportPantry));
+    }
+    btn_row.append(&import_btn);
+
+    let export_btn = gtk::Button::from_icon_name("document-save-as-symbolic");
+    export_btn.add_css_class("flat");
+    export_btn.set_tooltip_text(Some(s.export_pantry_title));
+    {
+        let sender_export = sender.clone();
+        export_btn.connect_clicked(move |_| sender_export.input(AppMsg::ExportPantry));
+    }
+    btn_row.append(&export_btn);
+    left.append(&btn_row);
 
     // ── Right: ingredient detail ──────────────────────────────────────────────
     let detail_scroll = gtk::ScrolledWindow::new();
@@ -93,7 +196,18 @@ pub fn build_pantry_tab(
     paned.set_end_child(Some(&detail_scroll));
 
     // Populate initial list
-    populate_pantry_list(&pantry_list, dm, "", &[], in_stock_only, &sender);
+    populate_pantry_list(
+        &pantry_list,
+        dm,
+        "",
+        &[],
+        &[],
+        in_stock_only,
+        &PantryDetails::default(),
+        false,
+        false,
+        &sender,
+    );
 
     // Search handler — sends message to update model, update_view will rebuild list
     {
@@ -111,30 +225,75 @@ pub fn build_pantry_tab(
         });
     }
 
+    // Expiring-soon toggle handler
+    {
+        let sender_switch = sender.clone();
+        expiring_switch.connect_active_notify(move |sw| {
+            sender_switch.input(AppMsg::ToggleExpiringOnly(sw.is_active()));
+        });
+    }
+
+    // Grouping toggle handler
+    {
+        let sender_switch = sender.clone();
+        location_switch.connect_active_notify(move |sw| {
+            sender_switch.input(AppMsg::ToggleGroupByLocation(sw.is_active()));
+        });
+    }
+
     // Row selection handler
     {
         let sender_select = sender.clone();
         pantry_list.connect_row_selected(move |_, row| {
             if let Some(row) = row {
                 let name = row.widget_name().to_string();
-                if !name.is_empty() && name != "__empty__" && name != "__header__" {
+                if !name.is_empty()
+                    && name != "__empty__"
+                    && name != "__header__"
+                    && name != "__low__"
+                {
                     sender_select.input(AppMsg::SelectIngredient(Some(name)));
                 }
             }
         });
     }
 
-    (paned.upcast(), pantry_list, ingredient_detail, in_stock_switch)
+    (
+        paned.upcast(),
+        pantry_list,
+        ingredient_detail,
+        in_stock_switch,
+        expiring_switch,
+        location_switch,
+        filter_menu,
+    )
 }
 
 /// Rebuild the pantry ingredient list with current filters.
+///
+/// `categories` and `tags` keep the ingredients in any of the selected
+/// categories that have any of the selected tags.
+///
+/// Pantry items with a best-before date get an expiry badge; `expiring_only`
+/// keeps just the items that expire soon or have expired. Ingredients below
+/// their low-stock threshold are flagged and, unless a search or the expiry
+/// filter is active, also listed in a "Running low" section at the top.
+///
+/// Items are grouped by category, or with `group_by_location` by storage
+/// location in the order the locations are listed, followed by pantry items
+/// without a location and then ingredients that aren't in the pantry.
+#[allow(clippy::too_many_arguments)]
 pub fn populate_pantry_list(
     list: &gtk::ListBox,
     dm: &Option<Rc<RefCell<DataManager>>>,
     search: &str,
     categories: &[String],
+    tags: &[String],
     in_stock_only: bool,
-    _sender: &ComponentSender<App>,
+    details: &PantryDetails,
+    expiring_only: bool,
+    group_by_location: bool,
+    sender: &ComponentSender<App>,
 ) {
     let s = i18n::strings();
     crate::utils::clear_list_box(list);
@@ -145,35 +304,93 @@ pub fn populate_pantry_list(
     };
 
     let dm = dm.borrow();
+    let today = chrono::Local::now().date_naive();
+    // Days until the best-before date of an ingredient that is in the pantry
+    let days_left_of = |name: &str| {
+        if !dm.is_in_pantry(name) {
+            return None;
+        }
+        details.best_before(name).map(|d| days_left(d, today))
+    };
     let mut ingredients = dm.filter_ingredients(search, categories, in_stock_only);
-    // Sort by (category, name), empty category sorts last via sentinel
-    ingredients.sort_by(|a, b| {
-        let ca = if a.category.is_empty() { "\u{FFFF}" } else { a.category.as_str() };
-        let cb = if b.category.is_empty() { "\u{FFFF}" } else { b.category.as_str() };
-        ca.cmp(cb).then_with(|| a.name.cmp(&b.name))
+    ingredients.retain(|ing| matches_tags(ing, tags));
+    if expiring_only {
+        ingredients.retain(|ing| days_left_of(&ing.name).is_some_and(is_expiring_soon));
+    }
+    // Group header and position of an ingredient's group; empty categories
+    // sort last
+    let locations = details.locations_or(i18n::default_storage_locations());
+    let group_of = |ing: &janus_engine::Ingredient| -> (usize, String) {
+        if !group_by_location {
+            return if ing.category.is_empty() {
+                (1, s.uncategorised.to_string())
+            } else {
+                (0, ing.category.clone())
+            };
+        }
+        if !dm.is_in_pantry(&ing.name) {
+            return (usize::MAX, s.not_in_pantry.to_string());
+        }
+        match details.location(&ing.name) {
+            Some(loc) => (
+                locations
+                    .iter()
+                    .position(|l| l == loc)
+                    .unwrap_or(locations.len()),
+                loc.to_string(),
+            ),
+            None => (usize::MAX - 1, s.no_location.to_string()),
+        }
+    };
+    ingredients.sort_by_cached_key(|ing| {
+        let (position, group) = group_of(ing);
+        (position, group, ing.name.clone())
     });
 
     if ingredients.is_empty() {
-        list.append(&empty_state_row(s.no_ingredients_found));
+        if categories.is_empty() && tags.is_empty() {
+            list.append(&empty_state_row(s.no_ingredients_found));
+        } else {
+            list.append(&filtered_empty_row(sender));
+        }
         return;
     }
 
-    let mut current_category: Option<String> = None;
+    let thresholds = crate::low_stock::thresholds();
+    if search.is_empty() && !expiring_only {
+        let low = running_low(&dm, &thresholds);
+        if !low.is_empty() {
+            list.append(&build_running_low_header(&low, sender));
+            for item in &low {
+                list.append(&build_running_low_row(item, sender));
+            }
+        }
+    }
+
+    let mut current_group: Option<String> = None;
     for ing in &ingredients {
-        let cat = if ing.category.is_empty() { s.uncategorised } else { ing.category.as_str() };
-        if current_category.as_deref() != Some(cat) {
-            list.append(&build_category_header_row(cat));
-            current_category = Some(cat.to_string());
+        let (_, group) = group_of(ing);
+        if current_group.as_ref() != Some(&group) {
+            list.append(&build_category_header_row(&group));
+            current_group = Some(group);
         }
         let in_pantry = dm.is_in_pantry(&ing.name);
-        let row = build_ingredient_row(ing, in_pantry);
+        let low = in_pantry
+            && thresholds
+                .get(&ing.name.to_lowercase())
+                .is_some_and(|t| is_low(&dm, &ing.name, t));
+        let row = build_ingredient_row(ing, in_pantry, low, days_left_of(&ing.name));
         list.append(&row);
     }
 }
 
+/// One list row: pantry status dot (half-filled when running `low`), name and,
+/// for items with a best-before date, a badge with the days left.
 fn build_ingredient_row(
     ing: &janus_engine::Ingredient,
     in_pantry: bool,
+    low: bool,
+    days_left: Option<i64>,
 ) -> gtk::ListBoxRow {
     let row = gtk::ListBoxRow::new();
     row.set_widget_name(&ing.name);
@@ -185,8 +402,17 @@ fn build_ingredient_row(
     hbox.set_margin_end(DEFAULT_MARGIN);
 
     // Pantry status dot
-    let dot = gtk::Label::new(Some(if in_pantry { "●" } else { "○" }));
-    if in_pantry {
+    let dot = gtk::Label::new(Some(if low {
+        "◐"
+    } else if in_pantry {
+        "●"
+    } else {
+        "○"
+    }));
+    if low {
+        dot.add_css_class("warning");
+        dot.set_tooltip_text(Some(i18n::strings().running_low_tooltip));
+    } else if in_pantry {
         dot.add_css_class("success");
     } else {
         dot.add_css_class("dim-label");
@@ -199,6 +425,109 @@ fn build_ingredient_row(
     name_label.set_halign(gtk::Align::Start);
     hbox.append(&name_label);
 
+    // Expiry badge
+    if let Some(days) = days_left {
+        let badge = gtk::Label::new(Some(&i18n::fmt_days_left(days)));
+        badge.add_css_class("caption");
+        badge.add_css_class(if days < 0 {
+            "error"
+        } else if is_expiring_soon(days) {
+            "warning"
+        } else {
+            "dim-label"
+        });
+        hbox.append(&badge);
+    }
+
+    row.set_child(Some(&hbox));
+    row
+}
+
+/// Heading of the "Running low" section, with a button that puts every item
+/// in it on the shopping list.
+fn build_running_low_header(low: &[LowItem], sender: &ComponentSender<App>) -> gtk::ListBoxRow {
+    let s = i18n::strings();
+    let row = gtk::ListBoxRow::new();
+    row.set_widget_name("__header__");
+    row.set_activatable(false);
+    row.set_selectable(false);
+
+    let hbox = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    hbox.set_margin_top(DEFAULT_MARGIN);
+    hbox.set_margin_bottom(ROW_SPACING);
+    hbox.set_margin_start(DEFAULT_MARGIN);
+    hbox.set_margin_end(DEFAULT_MARGIN);
+
+    let label = gtk::Label::new(Some(s.running_low_heading));
+    label.add_css_class("heading");
+    label.set_hexpand(true);
+    label.set_halign(gtk::Align::Start);
+    hbox.append(&label);
+
+    let add_all = gtk::Button::with_label(s.restock_all);
+    add_all.add_css_class("flat");
+    add_all.add_css_class("caption");
+    {
+        let sender_add = sender.clone();
+        let names: Vec<String> = low.iter().map(|l| l.ingredient.clone()).collect();
+        add_all.connect_clicked(move |_| {
+            sender_add.input(AppMsg::RestockIngredients(names.clone()));
+        });
+    }
+    hbox.append(&add_all);
+
+    row.set_child(Some(&hbox));
+    row
+}
+
+/// One "Running low" row: name, what is left of the amount to keep, and a
+/// button that adds the difference to the shopping list.
+fn build_running_low_row(item: &LowItem, sender: &ComponentSender<App>) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    row.set_widget_name("__low__");
+    row.set_activatable(false);
+    row.set_selectable(false);
+
+    let hbox = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    hbox.set_margin_top(ROW_SPACING);
+    hbox.set_margin_bottom(ROW_SPACING);
+    hbox.set_margin_start(DEFAULT_MARGIN);
+    hbox.set_margin_end(DEFAULT_MARGIN);
+
+    let text = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    text.set_hexpand(true);
+    let name_label = gtk::Label::new(Some(&item.ingredient));
+    name_label.set_halign(gtk::Align::Start);
+    text.append(&name_label);
+    let have = item.have.map(|have| {
+        threshold_text(&crate::low_stock::Threshold {
+            quantity: have,
+            unit: item.threshold.unit.clone(),
+        })
+    });
+    let amounts = gtk::Label::new(Some(&i18n::fmt_low_stock(
+        have.as_deref(),
+        &threshold_text(&item.threshold),
+    )));
+    amounts.add_css_class("caption");
+    amounts.add_css_class("warning");
+    amounts.set_halign(gtk::Align::Start);
+    text.append(&amounts);
+    hbox.append(&text);
+
+    let add_btn = gtk::Button::from_icon_name("list-add-symbolic");
+    add_btn.add_css_class("flat");
+    add_btn.set_valign(gtk::Align::Center);
+    add_btn.set_tooltip_text(Some(i18n::strings().add_to_basket));
+    {
+        let sender_add = sender.clone();
+        let name = item.ingredient.clone();
+        add_btn.connect_clicked(move |_| {
+            sender_add.input(AppMsg::RestockIngredients(vec![name.clone()]));
+        });
+    }
+    hbox.append(&add_btn);
+
     row.set_child(Some(&hbox));
     row
 }
@@ -225,6 +554,7 @@ pub fn update_ingredient_detail(
     detail: &gtk::Box,
     dm: &Option<Rc<RefCell<DataManager>>>,
     name: &str,
+    details: &PantryDetails,
     sender: &ComponentSender<App>,
 ) {
     let s = i18n::strings();
@@ -282,11 +612,33 @@ pub fn update_ingredient_detail(
         detail.append(&status);
 
         if let Some(qty) = item.quantity {
-            let qty_label = gtk::Label::new(Some(&i18n::fmt_quantity(qty, &item.quantity_type)));
+            let (qty, unit) = crate::units::localize(qty, &item.quantity_type);
+            let qty_label = gtk::Label::new(Some(&i18n::fmt_quantity(qty, &unit)));
             qty_label.set_halign(gtk::Align::Start);
             detail.append(&qty_label);
         }
 
+        if let Some(location) = details.location(name) {
+            let stored = gtk::Label::new(Some(&i18n::fmt_stored_in(location)));
+            stored.set_halign(gtk::Align::Start);
+            detail.append(&stored);
+        }
+
+        if let Some(date) = details.best_before(name) {
+            let days = days_left(date, chrono::Local::now().date_naive());
+            let expiry = gtk::Label::new(Some(&i18n::fmt_best_before(
+                &date.format("%Y-%m-%d").to_string(),
+                days,
+            )));
+            if days < 0 {
+                expiry.add_css_class("error");
+            } else if is_expiring_soon(days) {
+                expiry.add_css_class("warning");
+            }
+            expiry.set_halign(gtk::Align::Start);
+            detail.append(&expiry);
+        }
+
         let updated = gtk::Label::new(Some(&i18n::fmt_last_updated(&item.last_updated)));
         updated.add_css_class("caption");
         updated.add_css_class("dim-label");
@@ -299,6 +651,14 @@ pub fn update_ingredient_detail(
         detail.append(&status);
     }
 
+    if let Some(threshold) = crate::low_stock::threshold(name) {
+        let min = gtk::Label::new(Some(&i18n::fmt_min_quantity(&threshold_text(&threshold))));
+        min.add_css_class("caption");
+        min.add_css_class("dim-label");
+        min.set_halign(gtk::Align::Start);
+        detail.append(&min);
+    }
+
     // ── Used in recipes ───────────────────────────────────────────────────────
     let recipes = dm.get_recipes_with_ingredient(name);
     if !recipes.is_empty() {
@@ -309,10 +669,12 @@ pub fn update_ingredient_detail(
         detail.append(&recipes_header);
 
         for recipe in &recipes {
-            let cov = recipe.pantry_coverage(&dm);
-            let tooltip = i18n::fmt_required_tooltip(cov.required_in_stock, cov.required_total);
+            let cov = recipe_coverage(&dm, recipe, 1.0);
+            let tooltip =
+                i18n::fmt_required_tooltip(cov.covered, cov.partial, cov.required_total);
             let recipe_row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
-            let pie = crate::utils::build_coverage_pie(cov.required_ratio(), &tooltip);
+            let pie =
+                crate::utils::build_coverage_pie(cov.covered_ratio(), cov.partial_ratio(), &tooltip);
             recipe_row.append(&pie);
             let recipe_label = gtk::Label::new(Some(&recipe.title));
             recipe_label.set_halign(gtk::Align::Start);
@@ -394,6 +756,131 @@ pub fn show_ingredient_placeholder(detail: &gtk::Box) {
     detail.append(&status);
 }
 
+/// Rebuild the filter popover: a check button per category and tag with the
+/// number of listed ingredients it matches, and "Clear filters". The menu
+/// button shows how many filters are active.
+pub fn populate_pantry_filters(
+    menu: &FilterMenu,
+    dm: &Option<Rc<RefCell<DataManager>>>,
+    search: &str,
+    in_stock_only: bool,
+    categories: &[String],
+    tags: &[String],
+    sender: &ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    crate::utils::clear_box(&menu.content);
+    let active = categories.len() + tags.len();
+    if active == 0 {
+        menu.button.set_label(s.filter_label);
+        menu.button.remove_css_class("accent");
+    } else {
+        menu.button.set_label(&i18n::fmt_active_filters(active));
+        menu.button.add_css_class("accent");
+    }
+
+    let Some(dm) = dm else {
+        return;
+    };
+    let dm = dm.borrow();
+    // Counted over what the search and stock filter leave
+    let listed = dm.filter_ingredients(search, &[], in_stock_only);
+    let category_choices = category_options(
+        &dm.get_all_ingredient_categories(),
+        &listed,
+        categories,
+        tags,
+    );
+    let tag_choices = tag_options(
+        &all_tags(&dm.get_all_ingredients()),
+        &listed,
+        tags,
+        categories,
+    );
+
+    let scroll = gtk::ScrolledWindow::new();
+    scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    scroll.set_max_content_height(360);
+    scroll.set_propagate_natural_height(true);
+    let choices = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    append_filter_choices(&choices, s.categories_heading, category_choices, sender, |category, selected| {
+        AppMsg::SetCategoryFilter { category, selected }
+    });
+    append_filter_choices(&choices, s.tags_heading, tag_choices, sender, |tag, selected| {
+        AppMsg::SetTagFilter { tag, selected }
+    });
+    scroll.set_child(Some(&choices));
+    menu.content.append(&scroll);
+
+    let clear_btn = gtk::Button::with_label(s.clear_filters);
+    clear_btn.add_css_class("flat");
+    clear_btn.set_sensitive(active > 0);
+    {
+        let sender_clear = sender.clone();
+        let button = menu.button.clone();
+        clear_btn.connect_clicked(move |_| {
+            button.popdown();
+            sender_clear.input(AppMsg::ClearPantryFilters);
+        });
+    }
+    menu.content.append(&clear_btn);
+}
+
+/// A heading and one check button per choice; toggling sends `msg(name, active)`.
+fn append_filter_choices(
+    container: &gtk::Box,
+    heading: &str,
+    choices: Vec<FilterOption>,
+    sender: &ComponentSender<App>,
+    msg: fn(String, bool) -> AppMsg,
+) {
+    if choices.is_empty() {
+        return;
+    }
+    let label = gtk::Label::new(Some(heading));
+    label.add_css_class("heading");
+    label.set_halign(gtk::Align::Start);
+    label.set_margin_top(ROW_SPACING);
+    label.set_margin_bottom(ROW_SPACING);
+    container.append(&label);
+
+    for choice in choices {
+        let check = gtk::CheckButton::with_label(&format!("{} ({})", choice.name, choice.count));
+        check.set_active(choice.selected);
+        let sender_toggle = sender.clone();
+        check.connect_toggled(move |c| {
+            sender_toggle.input(msg(choice.name.clone(), c.is_active()));
+        });
+        container.append(&check);
+    }
+}
+
+/// Empty state when the category or tag filters hide everything, with a way out.
+fn filtered_empty_row(sender: &ComponentSender<App>) -> gtk::ListBoxRow {
+    let s = i18n::strings();
+    let row = gtk::ListBoxRow::new();
+    row.set_widget_name("__empty__");
+    row.set_activatable(false);
+    row.set_selectable(false);
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, ROW_SPACING);
+    content.set_margin_top(DEFAULT_MARGIN);
+    content.set_margin_bottom(DEFAULT_MARGIN);
+    let label = gtk::Label::new(Some(s.no_ingredients_match_filters));
+    label.add_css_class("dim-label");
+    content.append(&label);
+
+    let clear_btn = gtk::Button::with_label(s.clear_filters);
+    clear_btn.set_halign(gtk::Align::Center);
+    {
+        let sender_clear = sender.clone();
+        clear_btn.connect_clicked(move |_| sender_clear.input(AppMsg::ClearPantryFilters));
+    }
+    content.append(&clear_btn);
+    row.set_child(Some(&content));
+    row
+}
+
 fn empty_state_row(text: &str) -> gtk::ListBoxRow {
     let row = gtk::ListBoxRow::new();
     row.set_widget_name("__empty__");
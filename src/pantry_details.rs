@@ -0,0 +1,200 @@
+/// Pantry item details the engine's pantry files have no room for: best-before
+/// dates and storage locations.
+///
+/// The engine rewrites the pantry files from its own `PantryItem`s and drops keys
+/// it doesn't know, so the details are stored in `pantry_details.yaml` next to
+/// them, keyed by ingredient slug, together with the user's list of storage
+/// locations. Dates are plain `YYYY-MM-DD` strings. Entries for items that have
+/// left the pantry are dropped on the next save.
+use chrono::NaiveDate;
+use janus_engine::{slugify, DataManager};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// File name of the pantry details inside the data directory.
+pub const PANTRY_DETAILS_FILE: &str = "pantry_details.yaml";
+
+/// Items whose best-before date is at most this many days away count as
+/// expiring soon (already expired ones included).
+pub const EXPIRING_SOON_DAYS: i64 = 3;
+
+/// Details of one pantry item.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ItemDetails {
+    /// `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_before: Option<String>,
+    /// One of `PantryDetails::locations`, e.g. "Freezer".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl ItemDetails {
+    fn is_empty(&self) -> bool {
+        self.best_before.is_none() && self.location.is_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PantryDetails {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    /// Storage locations in the order they are listed; empty until the user
+    /// edits them (the UI then offers a default set).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<String>,
+    #[serde(default)]
+    pub items: BTreeMap<String, ItemDetails>,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for PantryDetails {
+    fn default() -> Self {
+        PantryDetails {
+            version: default_version(),
+            locations: Vec::new(),
+            items: BTreeMap::new(),
+        }
+    }
+}
+
+impl PantryDetails {
+    /// Load the details from `data_dir`. A missing file has no details.
+    pub fn load(data_dir: &Path) -> Result<Self, String> {
+        let path = data_dir.join(PANTRY_DETAILS_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                serde_yaml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PantryDetails::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join(PANTRY_DETAILS_FILE);
+        let text = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
+        crate::utils::write_atomically(&path, &text)
+    }
+
+    /// Details of `ingredient` (empty if none are set).
+    pub fn item(&self, ingredient: &str) -> ItemDetails {
+        self.items.get(&slugify(ingredient)).cloned().unwrap_or_default()
+    }
+
+    pub fn set_item(&mut self, ingredient: &str, details: ItemDetails) {
+        self.update(ingredient, |d| *d = details);
+    }
+
+    /// Best-before date of `ingredient`; `None` if unset or unreadable.
+    pub fn best_before(&self, ingredient: &str) -> Option<NaiveDate> {
+        let date = self.items.get(&slugify(ingredient))?.best_before.as_deref()?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+
+    pub fn set_best_before(&mut self, ingredient: &str, date: Option<NaiveDate>) {
+        self.update(ingredient, |d| {
+            d.best_before = date.map(|d| d.format("%Y-%m-%d").to_string())
+        });
+    }
+
+    /// Storage location of `ingredient`, if set.
+    pub fn location(&self, ingredient: &str) -> Option<&str> {
+        self.items.get(&slugify(ingredient))?.location.as_deref()
+    }
+
+    pub fn set_location(&mut self, ingredient: &str, location: Option<String>) {
+        self.update(ingredient, |d| d.location = location);
+    }
+
+    /// The storage locations to offer: the user's list, or `defaults` while
+    /// they haven't set one up.
+    pub fn locations_or(&self, defaults: &[&str]) -> Vec<String> {
+        if self.locations.is_empty() {
+            defaults.iter().map(|l| l.to_string()).collect()
+        } else {
+            self.locations.clone()
+        }
+    }
+
+    /// Replace the list of storage locations. Items stored in a location that
+    /// is no longer listed lose their location.
+    pub fn set_locations(&mut self, locations: Vec<String>) {
+        self.locations = locations;
+        for details in self.items.values_mut() {
+            if details.location.as_ref().is_some_and(|l| !self.locations.contains(l)) {
+                details.location = None;
+            }
+        }
+        self.items.retain(|_, d| !d.is_empty());
+    }
+
+    fn update(&mut self, ingredient: &str, change: impl FnOnce(&mut ItemDetails)) {
+        let key = slugify(ingredient);
+        let details = self.items.entry(key.clone()).or_default();
+        change(details);
+        if details.is_empty() {
+            self.items.remove(&key);
+        }
+    }
+
+    /// Follow an ingredient rename.
+    pub fn rename(&mut self, old: &str, new: &str) {
+        let (old_slug, new_slug) = (slugify(old), slugify(new));
+        if old_slug != new_slug {
+            if let Some(details) = self.items.remove(&old_slug) {
+                self.items.insert(new_slug, details);
+            }
+        }
+    }
+
+    /// Drop the details of items that are no longer in the pantry. Returns
+    /// whether anything was removed.
+    pub fn prune(&mut self, dm: &DataManager) -> bool {
+        let in_pantry: BTreeSet<String> = dm
+            .get_all_ingredients()
+            .into_iter()
+            .filter(|i| dm.is_in_pantry(&i.name))
+            .map(|i| slugify(&i.name))
+            .collect();
+        let before = self.items.len();
+        self.items.retain(|slug, _| in_pantry.contains(slug));
+        self.items.len() != before
+    }
+
+    /// Slugs of the pantry items that expire within [`EXPIRING_SOON_DAYS`] of
+    /// `today`, or have expired.
+    pub fn expiring_soon(&self, dm: &DataManager, today: NaiveDate) -> BTreeSet<String> {
+        dm.get_all_ingredients()
+            .into_iter()
+            .filter(|i| dm.is_in_pantry(&i.name))
+            .filter(|i| {
+                self.best_before(&i.name)
+                    .is_some_and(|d| is_expiring_soon(days_left(d, today)))
+            })
+            .map(|i| slugify(&i.name))
+            .collect()
+    }
+}
+
+/// Whole days from `today` until `best_before`; negative once it has passed.
+pub fn days_left(best_before: NaiveDate, today: NaiveDate) -> i64 {
+    (best_before - today).num_days()
+}
+
+pub fn is_expiring_soon(days_left: i64) -> bool {
+    days_left <= EXPIRING_SOON_DAYS
+}
+
+/// Parse a best-before date typed in the ingredient dialog: `YYYY-MM-DD`, or
+/// `DD-MM-YYYY` / `DD.MM.YYYY` / `DD/MM/YYYY` as written in Denmark.
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    ["%Y-%m-%d", "%d-%m-%Y", "%d.%m.%Y", "%d/%m/%Y"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(text, fmt).ok())
+}
@@ -0,0 +1,100 @@
+/// Category and tag filters of the Pantry tab.
+///
+/// Categories are filtered by the engine's `filter_ingredients`; tags are
+/// matched here against `Ingredient::tags`, ignoring case. An ingredient passes
+/// a filter when it matches any of its selected values. The counts shown next to
+/// each choice are faceted: category counts honour the tag filter and the other
+/// way round. The selected filters are kept in the user settings.
+use janus_engine::Ingredient;
+
+/// One choice in the filter popover.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterOption {
+    pub name: String,
+    /// How many of the listed ingredients it matches.
+    pub count: usize,
+    pub selected: bool,
+}
+
+pub fn has_tag(ing: &Ingredient, tag: &str) -> bool {
+    crate::tags::has_tag(&ing.tags, tag)
+}
+
+/// Whether `ing` has any of `tags`; always true when no tag is selected.
+pub fn matches_tags(ing: &Ingredient, tags: &[String]) -> bool {
+    tags.is_empty() || tags.iter().any(|t| has_tag(ing, t))
+}
+
+/// Whether `ing` is in any of `categories`; always true when none is selected.
+pub fn matches_categories(ing: &Ingredient, categories: &[String]) -> bool {
+    categories.is_empty() || categories.contains(&ing.category)
+}
+
+/// Every tag used by `ingredients`, sorted, with case variants merged.
+pub fn all_tags(ingredients: &[&Ingredient]) -> Vec<String> {
+    crate::tags::all_tags(ingredients.iter().map(|i| &i.tags))
+}
+
+/// Category choices with how many of `ingredients` that pass the tag filter
+/// are in each.
+pub fn category_options(
+    categories: &[String],
+    ingredients: &[&Ingredient],
+    selected: &[String],
+    tags: &[String],
+) -> Vec<FilterOption> {
+    options(categories, selected, |name| {
+        ingredients
+            .iter()
+            .filter(|i| i.category == name && matches_tags(i, tags))
+            .count()
+    })
+}
+
+/// Tag choices with how many of `ingredients` that pass the category filter
+/// have each.
+pub fn tag_options(
+    tags: &[String],
+    ingredients: &[&Ingredient],
+    selected: &[String],
+    categories: &[String],
+) -> Vec<FilterOption> {
+    options(tags, selected, |name| {
+        ingredients
+            .iter()
+            .filter(|i| has_tag(i, name) && matches_categories(i, categories))
+            .count()
+    })
+}
+
+/// `names` as choices. Selected names that no longer occur are kept (with a
+/// count of 0) so they can still be cleared.
+fn options(
+    names: &[String],
+    selected: &[String],
+    count: impl Fn(&str) -> usize,
+) -> Vec<FilterOption> {
+    let mut all: Vec<&String> = names.iter().collect();
+    for name in selected {
+        if !all.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            all.push(name);
+        }
+    }
+    all.sort_by_key(|n| n.to_lowercase());
+    all.into_iter()
+        .map(|name| FilterOption {
+            name: name.clone(),
+            count: count(name),
+            selected: selected.iter().any(|s| s.eq_ignore_ascii_case(name)),
+        })
+        .collect()
+}
+
+/// Add `name` to or remove it from the selected filter values.
+pub fn set_selected(values: &mut Vec<String>, name: &str, selected: bool) {
+    values.retain(|v| !v.eq_ignore_ascii_case(name));
+    if selected {
+        values.push(name.to_string());
+        values.sort_by_key(|v| v.to_lowercase());
+    }
+}
@@ -0,0 +1,383 @@
+/// Pantry import and export as CSV or JSON.
+///
+/// Both formats carry the same columns: `ingredient`, `quantity`, `unit`,
+/// `last_updated` and `category`. Importing is done in two steps: [`preview`]
+/// checks every row against the data (unknown ingredients, conflicts with what
+/// is in the pantry, quantities that don't parse) so the user can review it,
+/// and only then are the rows written. `last_updated` is exported for
+/// reference and shown in the preview, but imported items are dated by the
+/// engine when they are saved. `category` is only used for ingredients the
+/// import creates.
+use crate::quantity::parse_quantity;
+use janus_engine::{fold_for_matching, DataManager};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Column names, in export order.
+pub const COLUMNS: [&str; 5] = ["ingredient", "quantity", "unit", "last_updated", "category"];
+
+/// One pantry item as exported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PantryRow {
+    pub ingredient: String,
+    #[serde(default)]
+    pub quantity: Option<f64>,
+    #[serde(default)]
+    pub unit: String,
+    #[serde(default)]
+    pub last_updated: String,
+    #[serde(default)]
+    pub category: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PantryFormat {
+    Csv,
+    Json,
+}
+
+impl PantryFormat {
+    /// The format a file name stands for; anything but `.json` is read as CSV.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => PantryFormat::Json,
+            _ => PantryFormat::Csv,
+        }
+    }
+}
+
+/// Every pantry item, sorted by ingredient name.
+pub fn export_rows(dm: &DataManager) -> Vec<PantryRow> {
+    let mut rows: Vec<PantryRow> = dm
+        .get_all_ingredients()
+        .into_iter()
+        .filter_map(|ing| {
+            let item = dm.get_pantry_item(&ing.name)?;
+            Some(PantryRow {
+                ingredient: ing.name.clone(),
+                quantity: item.quantity,
+                unit: item.quantity_type.clone(),
+                last_updated: item.last_updated.clone(),
+                category: ing.category.clone(),
+            })
+        })
+        .collect();
+    rows.sort_by(|a, b| a.ingredient.cmp(&b.ingredient));
+    rows
+}
+
+/// `rows` as CSV with a header line.
+pub fn to_csv(rows: &[PantryRow]) -> String {
+    let mut out = COLUMNS.join(",");
+    out.push('\n');
+    for row in rows {
+        let quantity = row.quantity.map(|q| q.to_string()).unwrap_or_default();
+        let fields = [&row.ingredient, &quantity, &row.unit, &row.last_updated, &row.category];
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// `rows` as a pretty-printed JSON array.
+pub fn to_json(rows: &[PantryRow]) -> String {
+    serde_json::to_string_pretty(rows).unwrap_or_else(|_| "[]".to_string())
+}
+
+pub fn export(rows: &[PantryRow], format: PantryFormat) -> String {
+    match format {
+        PantryFormat::Csv => to_csv(rows),
+        PantryFormat::Json => to_json(rows),
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', ';', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Split CSV text into records. Fields may be quoted (with `""` for a quote)
+/// and span lines; the delimiter is `,`, or `;` or tab when the first line
+/// uses those instead (as spreadsheets in many locales do).
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let text = text.trim_start_matches('\u{feff}');
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|d| first_line.matches(*d).count())
+        .filter(|d| first_line.contains(*d))
+        .unwrap_or(',');
+
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    records
+}
+
+/// A row read from an import file, before it is checked against the data.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RawRow {
+    /// 1-based line (CSV) or item number (JSON), for messages.
+    pub line: usize,
+    pub ingredient: String,
+    pub quantity: String,
+    pub unit: String,
+    pub last_updated: String,
+    pub category: String,
+}
+
+/// The rows of a CSV file. A header line naming the columns is used when
+/// there is one; otherwise the columns are taken in export order.
+pub fn raw_rows_from_csv(text: &str) -> Vec<RawRow> {
+    let records = parse_csv(text);
+    let Some(first) = records.first() else {
+        return Vec::new();
+    };
+    let header: Vec<Option<usize>> = first.iter().map(|h| column_index(h)).collect();
+    let has_header = header.iter().any(|h| *h == Some(0));
+    let columns: Vec<Option<usize>> = if has_header {
+        header
+    } else {
+        (0..COLUMNS.len()).map(Some).collect()
+    };
+
+    let skip = usize::from(has_header);
+    records
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(n, record)| {
+            let mut row = RawRow { line: n + 1, ..RawRow::default() };
+            for (value, column) in record.iter().zip(&columns) {
+                let value = value.trim().to_string();
+                match column {
+                    Some(0) => row.ingredient = value,
+                    Some(1) => row.quantity = value,
+                    Some(2) => row.unit = value,
+                    Some(3) => row.last_updated = value,
+                    Some(4) => row.category = value,
+                    _ => {}
+                }
+            }
+            row
+        })
+        .collect()
+}
+
+/// Which of [`COLUMNS`] a header names, accepting a few common alternatives.
+fn column_index(header: &str) -> Option<usize> {
+    match header.trim().to_lowercase().replace([' ', '-'], "_").as_str() {
+        "ingredient" | "name" | "item" | "ingrediens" | "navn" => Some(0),
+        "quantity" | "amount" | "qty" | "mængde" | "antal" => Some(1),
+        "unit" | "quantity_type" | "enhed" => Some(2),
+        "last_updated" | "updated" | "date" | "dato" => Some(3),
+        "category" | "kategori" => Some(4),
+        _ => None,
+    }
+}
+
+/// The rows of a JSON file: an array of objects (as exported), or an object
+/// with such an array under `items`, like `pantry.yaml`.
+pub fn raw_rows_from_json(text: &str) -> Result<Vec<RawRow>, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let items = match &value {
+        Value::Array(items) => items,
+        Value::Object(map) => match map.get("items") {
+            Some(Value::Array(items)) => items,
+            _ => return Err("expected a list of pantry items".to_string()),
+        },
+        _ => return Err("expected a list of pantry items".to_string()),
+    };
+    let text = |item: &Value, keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| item.get(*k))
+            .map(|v| match v {
+                Value::String(s) => s.trim().to_string(),
+                Value::Number(n) => n.to_string(),
+                _ => String::new(),
+            })
+            .unwrap_or_default()
+    };
+    Ok(items
+        .iter()
+        .enumerate()
+        .map(|(n, item)| RawRow {
+            line: n + 1,
+            ingredient: text(item, &["ingredient", "name"]),
+            quantity: text(item, &["quantity", "amount"]),
+            unit: text(item, &["unit", "quantity_type"]),
+            last_updated: text(item, &["last_updated"]),
+            category: text(item, &["category"]),
+        })
+        .collect())
+}
+
+/// Read an import file in the format its name suggests.
+pub fn read_rows(path: &Path, text: &str) -> Result<Vec<RawRow>, String> {
+    match PantryFormat::from_path(path) {
+        PantryFormat::Csv => Ok(raw_rows_from_csv(text)),
+        PantryFormat::Json => raw_rows_from_json(text),
+    }
+}
+
+/// What importing a row would do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowStatus {
+    /// A known ingredient that isn't in the pantry yet.
+    New,
+    /// An ingredient the library doesn't have; it is created on import.
+    UnknownIngredient,
+    /// Already in the pantry with a different amount, shown here.
+    Conflict { quantity: Option<f64>, unit: String },
+    /// Already in the pantry with the same amount.
+    Unchanged,
+    /// Can't be imported, for the given reason.
+    Invalid(String),
+}
+
+/// A checked import row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRow {
+    pub line: usize,
+    /// The library's name for the ingredient when it is known.
+    pub ingredient: String,
+    pub quantity: Option<f64>,
+    pub unit: String,
+    /// The date the file gives; not imported, the item is dated when saved.
+    pub last_updated: String,
+    pub category: String,
+    /// The category a known ingredient keeps when the row names another one.
+    pub kept_category: Option<String>,
+    pub status: RowStatus,
+}
+
+/// Check `rows` against the ingredient library and the pantry. Names are
+/// matched ignoring case and accents; a unit written in the quantity column
+/// ("500 g") is split off when the unit column is empty.
+pub fn preview(dm: &DataManager, rows: Vec<RawRow>) -> Vec<ImportRow> {
+    let ingredients = dm.get_all_ingredients();
+    let mut seen: Vec<String> = Vec::new();
+    rows.into_iter()
+        .map(|raw| {
+            let known = ingredients
+                .iter()
+                .find(|ing| fold_for_matching(&ing.name) == fold_for_matching(&raw.ingredient));
+            let ingredient = known.map_or(raw.ingredient.clone(), |ing| ing.name.clone());
+            let kept_category = known
+                .filter(|ing| {
+                    !raw.category.is_empty()
+                        && fold_for_matching(&ing.category) != fold_for_matching(&raw.category)
+                })
+                .map(|ing| ing.category.clone());
+            let mut row = ImportRow {
+                line: raw.line,
+                ingredient,
+                quantity: None,
+                unit: raw.unit.clone(),
+                last_updated: raw.last_updated.clone(),
+                category: raw.category.clone(),
+                kept_category,
+                status: RowStatus::New,
+            };
+
+            if row.ingredient.is_empty() {
+                row.status = RowStatus::Invalid(crate::i18n::strings().missing_ingredient.to_string());
+                return row;
+            }
+            match parse_row_quantity(&raw.quantity, &raw.unit) {
+                Ok((quantity, unit)) => {
+                    row.quantity = quantity;
+                    row.unit = unit;
+                }
+                Err(message) => {
+                    row.status = RowStatus::Invalid(message);
+                    return row;
+                }
+            }
+            let folded = fold_for_matching(&row.ingredient);
+            if seen.contains(&folded) {
+                row.status =
+                    RowStatus::Invalid(crate::i18n::strings().duplicate_pantry_row.to_string());
+                return row;
+            }
+            seen.push(folded);
+
+            row.status = match (known, dm.get_pantry_item(&row.ingredient)) {
+                (None, _) => RowStatus::UnknownIngredient,
+                (Some(_), None) => RowStatus::New,
+                (Some(_), Some(item))
+                    if item.quantity == row.quantity && item.quantity_type == row.unit =>
+                {
+                    RowStatus::Unchanged
+                }
+                (Some(_), Some(item)) => RowStatus::Conflict {
+                    quantity: item.quantity,
+                    unit: item.quantity_type.clone(),
+                },
+            };
+            row
+        })
+        .collect()
+}
+
+/// The amount and unit of a row. An empty quantity means "in stock, amount
+/// unknown"; negative amounts and ranges are rejected. A unit given in its
+/// own column is kept as written, like the pantry stores it.
+fn parse_row_quantity(quantity: &str, unit: &str) -> Result<(Option<f64>, String), String> {
+    let quantity = quantity.trim();
+    let unit = unit.trim();
+    if quantity.is_empty() {
+        return Ok((None, unit.to_string()));
+    }
+    let invalid = || crate::i18n::fmt_invalid_quantity(quantity);
+    let (amount, parsed_unit) = match parse_quantity(quantity) {
+        Ok(parsed) => (parsed.amount, parsed.unit),
+        // Units the registry doesn't know are kept as written
+        Err(_) if unit.is_empty() => {
+            let split = quantity
+                .find(|c: char| c.is_alphabetic())
+                .ok_or_else(invalid)?;
+            let amount = parse_quantity(quantity[..split].trim()).map_err(|_| invalid())?;
+            (amount.amount, Some(quantity[split..].trim().to_string()))
+        }
+        Err(_) => return Err(invalid()),
+    };
+    let value = match amount {
+        Some(crate::quantity::Amount::Single(v)) if v >= 0.0 => v,
+        _ => return Err(invalid()),
+    };
+    let unit = if unit.is_empty() { parsed_unit.unwrap_or_default() } else { unit.to_string() };
+    Ok((Some(value), unit))
+}
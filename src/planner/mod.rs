@@ -0,0 +1,346 @@
+/// Planner tab: a week grid of days × meals with recipes placed on it.
+///
+/// Recipes are placed by dragging them from the list on the left or with the
+/// "+" picker in each cell. Each placed recipe shows its pantry coverage, fully
+/// cookable days get a check mark, and the week's missing ingredients are
+/// listed below the grid.
+use crate::app::{App, AppMsg};
+use crate::coverage::recipe_coverage;
+use crate::i18n;
+use crate::meal_plan::{week_days, Meal, MealPlan};
+use crate::quantity::format_amount;
+use crate::ui_constants::*;
+use chrono::NaiveDate;
+use janus_engine::DataManager;
+use relm4::gtk;
+use relm4::{gtk::prelude::*, ComponentSender, RelmWidgetExt};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Build the full Planner tab widget.
+///
+/// Returns `(tab_widget, recipe_list_box, week_content_box)`; both are filled by
+/// `update_planner_view`.
+pub fn build_planner_tab() -> (gtk::Widget, gtk::ListBox, gtk::Box) {
+    let s = i18n::strings();
+
+    let paned = gtk::Paned::new(gtk::Orientation::Horizontal);
+    paned.set_hexpand(true);
+    paned.set_vexpand(true);
+    paned.set_position(LIST_PANE_WIDTH);
+
+    // ── Left: recipes to drag onto the week ───────────────────────────────────
+    let left = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    left.set_width_request(200);
+
+    let hint = gtk::Label::new(Some(s.planner_recipes_hint));
+    hint.add_css_class("caption");
+    hint.add_css_class("dim-label");
+    hint.set_wrap(true);
+    hint.set_margin_all(DEFAULT_MARGIN);
+    left.append(&hint);
+    left.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+    let list_scroll = gtk::ScrolledWindow::new();
+    list_scroll.set_vexpand(true);
+    list_scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let recipe_list = gtk::ListBox::new();
+    recipe_list.set_selection_mode(gtk::SelectionMode::None);
+    recipe_list.add_css_class("navigation-sidebar");
+    list_scroll.set_child(Some(&recipe_list));
+    left.append(&list_scroll);
+
+    // ── Right: week grid + missing ingredients ────────────────────────────────
+    let scroll = gtk::ScrolledWindow::new();
+    scroll.set_hexpand(true);
+    scroll.set_vexpand(true);
+    scroll.set_policy(gtk::PolicyType::Automatic, gtk::PolicyType::Automatic);
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+    content.set_margin_all(DEFAULT_MARGIN);
+    scroll.set_child(Some(&content));
+
+    paned.set_start_child(Some(&left));
+    paned.set_end_child(Some(&scroll));
+
+    (paned.upcast(), recipe_list, content)
+}
+
+/// Rebuild the Planner tab for the week starting at `monday`.
+pub fn update_planner_view(
+    recipe_list: &gtk::ListBox,
+    content: &gtk::Box,
+    dm: &Option<Rc<RefCell<DataManager>>>,
+    data_dir: &Path,
+    plan: &MealPlan,
+    monday: NaiveDate,
+    sender: &ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    crate::utils::clear_list_box(recipe_list);
+    crate::utils::clear_box(content);
+
+    let Some(dm) = dm else {
+        let label = gtk::Label::new(Some(s.no_data_dir));
+        label.add_css_class("dim-label");
+        content.append(&label);
+        return;
+    };
+    let dm = dm.borrow();
+
+    let mut titles: Vec<String> = dm.get_all_recipes().iter().map(|r| r.title.clone()).collect();
+    titles.sort();
+    for title in &titles {
+        if let Some(recipe) = dm.get_recipe(title) {
+            let cov = recipe_coverage(&dm, recipe, 1.0);
+            // Thumbnails are generated by the Recipes tab; use whatever is cached
+            let thumbnail = crate::images::recipe_image_path(data_dir, recipe).and_then(|src| {
+                crate::images::cached_thumbnail(&src, crate::images::THUMBNAIL_SIZE)
+            });
+            recipe_list.append(&crate::recipes::build_recipe_row(
+                recipe,
+                cov,
+                None,
+                thumbnail.as_deref(),
+            ));
+        }
+    }
+    let titles = Rc::new(titles);
+
+    // ── Week navigation ───────────────────────────────────────────────────────
+    let nav = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+
+    let prev_btn = gtk::Button::from_icon_name("go-previous-symbolic");
+    prev_btn.add_css_class("flat");
+    prev_btn.set_tooltip_text(Some(s.previous_week));
+    {
+        let sender_prev = sender.clone();
+        prev_btn.connect_clicked(move |_| sender_prev.input(AppMsg::ShiftPlannerWeek(-1)));
+    }
+    nav.append(&prev_btn);
+
+    let week_label = gtk::Label::new(Some(&i18n::fmt_week(monday)));
+    week_label.add_css_class("title-3");
+    nav.append(&week_label);
+
+    let next_btn = gtk::Button::from_icon_name("go-next-symbolic");
+    next_btn.add_css_class("flat");
+    next_btn.set_tooltip_text(Some(s.next_week));
+    {
+        let sender_next = sender.clone();
+        next_btn.connect_clicked(move |_| sender_next.input(AppMsg::ShiftPlannerWeek(1)));
+    }
+    nav.append(&next_btn);
+
+    let today_btn = gtk::Button::with_label(s.this_week);
+    today_btn.add_css_class("flat");
+    {
+        let sender_today = sender.clone();
+        today_btn.connect_clicked(move |_| sender_today.input(AppMsg::ShiftPlannerWeek(0)));
+    }
+    nav.append(&today_btn);
+    content.append(&nav);
+
+    // ── Week grid ─────────────────────────────────────────────────────────────
+    let grid = gtk::Grid::new();
+    grid.set_column_spacing(ROW_SPACING as u32);
+    grid.set_row_spacing(ROW_SPACING as u32);
+    grid.set_column_homogeneous(true);
+
+    for (col, day) in week_days(monday).into_iter().enumerate() {
+        let col = col as i32 + 1;
+        let header = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        header.set_halign(gtk::Align::Center);
+        let day_label = gtk::Label::new(Some(&i18n::fmt_day(day)));
+        day_label.add_css_class("heading");
+        header.append(&day_label);
+        if plan.day_cookable(&dm, day) {
+            let check = gtk::Label::new(Some("✓"));
+            check.add_css_class("success");
+            check.set_tooltip_text(Some(s.day_cookable));
+            header.append(&check);
+        }
+        grid.attach(&header, col, 0, 1, 1);
+
+        for (row, meal) in Meal::ALL.into_iter().enumerate() {
+            let cell = build_cell(&dm, plan, day, meal, &titles, sender);
+            grid.attach(&cell, col, row as i32 + 1, 1, 1);
+        }
+    }
+    for (row, meal) in Meal::ALL.into_iter().enumerate() {
+        let label = gtk::Label::new(Some(i18n::meal_label(meal)));
+        label.add_css_class("dim-label");
+        label.set_valign(gtk::Align::Start);
+        label.set_xalign(0.0);
+        grid.attach(&label, 0, row as i32 + 1, 1, 1);
+    }
+    content.append(&grid);
+
+    content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+    // ── Missing for this week ─────────────────────────────────────────────────
+    let missing_header = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    let missing_label = gtk::Label::new(Some(s.missing_this_week));
+    missing_label.add_css_class("heading");
+    missing_label.set_halign(gtk::Align::Start);
+    missing_label.set_hexpand(true);
+    missing_header.append(&missing_label);
+
+    let basket_btn = gtk::Button::with_label(s.add_week_to_basket);
+    basket_btn.add_css_class("flat");
+    basket_btn.set_sensitive(!plan.week_recipes(monday).is_empty());
+    {
+        let sender_basket = sender.clone();
+        basket_btn.connect_clicked(move |_| sender_basket.input(AppMsg::AddWeekToBasket));
+    }
+    missing_header.append(&basket_btn);
+    content.append(&missing_header);
+
+    let missing = plan.missing_for_week(&dm, monday);
+    if missing.is_empty() {
+        let done = gtk::Label::new(Some(s.week_all_covered));
+        done.add_css_class("success");
+        done.set_halign(gtk::Align::Start);
+        content.append(&done);
+        return;
+    }
+    for item in missing {
+        let text = match item.quantity {
+            Some(q) => {
+                let (q, unit) = crate::units::localize(q, &item.quantity_type);
+                if unit.is_empty() {
+                    format!("• {} {}", format_amount(q), item.ingredient)
+                } else {
+                    format!("• {} {} {}", format_amount(q), unit, item.ingredient)
+                }
+            }
+            None => format!("• {}", item.ingredient),
+        };
+        let label = gtk::Label::new(Some(&text));
+        label.set_halign(gtk::Align::Start);
+        label.set_tooltip_text(Some(&item.recipes.join(", ")));
+        content.append(&label);
+    }
+}
+
+/// One day/meal cell: planned recipes with coverage, a "+" picker, and a drop
+/// target for recipes dragged from the list.
+fn build_cell(
+    dm: &DataManager,
+    plan: &MealPlan,
+    day: NaiveDate,
+    meal: Meal,
+    titles: &Rc<Vec<String>>,
+    sender: &ComponentSender<App>,
+) -> gtk::Box {
+    let s = i18n::strings();
+    let cell = gtk::Box::new(gtk::Orientation::Vertical, 2);
+    cell.add_css_class("card");
+    cell.set_size_request(-1, 48);
+
+    for title in plan.recipes_for(day, meal) {
+        let chip = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        chip.set_margin_start(4);
+        chip.set_margin_top(2);
+
+        match dm.get_recipe(title) {
+            Some(recipe) => {
+                let cov = recipe_coverage(dm, recipe, 1.0);
+                let tooltip =
+                    i18n::fmt_required_tooltip(cov.covered, cov.partial, cov.required_total);
+                chip.append(&crate::utils::build_coverage_pie(
+                    cov.covered_ratio(),
+                    cov.partial_ratio(),
+                    &tooltip,
+                ));
+            }
+            None => {
+                let unknown = gtk::Label::new(Some("?"));
+                unknown.add_css_class("dim-label");
+                chip.append(&unknown);
+            }
+        }
+
+        let label = gtk::Label::new(Some(title));
+        label.set_hexpand(true);
+        label.set_xalign(0.0);
+        label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        label.set_tooltip_text(Some(title));
+        chip.append(&label);
+
+        let remove_btn = gtk::Button::from_icon_name("window-close-symbolic");
+        remove_btn.add_css_class("flat");
+        remove_btn.add_css_class("circular");
+        remove_btn.set_tooltip_text(Some(s.remove_from_plan));
+        {
+            let sender_remove = sender.clone();
+            let title = title.to_string();
+            remove_btn.connect_clicked(move |_| {
+                sender_remove.input(AppMsg::UnplanRecipe { date: day, meal, title: title.clone() });
+            });
+        }
+        chip.append(&remove_btn);
+        cell.append(&chip);
+    }
+
+    // Picker: the popover is only built when opened
+    let add_btn = gtk::MenuButton::new();
+    add_btn.set_icon_name("list-add-symbolic");
+    add_btn.add_css_class("flat");
+    add_btn.set_halign(gtk::Align::Center);
+    add_btn.set_tooltip_text(Some(s.add_to_plan));
+    {
+        let sender_pick = sender.clone();
+        let titles = Rc::clone(titles);
+        add_btn.set_create_popup_func(move |btn| {
+            let popover = gtk::Popover::new();
+            let scroll = gtk::ScrolledWindow::new();
+            scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+            scroll.set_max_content_height(320);
+            scroll.set_propagate_natural_height(true);
+            let list = gtk::ListBox::new();
+            list.set_selection_mode(gtk::SelectionMode::None);
+            for title in titles.iter() {
+                let row = gtk::ListBoxRow::new();
+                row.set_widget_name(title);
+                let label = gtk::Label::new(Some(title));
+                label.set_xalign(0.0);
+                label.set_margin_all(4);
+                row.set_child(Some(&label));
+                list.append(&row);
+            }
+            let sender_row = sender_pick.clone();
+            let popover_clone = popover.clone();
+            list.connect_row_activated(move |_, row| {
+                sender_row.input(AppMsg::PlanRecipe {
+                    date: day,
+                    meal,
+                    title: row.widget_name().to_string(),
+                });
+                popover_clone.popdown();
+            });
+            scroll.set_child(Some(&list));
+            popover.set_child(Some(&scroll));
+            btn.set_popover(Some(&popover));
+        });
+    }
+    cell.append(&add_btn);
+
+    // Drop target for rows dragged from a recipe list
+    let drop = gtk::DropTarget::new(gtk::glib::Type::STRING, gtk::gdk::DragAction::COPY);
+    {
+        let sender_drop = sender.clone();
+        drop.connect_drop(move |_, value, _, _| match value.get::<String>() {
+            Ok(title) => {
+                sender_drop.input(AppMsg::PlanRecipe { date: day, meal, title });
+                true
+            }
+            Err(_) => false,
+        });
+    }
+    cell.add_controller(drop);
+
+    cell
+}
@@ -0,0 +1,433 @@
+/// Printing and PDF export of recipes.
+///
+/// A recipe, or a cookbook of several, is first turned into [`PrintBlock`]s:
+/// plain data that can be tested without GTK. [`paginate`] splits the blocks
+/// into pages given a way to measure them, and [`draw_page`] draws a page with
+/// Pango on any cairo context, so printing (through `gtk::PrintOperation`) and
+/// PDF export (a cairo PDF surface) share one layout.
+use crate::i18n;
+use crate::markdown::{escape, inline_markup, parse_blocks, Block};
+use crate::recipes::ingredient_text;
+use janus_engine::{DataManager, Recipe};
+use relm4::gtk;
+use relm4::gtk::prelude::*;
+use relm4::gtk::{cairo, pango};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// A4, in points.
+pub const PDF_PAGE_SIZE: (f64, f64) = (595.276, 841.89);
+/// Margin around the PDF page content, in points (20 mm).
+const PDF_MARGIN: f64 = 56.7;
+/// Room kept at the bottom of each page for the page number.
+const FOOTER_HEIGHT: f64 = 24.0;
+/// Indent of list items and quotes.
+const INDENT: f64 = 18.0;
+
+/// One piece of a printed page. Text is Pango markup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrintBlock {
+    /// A recipe's title.
+    Title(String),
+    /// A line of times, servings or tags under the title.
+    Meta(String),
+    Heading(String),
+    /// A list line: its bullet or number, and its text.
+    Item { marker: String, text: String },
+    Paragraph(String),
+    Quote(String),
+    /// Preformatted text.
+    Code(String),
+    Rule,
+    /// Title of a cookbook, at the top of its contents.
+    BookTitle(String),
+    /// A line of a cookbook's table of contents; `page` is filled in by
+    /// [`number_contents`] once the pages are known.
+    Contents { title: String, page: usize },
+    /// Start the next block on a new page.
+    PageBreak,
+}
+
+impl PrintBlock {
+    /// Space left above the block when it doesn't start a page.
+    fn gap_before(&self) -> f64 {
+        match self {
+            PrintBlock::Title(_) | PrintBlock::BookTitle(_) | PrintBlock::Heading(_) => 14.0,
+            PrintBlock::Item { .. } | PrintBlock::Meta(_) | PrintBlock::Contents { .. } => 3.0,
+            _ => 8.0,
+        }
+    }
+
+    /// Headings are moved to the next page rather than left at the bottom
+    /// without what follows them.
+    fn keeps_with_next(&self) -> bool {
+        matches!(self, PrintBlock::Title(_) | PrintBlock::BookTitle(_) | PrintBlock::Heading(_))
+    }
+}
+
+/// Lay out `recipe` for printing at `servings` (`None` prints it as written).
+pub fn recipe_blocks(dm: &DataManager, recipe: &Recipe, servings: Option<u32>) -> Vec<PrintBlock> {
+    let s = i18n::strings();
+    let base = recipe.servings.unwrap_or(1).max(1);
+    let target = servings.unwrap_or(base).max(1);
+    let factor = target as f64 / base as f64;
+
+    let mut blocks = vec![PrintBlock::Title(escape(&recipe.title))];
+
+    let mut meta = Vec::new();
+    if let Some(prep) = recipe.prep_time {
+        meta.push(i18n::fmt_prep_time(prep));
+    }
+    if let Some(down) = recipe.downtime {
+        meta.push(i18n::fmt_cook_time(down));
+    }
+    if recipe.servings.is_some() {
+        meta.push(i18n::fmt_servings(target));
+    } else if target > 1 {
+        meta.push(format!("× {}", target));
+    }
+    if !meta.is_empty() {
+        blocks.push(PrintBlock::Meta(escape(&meta.join("   "))));
+    }
+    if let Some(tags) = recipe.tags.as_ref().filter(|t| !t.is_empty()) {
+        blocks.push(PrintBlock::Meta(escape(&i18n::fmt_tags(&tags.join(", ")))));
+    }
+
+    if !recipe.ingredients.is_empty() {
+        blocks.push(PrintBlock::Heading(escape(s.ingredients_heading)));
+        for ing in &recipe.ingredients {
+            blocks.push(PrintBlock::Item {
+                marker: "•".to_string(),
+                text: escape(&ingredient_text(dm, ing, factor)),
+            });
+        }
+    }
+
+    if !recipe.instructions.trim().is_empty() {
+        blocks.push(PrintBlock::Heading(escape(s.instructions_heading)));
+        instruction_blocks(&parse_blocks(&recipe.instructions), false, &mut blocks);
+    }
+    blocks
+}
+
+/// A cookbook of `recipes`: a contents page, then each recipe on a new page.
+pub fn cookbook_blocks(dm: &DataManager, title: &str, recipes: &[&Recipe]) -> Vec<PrintBlock> {
+    let s = i18n::strings();
+    let mut blocks = vec![
+        PrintBlock::BookTitle(escape(title)),
+        PrintBlock::Heading(escape(s.contents_heading)),
+    ];
+    for recipe in recipes {
+        blocks.push(PrintBlock::Contents { title: escape(&recipe.title), page: 0 });
+    }
+    for recipe in recipes {
+        blocks.push(PrintBlock::PageBreak);
+        blocks.extend(recipe_blocks(dm, recipe, None));
+    }
+    blocks
+}
+
+/// Turn Markdown instructions into print blocks. Quoted blocks become quotes.
+fn instruction_blocks(markdown: &[Block], quoted: bool, out: &mut Vec<PrintBlock>) {
+    let text = |markup: String| {
+        if quoted {
+            PrintBlock::Quote(markup)
+        } else {
+            PrintBlock::Paragraph(markup)
+        }
+    };
+    for block in markdown {
+        match block {
+            Block::Heading { text: heading, .. } if !quoted => {
+                out.push(PrintBlock::Heading(pango_markup(heading)));
+            }
+            Block::Heading { text: heading, .. } => {
+                out.push(text(format!("<b>{}</b>", pango_markup(heading))));
+            }
+            Block::Paragraph(paragraph) => out.push(text(pango_markup(paragraph))),
+            Block::List { ordered, start, items } => {
+                for (n, item) in items.iter().enumerate() {
+                    let marker = if *ordered {
+                        format!("{}.", *start as usize + n)
+                    } else {
+                        "•".to_string()
+                    };
+                    if quoted {
+                        out.push(PrintBlock::Quote(format!("{} {}", marker, pango_markup(item))));
+                    } else {
+                        out.push(PrintBlock::Item { marker, text: pango_markup(item) });
+                    }
+                }
+            }
+            Block::Quote(inner) => instruction_blocks(inner, true, out),
+            Block::Code(code) => out.push(PrintBlock::Code(escape(code))),
+            // Images are left out of print; their description stands in
+            Block::Image { alt, .. } if !alt.is_empty() => {
+                out.push(text(format!("<i>{}</i>", escape(alt))));
+            }
+            Block::Image { .. } => {}
+            Block::Rule => out.push(PrintBlock::Rule),
+        }
+    }
+}
+
+/// Pango markup for inline Markdown. Pango has no links, so only their text is kept.
+fn pango_markup(text: &str) -> String {
+    let markup = inline_markup(text, &[]);
+    let mut out = String::with_capacity(markup.len());
+    let mut rest = markup.as_str();
+    while let Some(start) = rest.find("<a ") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => rest = "",
+        }
+    }
+    out.push_str(rest);
+    out.replace("</a>", "")
+}
+
+/// Split `blocks` into pages of `page_height`, given each block's height.
+/// Each page lists its blocks as (index into `blocks`, top offset).
+pub fn paginate(
+    blocks: &[PrintBlock],
+    page_height: f64,
+    mut measure: impl FnMut(&PrintBlock) -> f64,
+) -> Vec<Vec<(usize, f64)>> {
+    let heights: Vec<f64> = blocks.iter().map(&mut measure).collect();
+    let mut pages = Vec::new();
+    let mut page: Vec<(usize, f64)> = Vec::new();
+    let mut y = 0.0;
+
+    for (i, block) in blocks.iter().enumerate() {
+        if *block == PrintBlock::PageBreak {
+            if !page.is_empty() {
+                pages.push(std::mem::take(&mut page));
+                y = 0.0;
+            }
+            continue;
+        }
+        let mut gap = if page.is_empty() { 0.0 } else { block.gap_before() };
+        let mut needed = heights[i];
+        if block.keeps_with_next() {
+            if let Some(next) = blocks.get(i + 1).filter(|b| **b != PrintBlock::PageBreak) {
+                needed += next.gap_before() + heights[i + 1];
+            }
+        }
+        if !page.is_empty() && y + gap + needed > page_height {
+            pages.push(std::mem::take(&mut page));
+            y = 0.0;
+            gap = 0.0;
+        }
+        page.push((i, y + gap));
+        y += gap + heights[i];
+    }
+    if !page.is_empty() || pages.is_empty() {
+        pages.push(page);
+    }
+    pages
+}
+
+/// Fill in the page numbers of a cookbook's contents: the n-th contents line
+/// gets the (1-based) page of the n-th recipe title.
+pub fn number_contents(blocks: &mut [PrintBlock], pages: &[Vec<(usize, f64)>]) {
+    let mut title_pages = Vec::new();
+    for (n, page) in pages.iter().enumerate() {
+        for &(i, _) in page {
+            if matches!(blocks[i], PrintBlock::Title(_)) {
+                title_pages.push(n + 1);
+            }
+        }
+    }
+    let contents = blocks.iter_mut().filter_map(|b| match b {
+        PrintBlock::Contents { page, .. } => Some(page),
+        _ => None,
+    });
+    for (page, number) in contents.zip(title_pages) {
+        *page = number;
+    }
+}
+
+/// Split `blocks` into pages of the given content size and number the
+/// contents, measuring text with Pango on `cr`.
+pub fn layout_pages(
+    cr: &cairo::Context,
+    blocks: &mut [PrintBlock],
+    width: f64,
+    height: f64,
+) -> Vec<Vec<(usize, f64)>> {
+    let pages = paginate(blocks, height - FOOTER_HEIGHT, |b| block_height(cr, b, width));
+    // Contents lines are as tall with or without their numbers, so numbering
+    // doesn't move anything
+    number_contents(blocks, &pages);
+    pages
+}
+
+fn font(block: &PrintBlock) -> &'static str {
+    match block {
+        PrintBlock::BookTitle(_) => "Sans Bold 26",
+        PrintBlock::Title(_) => "Sans Bold 20",
+        PrintBlock::Heading(_) => "Sans Bold 13",
+        PrintBlock::Meta(_) => "Sans 10",
+        PrintBlock::Quote(_) => "Sans Italic 11",
+        PrintBlock::Code(_) => "Monospace 10",
+        PrintBlock::Contents { .. } => "Sans 12",
+        _ => "Sans 11",
+    }
+}
+
+/// A wrapping Pango layout of `markup` in `block`'s font.
+fn text_layout(cr: &cairo::Context, block: &PrintBlock, markup: &str, width: f64) -> pango::Layout {
+    let layout = pangocairo::functions::create_layout(cr);
+    layout.set_font_description(Some(&pango::FontDescription::from_string(font(block))));
+    layout.set_width((width * pango::SCALE as f64) as i32);
+    layout.set_wrap(pango::WrapMode::WordChar);
+    layout.set_markup(markup);
+    layout
+}
+
+/// The block's text layout and how far it is indented.
+fn block_layout(cr: &cairo::Context, block: &PrintBlock, width: f64) -> Option<(pango::Layout, f64)> {
+    let (markup, indent) = match block {
+        PrintBlock::Title(t)
+        | PrintBlock::BookTitle(t)
+        | PrintBlock::Heading(t)
+        | PrintBlock::Meta(t)
+        | PrintBlock::Paragraph(t) => (t.as_str(), 0.0),
+        PrintBlock::Item { text, .. } | PrintBlock::Quote(text) | PrintBlock::Code(text) => {
+            (text.as_str(), INDENT)
+        }
+        PrintBlock::Contents { title, .. } => (title.as_str(), 0.0),
+        PrintBlock::Rule | PrintBlock::PageBreak => return None,
+    };
+    Some((text_layout(cr, block, markup, width - indent), indent))
+}
+
+fn block_height(cr: &cairo::Context, block: &PrintBlock, width: f64) -> f64 {
+    match block_layout(cr, block, width) {
+        Some((layout, _)) => layout.pixel_size().1 as f64,
+        None if *block == PrintBlock::Rule => 1.0,
+        None => 0.0,
+    }
+}
+
+/// Draw one page (`number` of `total`) of content `width` × `height`, with its
+/// top-left corner at the origin of `cr`.
+pub fn draw_page(
+    cr: &cairo::Context,
+    blocks: &[PrintBlock],
+    page: &[(usize, f64)],
+    width: f64,
+    height: f64,
+    number: usize,
+    total: usize,
+) {
+    for &(i, y) in page {
+        let block = &blocks[i];
+        let gray = matches!(block, PrintBlock::Meta(_) | PrintBlock::Quote(_));
+        if gray {
+            cr.set_source_rgb(0.35, 0.35, 0.35);
+        } else {
+            cr.set_source_rgb(0.0, 0.0, 0.0);
+        }
+
+        if *block == PrintBlock::Rule {
+            cr.rectangle(0.0, y, width, 0.5);
+            let _ = cr.fill();
+            continue;
+        }
+        let Some((layout, indent)) = block_layout(cr, block, width) else {
+            continue;
+        };
+        match block {
+            PrintBlock::Item { marker, .. } => {
+                let marker = text_layout(cr, block, &escape(marker), INDENT);
+                cr.move_to(0.0, y);
+                pangocairo::functions::show_layout(cr, &marker);
+            }
+            PrintBlock::Quote(_) => {
+                cr.rectangle(INDENT / 3.0, y, 1.5, layout.pixel_size().1 as f64);
+                let _ = cr.fill();
+            }
+            PrintBlock::Contents { page, .. } => {
+                let number = text_layout(cr, block, &page.to_string(), width);
+                number.set_alignment(pango::Alignment::Right);
+                cr.move_to(0.0, y);
+                pangocairo::functions::show_layout(cr, &number);
+            }
+            _ => {}
+        }
+        cr.move_to(indent, y);
+        pangocairo::functions::show_layout(cr, &layout);
+    }
+
+    // Page number, centred in the footer
+    if total > 1 {
+        cr.set_source_rgb(0.35, 0.35, 0.35);
+        let footer = text_layout(cr, &PrintBlock::Meta(String::new()), &number.to_string(), width);
+        footer.set_alignment(pango::Alignment::Center);
+        cr.move_to(0.0, height - footer.pixel_size().1 as f64);
+        pangocairo::functions::show_layout(cr, &footer);
+    }
+}
+
+/// Write `blocks` to an A4 PDF at `path`.
+pub fn write_pdf(path: &Path, mut blocks: Vec<PrintBlock>) -> Result<(), String> {
+    let error = |e: cairo::Error| format!("{}: {}", path.display(), e);
+    let (page_width, page_height) = PDF_PAGE_SIZE;
+    let surface = cairo::PdfSurface::new(page_width, page_height, path).map_err(error)?;
+    let cr = cairo::Context::new(&surface).map_err(error)?;
+    let width = page_width - 2.0 * PDF_MARGIN;
+    let height = page_height - 2.0 * PDF_MARGIN;
+
+    let pages = layout_pages(&cr, &mut blocks, width, height);
+    for (n, page) in pages.iter().enumerate() {
+        cr.save().map_err(error)?;
+        cr.translate(PDF_MARGIN, PDF_MARGIN);
+        draw_page(&cr, &blocks, page, width, height, n + 1, pages.len());
+        cr.restore().map_err(error)?;
+        cr.show_page().map_err(error)?;
+    }
+    drop(cr);
+    surface.finish();
+    surface.status().map_err(error)
+}
+
+/// Print `blocks` through the system print dialog. `job_name` names the print job.
+pub fn print_blocks(
+    parent: &impl IsA<gtk::Window>,
+    job_name: &str,
+    blocks: Vec<PrintBlock>,
+) -> Result<(), String> {
+    let op = gtk::PrintOperation::new();
+    op.set_job_name(job_name);
+    op.set_unit(gtk::Unit::Points);
+    op.set_embed_page_setup(true);
+
+    let blocks = Rc::new(RefCell::new(blocks));
+    let pages: Rc<RefCell<Vec<Vec<(usize, f64)>>>> = Rc::new(RefCell::new(Vec::new()));
+    {
+        let blocks = Rc::clone(&blocks);
+        let pages = Rc::clone(&pages);
+        op.connect_begin_print(move |op, ctx| {
+            let cr = ctx.cairo_context();
+            let laid_out =
+                layout_pages(&cr, &mut blocks.borrow_mut(), ctx.width(), ctx.height());
+            op.set_n_pages(laid_out.len() as i32);
+            *pages.borrow_mut() = laid_out;
+        });
+    }
+    op.connect_draw_page(move |_, ctx, n| {
+        let pages = pages.borrow();
+        if let Some(page) = pages.get(n as usize) {
+            let cr = ctx.cairo_context();
+            let total = pages.len();
+            draw_page(&cr, &blocks.borrow(), page, ctx.width(), ctx.height(), n as usize + 1, total);
+        }
+    });
+
+    op.run(gtk::PrintOperationAction::PrintDialog, Some(parent))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
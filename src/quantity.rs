@@ -0,0 +1,322 @@
+/// Parsing of free-text quantities such as "2 kg", "1½ dl", "1/2 tsp" or "2-3 stk".
+///
+/// Used by the recipe editor to split the quantity entry into the structured
+/// `RecipeIngredient::quantity` / `quantity_type` fields, and by anything that needs
+/// the numeric value of a stored quantity string.
+use crate::units::{preferred_system, unit_in_system};
+use std::fmt;
+
+/// The unit lookups the parser builds on; the registry itself lives in `units`.
+pub use crate::units::{canonical_unit, convert, unit_size, Dimension};
+
+/// Numeric part of a quantity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Amount {
+    Single(f64),
+    /// Inclusive range, e.g. "2-3".
+    Range(f64, f64),
+}
+
+impl Amount {
+    /// Representative single value (midpoint for ranges).
+    pub fn value(&self) -> f64 {
+        match *self {
+            Amount::Single(v) => v,
+            Amount::Range(lo, hi) => (lo + hi) / 2.0,
+        }
+    }
+
+    /// Upper bound (the amount to have on hand to be safe).
+    pub fn max(&self) -> f64 {
+        match *self {
+            Amount::Single(v) => v,
+            Amount::Range(_, hi) => hi,
+        }
+    }
+}
+
+/// Result of parsing a quantity entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedQuantity {
+    pub amount: Option<Amount>,
+    /// Normalised text for `RecipeIngredient::quantity` ("1½" → "1 1/2", "2,5" → "2.5").
+    pub quantity: Option<String>,
+    /// Canonical unit for `RecipeIngredient::quantity_type`.
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuantityError {
+    InvalidNumber(String),
+    UnknownUnit(String),
+}
+
+impl fmt::Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantityError::InvalidNumber(s) => write!(f, "invalid number: {}", s),
+            QuantityError::UnknownUnit(s) => write!(f, "unknown unit: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for QuantityError {}
+
+/// Parse a quantity entry using only the built-in units.
+pub fn parse_quantity(input: &str) -> Result<ParsedQuantity, QuantityError> {
+    parse_quantity_with(input, &[])
+}
+
+/// Parse a quantity entry. `extra_units` are additional units accepted verbatim
+/// (e.g. units already used elsewhere in the data directory, like "fed").
+pub fn parse_quantity_with(
+    input: &str,
+    extra_units: &[String],
+) -> Result<ParsedQuantity, QuantityError> {
+    let text = normalise(input);
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '/' | '-' | ' ')))
+        .unwrap_or(text.len());
+    let (num_part, unit_part) = text.split_at(split);
+    let num_part = num_part.trim();
+    let unit_part = unit_part.trim();
+
+    let (amount, quantity) = if num_part.is_empty() {
+        (None, None)
+    } else {
+        let amount = parse_amount(num_part)
+            .ok_or_else(|| QuantityError::InvalidNumber(num_part.to_string()))?;
+        (Some(amount), Some(compact_amount_text(num_part)))
+    };
+
+    let unit = if unit_part.is_empty() {
+        None
+    } else if let Some(canonical) = canonical_unit(unit_part) {
+        Some(canonical.to_string())
+    } else if let Some(extra) = extra_units
+        .iter()
+        .find(|u| !u.is_empty() && u.to_lowercase() == unit_part.to_lowercase())
+    {
+        Some(extra.clone())
+    } else {
+        return Err(QuantityError::UnknownUnit(unit_part.to_string()));
+    };
+
+    Ok(ParsedQuantity { amount, quantity, unit })
+}
+
+/// Parse the numeric part of a stored quantity string: "2", "2.0", "0,5", "1/2",
+/// "1 1/2", "1½", "2-3". Returns `None` if it is not a number.
+pub fn parse_amount(text: &str) -> Option<Amount> {
+    let text = normalise(text);
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if let Some((lo, hi)) = text.split_once('-') {
+        let lo = parse_number(lo.trim())?;
+        let hi = parse_number(hi.trim())?;
+        if hi < lo {
+            return None;
+        }
+        return Some(Amount::Range(lo, hi));
+    }
+    parse_number(text).map(Amount::Single)
+}
+
+/// "2", "2.5", "1/2", "1 1/2".
+fn parse_number(text: &str) -> Option<f64> {
+    let mut parts = text.split_whitespace();
+    let first = parts.next()?;
+    let second = parts.next();
+    if parts.next().is_some() {
+        return None;
+    }
+    match second {
+        Some(frac) => {
+            let whole: u32 = first.parse().ok()?;
+            let frac = parse_fraction(frac)?;
+            (frac < 1.0).then_some(whole as f64 + frac)
+        }
+        None if first.contains('/') => parse_fraction(first),
+        None => first.parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0),
+    }
+}
+
+fn parse_fraction(text: &str) -> Option<f64> {
+    let (num, den) = text.split_once('/')?;
+    let num: u32 = num.parse().ok()?;
+    let den: u32 = den.parse().ok()?;
+    (den != 0).then(|| num as f64 / den as f64)
+}
+
+/// Replace Unicode fractions and dashes, and decimal commas between digits.
+fn normalise(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() + 4);
+    let chars: Vec<char> = input.trim().chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        let frac = match c {
+            '½' => Some("1/2"),
+            '⅓' => Some("1/3"),
+            '⅔' => Some("2/3"),
+            '¼' => Some("1/4"),
+            '¾' => Some("3/4"),
+            '⅛' => Some("1/8"),
+            '⅜' => Some("3/8"),
+            '⅝' => Some("5/8"),
+            '⅞' => Some("7/8"),
+            _ => None,
+        };
+        if let Some(f) = frac {
+            if out.chars().last().is_some_and(|p| p.is_ascii_digit()) {
+                out.push(' ');
+            }
+            out.push_str(f);
+            continue;
+        }
+        match c {
+            '–' | '—' => out.push('-'),
+            ',' if i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()) =>
+            {
+                out.push('.')
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Collapse whitespace and remove spaces around a range dash: " 2 - 3 " → "2-3".
+fn compact_amount_text(text: &str) -> String {
+    let joined = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    joined.replace(" - ", "-").replace(" -", "-").replace("- ", "-")
+}
+
+// ── Scaling ───────────────────────────────────────────────────────────────────
+
+/// How finely a scaled amount is rounded, depending on its unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Rounding {
+    /// Whole items: nearest ½ (you can't buy 1.37 eggs).
+    Count,
+    /// Spoons and pinches: nearest ¼.
+    Spoon,
+    /// Grams / millilitres: whole numbers, coarser for big amounts.
+    Fine,
+    /// Kilograms / litres / decilitres, US units and unknown units: two decimals.
+    Decimal,
+}
+
+fn rounding_for(unit: Option<&str>) -> Rounding {
+    let Some(unit) = unit.filter(|u| !u.trim().is_empty()) else {
+        return Rounding::Count;
+    };
+    match canonical_unit(unit) {
+        Some("pcs" | "stk" | "can" | "dåse" | "pack" | "pakke" | "bag" | "pose" | "bottle"
+            | "flaske" | "carton" | "karton") => Rounding::Count,
+        Some("tsp" | "tbsp" | "tsk" | "spsk" | "pinch" | "nip" | "cup") => Rounding::Spoon,
+        Some("g" | "ml") => Rounding::Fine,
+        _ => Rounding::Decimal,
+    }
+}
+
+/// Round `value` the way a cook would write it for `unit`.
+pub fn round_for_unit(value: f64, unit: Option<&str>) -> f64 {
+    let step = |v: f64, s: f64| ((v / s).round() * s).max(s);
+    match rounding_for(unit) {
+        Rounding::Count => {
+            if value < 0.5 {
+                step(value, 0.25)
+            } else {
+                step(value, 0.5)
+            }
+        }
+        Rounding::Spoon => step(value, 0.25),
+        Rounding::Fine => {
+            if value < 10.0 {
+                step(value, 0.5)
+            } else if value < 100.0 {
+                step(value, 1.0)
+            } else if value < 1000.0 {
+                step(value, 5.0)
+            } else {
+                step(value, 10.0)
+            }
+        }
+        Rounding::Decimal => step(value, 0.01),
+    }
+}
+
+/// Format a number for display, using ½ ¼ ¾ ⅓ ⅔ for common fractions.
+pub fn format_amount(value: f64) -> String {
+    let whole = value.trunc();
+    let frac = value - whole;
+    let glyph = [
+        (0.25, "¼"),
+        (1.0 / 3.0, "⅓"),
+        (0.5, "½"),
+        (2.0 / 3.0, "⅔"),
+        (0.75, "¾"),
+    ]
+    .iter()
+    .find(|(f, _)| (frac - f).abs() < 0.005)
+    .map(|(_, g)| *g);
+    match glyph {
+        Some(g) if whole == 0.0 => g.to_string(),
+        Some(g) => format!("{}{}", whole as i64, g),
+        None if frac.abs() < 0.005 => format!("{}", whole as i64),
+        None if (1.0 - frac).abs() < 0.005 => format!("{}", whole as i64 + 1),
+        None => {
+            let s = format!("{:.2}", value);
+            s.trim_end_matches('0').trim_end_matches('.').to_string()
+        }
+    }
+}
+
+/// Scale a stored quantity string by `factor` and format it for display.
+///
+/// Non-numeric quantities ("a handful") are returned unchanged.
+pub fn scale_quantity(quantity: &str, factor: f64, unit: Option<&str>) -> String {
+    match parse_amount(quantity) {
+        Some(Amount::Single(v)) => format_amount(round_for_unit(v * factor, unit)),
+        Some(Amount::Range(lo, hi)) => format!(
+            "{}-{}",
+            format_amount(round_for_unit(lo * factor, unit)),
+            format_amount(round_for_unit(hi * factor, unit))
+        ),
+        None => quantity.to_string(),
+    }
+}
+
+/// Stored quantity and unit as shown in the recipe view: scaled by `factor` and
+/// converted to the preferred measurement system. Returns `(quantity, unit)`;
+/// quantities that need neither are returned exactly as written.
+pub fn display_quantity(
+    quantity: &str,
+    unit: Option<&str>,
+    factor: f64,
+) -> (String, Option<String>) {
+    let as_written = (quantity.to_string(), unit.map(str::to_string));
+    let Some(amount) = parse_amount(quantity) else {
+        return as_written;
+    };
+    let target = unit.and_then(|u| unit_in_system(amount.max() * factor, u, preferred_system()));
+    let Some(target) = target else {
+        if factor == 1.0 {
+            return as_written;
+        }
+        return (scale_quantity(quantity, factor, unit), unit.map(str::to_string));
+    };
+    let from = unit.unwrap_or_default();
+    let show = |v: f64| {
+        let converted = convert(v * factor, from, target).unwrap_or(v * factor);
+        format_amount(round_for_unit(converted, Some(target)))
+    };
+    let text = match amount {
+        Amount::Single(v) => show(v),
+        Amount::Range(lo, hi) => format!("{}-{}", show(lo), show(hi)),
+    };
+    (text, Some(target.to_string()))
+}
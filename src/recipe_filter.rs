@@ -0,0 +1,176 @@
+/// Filters of the Recipes tab's filter panel: tags, time, servings and what the
+/// pantry covers.
+///
+/// Filters combine with each other and with the search text. A recipe without
+/// the field a filter looks at (no prep time, no servings) doesn't pass that
+/// filter. Pantry coverage is the quantity-aware [`Coverage`] the list shows.
+use crate::coverage::Coverage;
+use janus_engine::Recipe;
+
+/// Choices of the "max prep time" and "max total time" menus, in minutes.
+pub const TIME_CHOICES: [u32; 7] = [10, 15, 20, 30, 45, 60, 90];
+
+/// Largest serving count offered in the servings range menus.
+pub const MAX_SERVINGS_CHOICE: u32 = 12;
+
+/// Highest "missing at most" value on the slider; one step further means any.
+pub const MAX_MISSING_CHOICE: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RecipeFilter {
+    /// Recipes with any of these tags.
+    pub tags: Vec<String>,
+    /// Minutes.
+    pub max_prep: Option<u32>,
+    /// Prep time plus downtime, in minutes.
+    pub max_total: Option<u32>,
+    pub min_servings: Option<u32>,
+    pub max_servings: Option<u32>,
+    /// Only recipes whose required ingredients the pantry fully covers.
+    pub cookable_only: bool,
+    /// Required ingredients the pantry may not (fully) cover.
+    pub max_missing: Option<usize>,
+}
+
+/// One change made in the filter panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterChange {
+    Tag { tag: String, selected: bool },
+    MaxPrep(Option<u32>),
+    MaxTotal(Option<u32>),
+    MinServings(Option<u32>),
+    MaxServings(Option<u32>),
+    CookableOnly(bool),
+    MaxMissing(Option<usize>),
+    Clear,
+}
+
+/// An active filter, as listed in the empty-state explanation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Criterion {
+    Search(String),
+    Tags(Vec<String>),
+    MaxPrep(u32),
+    MaxTotal(u32),
+    Servings(Option<u32>, Option<u32>),
+    Cookable,
+    MaxMissing(usize),
+}
+
+/// Prep time plus downtime; `None` when the recipe gives neither.
+pub fn total_time(recipe: &Recipe) -> Option<u32> {
+    match (recipe.prep_time, recipe.downtime) {
+        (None, None) => None,
+        (prep, down) => Some(prep.unwrap_or(0) + down.unwrap_or(0)),
+    }
+}
+
+pub fn has_tag(recipe: &Recipe, tag: &str) -> bool {
+    crate::tags::has_tag(&recipe.tags, tag)
+}
+
+/// Every tag used by `recipes`, sorted, with case variants merged.
+pub fn all_tags<'a>(recipes: impl IntoIterator<Item = &'a Recipe>) -> Vec<String> {
+    crate::tags::all_tags(recipes.into_iter().map(|r| &r.tags))
+}
+
+impl RecipeFilter {
+    pub fn is_active(&self) -> bool {
+        *self != RecipeFilter::default()
+    }
+
+    /// Number of active filters, for the filter button.
+    pub fn active_count(&self) -> usize {
+        self.criteria("").len()
+    }
+
+    pub fn apply(&mut self, change: FilterChange) {
+        match change {
+            FilterChange::Tag { tag, selected } => {
+                self.tags.retain(|t| !t.eq_ignore_ascii_case(&tag));
+                if selected {
+                    self.tags.push(tag);
+                    self.tags.sort_by_key(|t| t.to_lowercase());
+                }
+            }
+            FilterChange::MaxPrep(v) => self.max_prep = v,
+            FilterChange::MaxTotal(v) => self.max_total = v,
+            FilterChange::MinServings(v) => self.min_servings = v,
+            FilterChange::MaxServings(v) => self.max_servings = v,
+            FilterChange::CookableOnly(v) => self.cookable_only = v,
+            FilterChange::MaxMissing(v) => self.max_missing = v,
+            FilterChange::Clear => *self = RecipeFilter::default(),
+        }
+    }
+
+    /// Whether `recipe`, with pantry coverage `cov`, passes every filter.
+    pub fn matches(&self, recipe: &Recipe, cov: Coverage) -> bool {
+        self.criteria("").iter().all(|c| criterion_matches(c, recipe, cov))
+    }
+
+    /// The active filters, led by the search text when there is one.
+    pub fn criteria(&self, search: &str) -> Vec<Criterion> {
+        let mut criteria = Vec::new();
+        if !search.trim().is_empty() {
+            criteria.push(Criterion::Search(search.trim().to_string()));
+        }
+        if !self.tags.is_empty() {
+            criteria.push(Criterion::Tags(self.tags.clone()));
+        }
+        if let Some(max) = self.max_prep {
+            criteria.push(Criterion::MaxPrep(max));
+        }
+        if let Some(max) = self.max_total {
+            criteria.push(Criterion::MaxTotal(max));
+        }
+        if self.min_servings.is_some() || self.max_servings.is_some() {
+            criteria.push(Criterion::Servings(self.min_servings, self.max_servings));
+        }
+        if self.cookable_only {
+            criteria.push(Criterion::Cookable);
+        }
+        if let Some(max) = self.max_missing {
+            criteria.push(Criterion::MaxMissing(max));
+        }
+        criteria
+    }
+
+    /// Explain an empty result: each active criterion with how many of
+    /// `recipes` (all recipes, with their coverage) it lets through on its own.
+    /// `search_hits` is the number of search results.
+    pub fn explain(
+        &self,
+        search: &str,
+        search_hits: usize,
+        recipes: &[(&Recipe, Coverage)],
+    ) -> Vec<(Criterion, usize)> {
+        self.criteria(search)
+            .into_iter()
+            .map(|c| {
+                let count = match c {
+                    Criterion::Search(_) => search_hits,
+                    _ => recipes
+                        .iter()
+                        .filter(|(r, cov)| criterion_matches(&c, r, *cov))
+                        .count(),
+                };
+                (c, count)
+            })
+            .collect()
+    }
+}
+
+fn criterion_matches(criterion: &Criterion, recipe: &Recipe, cov: Coverage) -> bool {
+    match criterion {
+        // The search is applied by the engine
+        Criterion::Search(_) => true,
+        Criterion::Tags(tags) => tags.iter().any(|t| has_tag(recipe, t)),
+        Criterion::MaxPrep(max) => recipe.prep_time.is_some_and(|p| p <= *max),
+        Criterion::MaxTotal(max) => total_time(recipe).is_some_and(|t| t <= *max),
+        Criterion::Servings(min, max) => recipe.servings.is_some_and(|n| {
+            !min.is_some_and(|min| n < min) && !max.is_some_and(|max| n > max)
+        }),
+        Criterion::Cookable => cov.is_cookable(),
+        Criterion::MaxMissing(max) => cov.not_covered() <= *max,
+    }
+}
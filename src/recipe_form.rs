@@ -0,0 +1,184 @@
+/// The recipe dialog's form model.
+///
+/// The form holds every field as the text the dialog shows. Saving starts from
+/// the recipe being edited and only overwrites the fields whose text changed,
+/// so fields the dialog doesn't expose (slug, file stem, image, anything added
+/// by newer engine versions) and values it would normalise (an untouched
+/// "4 cloves") are kept exactly as stored.
+use crate::dialogs::IngredientInfo;
+use crate::quantity::parse_quantity_with;
+use janus_engine::{Recipe, RecipeIngredient};
+
+/// One ingredient row of the form.
+#[derive(Debug, Clone, Default)]
+pub struct IngredientLine {
+    /// Amount and unit as typed, e.g. "1½ dl".
+    pub quantity: String,
+    pub ingredient: String,
+    pub note: String,
+    pub optional: bool,
+    /// The stored ingredient this row was filled from (`None` for new rows).
+    pub loaded: Option<RecipeIngredient>,
+}
+
+impl IngredientLine {
+    pub fn from_ingredient(ing: &RecipeIngredient) -> Self {
+        IngredientLine {
+            quantity: quantity_text(ing),
+            ingredient: ing.ingredient.clone(),
+            note: ing.note.clone().unwrap_or_default(),
+            optional: ing.optional,
+            loaded: Some(ing.clone()),
+        }
+    }
+}
+
+/// Stored quantity and unit as the form shows them: "2.0 kg", "1/2", "".
+pub fn quantity_text(ing: &RecipeIngredient) -> String {
+    match (&ing.quantity, &ing.quantity_type) {
+        (Some(q), Some(u)) if !u.is_empty() => format!("{} {}", q, u),
+        (Some(q), _) => q.clone(),
+        (None, _) => String::new(),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecipeForm {
+    pub title: String,
+    pub prep_time: String,
+    pub downtime: String,
+    pub servings: String,
+    /// Comma-separated.
+    pub tags: String,
+    pub image: Option<String>,
+    pub ingredients: Vec<IngredientLine>,
+    pub instructions: String,
+}
+
+/// Why the form can't be saved yet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormError {
+    EmptyTitle,
+    /// The quantity of `ingredients[i]` doesn't parse.
+    Quantity(usize),
+}
+
+impl RecipeForm {
+    pub fn from_recipe(recipe: &Recipe) -> Self {
+        let number = |n: Option<u32>| n.map(|n| n.to_string()).unwrap_or_default();
+        RecipeForm {
+            title: recipe.title.clone(),
+            prep_time: number(recipe.prep_time),
+            downtime: number(recipe.downtime),
+            servings: number(recipe.servings),
+            tags: recipe.tags.as_ref().map(|t| t.join(", ")).unwrap_or_default(),
+            image: recipe.image.clone(),
+            ingredients: recipe.ingredients.iter().map(IngredientLine::from_ingredient).collect(),
+            instructions: recipe.instructions.clone(),
+        }
+    }
+
+    /// The recipe to save. `base` is the recipe being edited (`None` when adding
+    /// one); `known` resolves typed ingredient names to their canonical names and
+    /// `units` are extra units the quantity parser accepts.
+    pub fn to_recipe(
+        &self,
+        base: Option<&Recipe>,
+        known: &[IngredientInfo],
+        units: &[String],
+    ) -> Result<Recipe, FormError> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err(FormError::EmptyTitle);
+        }
+        let shown = base.map(RecipeForm::from_recipe).unwrap_or_default();
+        let mut recipe = match base {
+            Some(r) => r.clone(),
+            None => Recipe {
+                title: String::new(),
+                slug: String::new(),
+                file_stem: String::new(),
+                ingredients: Vec::new(),
+                prep_time: None,
+                downtime: None,
+                servings: None,
+                tags: None,
+                image: None,
+                instructions: String::new(),
+            },
+        };
+
+        if self.title != shown.title {
+            recipe.title = title.to_string();
+        }
+        if self.prep_time != shown.prep_time {
+            recipe.prep_time = self.prep_time.trim().parse().ok();
+        }
+        if self.downtime != shown.downtime {
+            recipe.downtime = self.downtime.trim().parse().ok();
+        }
+        if self.servings != shown.servings {
+            recipe.servings = self.servings.trim().parse().ok();
+        }
+        if self.tags != shown.tags {
+            let tags: Vec<String> = self
+                .tags
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+            recipe.tags = (!tags.is_empty()).then_some(tags);
+        }
+        recipe.image = self.image.clone();
+        if self.instructions != shown.instructions {
+            recipe.instructions = self.instructions.clone();
+        }
+
+        let mut ingredients = Vec::new();
+        for (i, line) in self.ingredients.iter().enumerate() {
+            if line.ingredient.trim().is_empty() {
+                continue;
+            }
+            ingredients.push(line_to_ingredient(line, known, units).map_err(|_| FormError::Quantity(i))?);
+        }
+        recipe.ingredients = ingredients;
+        Ok(recipe)
+    }
+}
+
+/// Build the stored ingredient for a row, keeping what the row was loaded
+/// from for every field that wasn't edited.
+fn line_to_ingredient(
+    line: &IngredientLine,
+    known: &[IngredientInfo],
+    units: &[String],
+) -> Result<RecipeIngredient, ()> {
+    let shown = line.loaded.as_ref().map(IngredientLine::from_ingredient).unwrap_or_default();
+    let mut ing = line.loaded.clone().unwrap_or(RecipeIngredient {
+        ingredient: String::new(),
+        quantity: None,
+        quantity_type: None,
+        note: None,
+        optional: false,
+    });
+
+    if line.ingredient != shown.ingredient {
+        let typed = line.ingredient.trim();
+        ing.ingredient = known
+            .iter()
+            .find(|i| i.forms.iter().any(|f| *f == typed.to_lowercase()))
+            .map(|i| i.name.clone())
+            .unwrap_or_else(|| typed.to_string());
+    }
+    if line.quantity != shown.quantity {
+        let parsed = parse_quantity_with(&line.quantity, units).map_err(|_| ())?;
+        ing.quantity = parsed.quantity;
+        ing.quantity_type = parsed.unit;
+    }
+    if line.note != shown.note {
+        let note = line.note.trim();
+        ing.note = (!note.is_empty()).then(|| note.to_string());
+    }
+    ing.optional = line.optional;
+    Ok(ing)
+}
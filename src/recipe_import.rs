@@ -0,0 +1,519 @@
+/// Importing recipes published as schema.org `Recipe` JSON-LD.
+///
+/// Most recipe websites embed the recipe as JSON-LD in a
+/// `<script type="application/ld+json">` block. The importer takes a saved page
+/// (or the JSON itself) and maps it onto a `Recipe`, which opens in the recipe
+/// dialog for review before anything is written.
+use crate::dialogs::IngredientInfo;
+use crate::quantity::parse_quantity_with;
+use janus_engine::{fold_for_matching, Recipe, RecipeIngredient};
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// File extensions the recipe import accepts (Cooklang, saved pages, JSON-LD).
+pub const IMPORT_EXTENSIONS: [&str; 5] = ["cook", "html", "htm", "json", "jsonld"];
+
+/// A recipe read from another format, not saved yet.
+#[derive(Debug, Clone)]
+pub struct ImportedRecipe {
+    pub recipe: Recipe,
+    /// Local image file to copy into the image folder when the recipe is saved.
+    pub image_file: Option<PathBuf>,
+    /// Ingredient names that matched no known ingredient.
+    pub unmatched: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportError {
+    /// The text holds no schema.org `Recipe`.
+    NoRecipe,
+    /// A JSON-LD block that doesn't parse.
+    InvalidJson(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::NoRecipe => write!(f, "no schema.org Recipe found"),
+            ImportError::InvalidJson(e) => write!(f, "invalid JSON-LD: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Import the first `Recipe` in `text`, an HTML page or JSON-LD. `source` is the
+/// file the text was read from, used to find images saved next to the page.
+/// Ingredient lines are matched against `known` and their quantities parsed
+/// with the extra `units`.
+pub fn import_json_ld(
+    text: &str,
+    source: Option<&Path>,
+    known: &[IngredientInfo],
+    units: &[String],
+) -> Result<ImportedRecipe, ImportError> {
+    let mut error = None;
+    for block in json_ld_blocks(text) {
+        match serde_json::from_str::<Value>(block) {
+            Ok(value) => {
+                if let Some(node) = find_recipe(&value) {
+                    return Ok(map_recipe(node, source, known, units));
+                }
+            }
+            Err(e) => {
+                error.get_or_insert(ImportError::InvalidJson(e.to_string()));
+            }
+        }
+    }
+    Err(error.unwrap_or(ImportError::NoRecipe))
+}
+
+/// The JSON-LD blocks of an HTML page, or the whole text if it is JSON.
+fn json_ld_blocks(text: &str) -> Vec<&str> {
+    let trimmed = text.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        return vec![trimmed];
+    }
+    // ASCII lowercasing keeps byte offsets, so they index `text` too
+    let lower = text.to_ascii_lowercase();
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find("<script").map(|i| pos + i) {
+        let Some(body) = lower[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = lower[body..].find("</script").map(|i| body + i) else {
+            break;
+        };
+        if lower[start..body].contains("application/ld+json") {
+            blocks.push(text[body..end].trim());
+        }
+        pos = end;
+    }
+    blocks
+}
+
+/// The first node typed `Recipe`, searching arrays, `@graph` and nested objects.
+fn find_recipe(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(find_recipe),
+        Value::Object(map) => {
+            let is_recipe = match map.get("@type") {
+                Some(Value::String(t)) => is_recipe_type(t),
+                Some(Value::Array(types)) => {
+                    types.iter().filter_map(Value::as_str).any(is_recipe_type)
+                }
+                _ => false,
+            };
+            if is_recipe {
+                Some(value)
+            } else {
+                map.values().find_map(find_recipe)
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_recipe_type(t: &str) -> bool {
+    t == "Recipe" || t.ends_with("/Recipe") || t.ends_with(":Recipe")
+}
+
+fn map_recipe(
+    node: &Value,
+    source: Option<&Path>,
+    known: &[IngredientInfo],
+    units: &[String],
+) -> ImportedRecipe {
+    let ingredients: Vec<RecipeIngredient> =
+        strings(node.get("recipeIngredient").or_else(|| node.get("ingredients")))
+            .iter()
+            .map(|line| parse_ingredient_line(line, known, units))
+            .filter(|ing| !ing.ingredient.is_empty())
+            .collect();
+    let unmatched = unmatched_names(&ingredients, known);
+
+    let mut tags: Vec<String> = Vec::new();
+    for keyword in strings(node.get("keywords")) {
+        for tag in keyword.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+
+    let duration = |key: &str| node.get(key).and_then(Value::as_str).and_then(parse_duration);
+
+    // A file saved with the page is copied into the image folder on save;
+    // otherwise the address is kept as it is
+    let (image, image_file) = match node.get("image").and_then(image_url) {
+        Some(url) => match source.and_then(|s| local_image(&url, s)) {
+            Some(file) => (None, Some(file)),
+            None => (Some(url), None),
+        },
+        None => (None, None),
+    };
+
+    let recipe = Recipe {
+        title: node.get("name").map(text).unwrap_or_default(),
+        slug: String::new(),
+        file_stem: String::new(),
+        ingredients,
+        prep_time: duration("prepTime"),
+        downtime: duration("cookTime"),
+        servings: node.get("recipeYield").and_then(parse_yield),
+        tags: (!tags.is_empty()).then_some(tags),
+        image,
+        instructions: node
+            .get("recipeInstructions")
+            .map(instructions_markdown)
+            .unwrap_or_default(),
+    };
+    ImportedRecipe { recipe, image_file, unmatched }
+}
+
+/// Minutes in an ISO 8601 duration such as "PT1H30M" or "P0DT0H20M", rounded
+/// up. `None` if it doesn't parse or is zero.
+pub fn parse_duration(text: &str) -> Option<u32> {
+    let rest = text.trim().strip_prefix(['P', 'p'])?;
+    let mut seconds = 0.0;
+    let mut in_time = false;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            'T' => in_time = true,
+            '0'..='9' | '.' => number.push(c),
+            ',' => number.push('.'),
+            unit => {
+                let n: f64 = number.parse().ok()?;
+                number.clear();
+                seconds += n * match (unit, in_time) {
+                    ('W', false) => 7.0 * 86400.0,
+                    ('D', false) => 86400.0,
+                    ('H', true) => 3600.0,
+                    ('M', true) => 60.0,
+                    ('S', true) => 1.0,
+                    _ => return None,
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    let minutes = (seconds / 60.0).ceil() as u32;
+    (minutes > 0).then_some(minutes)
+}
+
+/// Servings from a `recipeYield` such as `4`, `"4 servings"` or `["4", "4 bowls"]`.
+fn parse_yield(value: &Value) -> Option<u32> {
+    match value {
+        Value::Number(n) => n.as_f64().map(|n| n.round() as u32).filter(|n| *n > 0),
+        Value::String(s) => s
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|part| !part.is_empty())?
+            .parse()
+            .ok()
+            .filter(|n| *n > 0),
+        Value::Array(items) => items.iter().find_map(parse_yield),
+        _ => None,
+    }
+}
+
+/// Split an ingredient line like "2 cups flour, sifted" into quantity, unit,
+/// ingredient and note. The ingredient is the known ingredient the line names
+/// (by one of its forms), or the remaining text if there is none.
+pub fn parse_ingredient_line(
+    line: &str,
+    known: &[IngredientInfo],
+    units: &[String],
+) -> RecipeIngredient {
+    let line = clean_text(line);
+    let (main, mut notes) = split_notes(&line);
+    let words: Vec<&str> = main.split_whitespace().collect();
+
+    // The longest leading run of words that reads as an amount (and unit)
+    let (quantity, quantity_type, used) = (1..words.len().min(5))
+        .rev()
+        .find_map(|n| {
+            let parsed = parse_quantity_with(&words[..n].join(" "), units).ok()?;
+            parsed.amount?;
+            Some((parsed.quantity, parsed.unit, n))
+        })
+        .unwrap_or((None, None, 0));
+
+    let name = words[used..].join(" ");
+    let name = name.strip_prefix("of ").unwrap_or(&name).trim().to_string();
+    let matched = match_ingredient(&name, known).map(|info| info.name.clone());
+
+    let optional = notes.iter().any(|n| fold_for_matching(n) == "optional");
+    notes.retain(|n| fold_for_matching(n) != "optional");
+
+    RecipeIngredient {
+        ingredient: matched.unwrap_or(name),
+        quantity,
+        quantity_type,
+        note: (!notes.is_empty()).then(|| notes.join(", ")),
+        optional,
+    }
+}
+
+/// The known ingredient `text` names: the one with a form equal to the whole
+/// text, so "Leeks" finds "leek" through its plural but "rice vinegar" doesn't
+/// find "rice".
+pub fn match_ingredient<'a>(
+    text: &str,
+    known: &'a [IngredientInfo],
+) -> Option<&'a IngredientInfo> {
+    let folded = fold_for_matching(text);
+    if folded.is_empty() {
+        return None;
+    }
+    known.iter().find(|info| info.forms.contains(&folded))
+}
+
+/// Names of `ingredients` that aren't known ingredients, without duplicates.
+pub fn unmatched_names(
+    ingredients: &[RecipeIngredient],
+    known: &[IngredientInfo],
+) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for ing in ingredients {
+        let is_known = known.iter().any(|info| info.name == ing.ingredient);
+        if !is_known && !names.contains(&ing.ingredient) {
+            names.push(ing.ingredient.clone());
+        }
+    }
+    names
+}
+
+/// Split off parenthesised remarks and anything after the first comma.
+fn split_notes(line: &str) -> (String, Vec<String>) {
+    let mut main = String::new();
+    let mut notes = Vec::new();
+    let mut depth = 0;
+    let mut remark = String::new();
+    for c in line.chars() {
+        match c {
+            '(' => {
+                depth += 1;
+                if depth > 1 {
+                    remark.push(c);
+                }
+            }
+            ')' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    notes.push(remark.trim().to_string());
+                    remark.clear();
+                } else {
+                    remark.push(c);
+                }
+            }
+            c if depth > 0 => remark.push(c),
+            c => main.push(c),
+        }
+    }
+    if let Some((head, tail)) = main.split_once(',') {
+        notes.push(tail.trim().to_string());
+        main = head.to_string();
+    }
+    notes.retain(|n| !n.is_empty());
+    (main.trim().to_string(), notes)
+}
+
+/// `recipeInstructions` as Markdown: numbered steps, with `HowToSection`
+/// names as headings.
+fn instructions_markdown(value: &Value) -> String {
+    let mut blocks = Vec::new();
+    let mut steps = Vec::new();
+    collect_steps(value, &mut blocks, &mut steps);
+    if blocks.is_empty() && steps.len() == 1 {
+        return steps.remove(0);
+    }
+    flush_steps(&mut blocks, &mut steps);
+    blocks.join("\n\n")
+}
+
+fn collect_steps(value: &Value, blocks: &mut Vec<String>, steps: &mut Vec<String>) {
+    match value {
+        Value::String(s) => {
+            // Plain or HTML text: one step per line, paragraph or list item
+            let mut s = s.clone();
+            for tag in ["<br>", "<br/>", "<br />", "</p>", "</li>"] {
+                s = s.replace(tag, "\n").replace(&tag.to_uppercase(), "\n");
+            }
+            let lines = s.lines().map(|line| strip_step_number(&clean_text(line)));
+            steps.extend(lines.filter(|line| !line.is_empty()));
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_steps(item, blocks, steps);
+            }
+        }
+        Value::Object(map) => {
+            if let Some(items) = map.get("itemListElement") {
+                flush_steps(blocks, steps);
+                if let Some(name) = map.get("name").map(text).filter(|n| !n.is_empty()) {
+                    blocks.push(format!("## {}", name));
+                }
+                collect_steps(items, blocks, steps);
+                flush_steps(blocks, steps);
+            } else if let Some(step) = map.get("text").or_else(|| map.get("name")) {
+                let step = strip_step_number(&text(step));
+                if !step.is_empty() {
+                    steps.push(step);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Move the collected `steps` into `blocks` as one numbered list.
+pub fn flush_steps(blocks: &mut Vec<String>, steps: &mut Vec<String>) {
+    if steps.is_empty() {
+        return;
+    }
+    let list: Vec<String> =
+        steps.drain(..).enumerate().map(|(i, step)| format!("{}. {}", i + 1, step)).collect();
+    blocks.push(list.join("\n"));
+}
+
+/// Drop a leading "1." or "2)" the site numbered its steps with.
+fn strip_step_number(step: &str) -> String {
+    let rest = step.trim_start_matches(|c: char| c.is_ascii_digit());
+    match rest.strip_prefix(['.', ')']) {
+        Some(rest) if rest.len() < step.len() - 1 && rest.starts_with(' ') => {
+            rest.trim().to_string()
+        }
+        _ => step.to_string(),
+    }
+}
+
+/// Address of an `image`: a URL, an `ImageObject` or a list of either.
+fn image_url(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Array(items) => items.iter().find_map(image_url),
+        Value::Object(map) => map
+            .get("url")
+            .or_else(|| map.get("contentUrl"))
+            .or_else(|| map.get("@id"))
+            .and_then(image_url),
+        _ => None,
+    }
+}
+
+/// The image file for `url` saved alongside the page at `source`: a relative
+/// path, or the file browsers put in the page's `<name>_files` folder.
+fn local_image(url: &str, source: &Path) -> Option<PathBuf> {
+    let dir = source.parent()?;
+    let path = url.split(['?', '#']).next().unwrap_or(url).replace("%20", " ");
+    let mut candidates = Vec::new();
+    if let Some(file) = path.strip_prefix("file://") {
+        candidates.push(PathBuf::from(file));
+    } else if !path.contains("://") {
+        candidates.push(dir.join(path.trim_start_matches("./")));
+    }
+    if let (Some(stem), Some(name)) = (source.file_stem(), path.rsplit('/').next()) {
+        let mut folder = stem.to_os_string();
+        folder.push("_files");
+        candidates.push(dir.join(folder).join(name));
+    }
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// The text of a value: a string, a number, the first of a list, or an
+/// object's `text` or `name`.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => clean_text(s),
+        Value::Number(n) => n.to_string(),
+        Value::Array(items) => items.first().map(text).unwrap_or_default(),
+        Value::Object(map) => {
+            map.get("text").or_else(|| map.get("name")).map(text).unwrap_or_default()
+        }
+        _ => String::new(),
+    }
+}
+
+/// The non-empty texts of a value or list of values.
+fn strings(value: Option<&Value>) -> Vec<String> {
+    let texts: Vec<String> = match value {
+        Some(Value::Array(items)) => items.iter().map(text).collect(),
+        Some(value) => vec![text(value)],
+        None => Vec::new(),
+    };
+    texts.into_iter().filter(|t| !t.is_empty()).collect()
+}
+
+/// Text without HTML tags and entities, whitespace collapsed.
+fn clean_text(s: &str) -> String {
+    const INLINE_TAGS: [&str; 8] = ["a", "b", "em", "i", "small", "span", "strong", "u"];
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let starts_tag =
+            c == '<' && matches!(chars.peek(), Some(n) if n.is_ascii_alphabetic() || *n == '/');
+        if !starts_tag {
+            stripped.push(c);
+            continue;
+        }
+        let tag: String = chars.by_ref().take_while(|&c| c != '>').collect();
+        let name = tag.trim_start_matches('/').split([' ', '/']).next().unwrap_or("");
+        // Block tags separate words, inline ones don't
+        if !INLINE_TAGS.contains(&name.to_ascii_lowercase().as_str()) {
+            stripped.push(' ');
+        }
+    }
+    decode_entities(&stripped).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        "frac12" => Some('½'),
+        "frac14" => Some('¼'),
+        "frac34" => Some('¾'),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
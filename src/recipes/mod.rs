@@ -1,21 +1,187 @@
 /// Recipes tab: recipe list with availability indicators and detail view.
 use crate::app::{App, AppMsg};
+use crate::cooking::{days_since, CookStats, CookingLog};
+use crate::coverage::{ingredient_stock, recipe_coverage, Coverage, Stock};
 use crate::i18n;
+use crate::images;
 use crate::ui_constants::*;
-use janus_engine::DataManager;
+use crate::quantity::{display_quantity, format_amount};
+use crate::recipe_filter::{
+    all_tags, has_tag, total_time, FilterChange, RecipeFilter, MAX_MISSING_CHOICE,
+    MAX_SERVINGS_CHOICE, TIME_CHOICES,
+};
+use crate::recipe_import::IMPORT_EXTENSIONS;
+use crate::units::localize;
+use janus_engine::{slugify, DataManager, Recipe, RecipeIngredient};
 use libadwaita as adw;
 use relm4::gtk;
+use relm4::gtk::glib;
 use relm4::{gtk::prelude::*, ComponentSender, RelmWidgetExt};
 use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+/// Order of the recipe list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecipeSort {
+    /// Alphabetical (search results keep their relevance order).
+    #[default]
+    Name,
+    /// Highest share of required ingredients covered by the pantry first.
+    BestCoverage,
+    FewestMissing,
+    /// Shortest prep time plus downtime first; recipes without times last.
+    ShortestTime,
+    /// Most recently added or edited recipe files first.
+    RecentlyModified,
+    /// Never-cooked recipes first, then the longest since last cooked.
+    LeastRecentlyCooked,
+    MostCooked,
+    /// Recipes using the most pantry items that expire soon first.
+    UseItUp,
+}
+
+impl RecipeSort {
+    /// All orders, in the order the sort menu lists them.
+    pub const ALL: [RecipeSort; 8] = [
+        RecipeSort::Name,
+        RecipeSort::BestCoverage,
+        RecipeSort::FewestMissing,
+        RecipeSort::ShortestTime,
+        RecipeSort::RecentlyModified,
+        RecipeSort::LeastRecentlyCooked,
+        RecipeSort::MostCooked,
+        RecipeSort::UseItUp,
+    ];
+
+    pub fn label(self) -> &'static str {
+        let s = i18n::strings();
+        match self {
+            RecipeSort::Name => s.sort_by_name,
+            RecipeSort::BestCoverage => s.sort_best_coverage,
+            RecipeSort::FewestMissing => s.sort_fewest_missing,
+            RecipeSort::ShortestTime => s.sort_shortest_time,
+            RecipeSort::RecentlyModified => s.sort_recently_modified,
+            RecipeSort::LeastRecentlyCooked => s.sort_least_recently_cooked,
+            RecipeSort::MostCooked => s.sort_most_cooked,
+            RecipeSort::UseItUp => s.sort_use_it_up,
+        }
+    }
+}
+
+/// What the recipe orders need besides the recipes and their coverage.
+pub struct SortKeys<'a> {
+    pub log: &'a CookingLog,
+    /// Slugs of pantry items that expire soon.
+    pub expiring: &'a BTreeSet<String>,
+    /// When each recipe file was last modified, by title. Only needed for
+    /// [`RecipeSort::RecentlyModified`].
+    pub modified: &'a HashMap<String, SystemTime>,
+}
+
+/// Sort `recipes`, each with its pantry coverage, in place. Ties fall back to
+/// the title.
+pub fn sort_recipes(recipes: &mut [(&Recipe, Coverage)], sort: RecipeSort, keys: &SortKeys) {
+    match sort {
+        RecipeSort::Name => recipes.sort_by(|(a, _), (b, _)| a.title.cmp(&b.title)),
+        RecipeSort::BestCoverage => recipes.sort_by(|(a, ca), (b, cb)| {
+            cb.covered_ratio()
+                .total_cmp(&ca.covered_ratio())
+                .then(cb.partial_ratio().total_cmp(&ca.partial_ratio()))
+                .then_with(|| a.title.cmp(&b.title))
+        }),
+        RecipeSort::FewestMissing => {
+            recipes.sort_by_cached_key(|(r, cov)| (cov.not_covered(), r.title.clone()))
+        }
+        RecipeSort::ShortestTime => recipes.sort_by_cached_key(|(r, _)| {
+            let time = total_time(r);
+            (time.is_none(), time, r.title.clone())
+        }),
+        RecipeSort::RecentlyModified => recipes.sort_by_cached_key(|(r, _)| {
+            let modified = keys.modified.get(&r.title).copied();
+            (modified.is_none(), Reverse(modified), r.title.clone())
+        }),
+        RecipeSort::LeastRecentlyCooked => recipes.sort_by_cached_key(|(r, _)| {
+            (keys.log.stats(&r.title).map(|st| st.last), r.title.clone())
+        }),
+        RecipeSort::MostCooked => recipes.sort_by_cached_key(|(r, _)| {
+            let count = keys.log.stats(&r.title).map_or(0, |st| st.count);
+            (Reverse(count), r.title.clone())
+        }),
+        RecipeSort::UseItUp => recipes.sort_by_cached_key(|(r, _)| {
+            let used = r
+                .ingredients
+                .iter()
+                .filter(|i| keys.expiring.contains(&slugify(&i.ingredient)))
+                .count();
+            (Reverse(used), r.title.clone())
+        }),
+    }
+}
+
+/// When the file of `recipe` was last modified, if it can be read.
+pub fn modified_time(data_dir: &Path, recipe: &Recipe) -> Option<SystemTime> {
+    let path = data_dir.join("recipes").join(format!("{}.md", recipe.file_stem));
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Sections of the grouped recipe list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Cookability {
+    /// The pantry covers every required ingredient.
+    Now,
+    /// At most [`ALMOST_MAX_MISSING`] required ingredients missing.
+    Almost,
+    NeedsShopping,
+}
+
+/// Most missing ingredients a recipe in the "Almost" section may have.
+pub const ALMOST_MAX_MISSING: usize = 2;
+
+impl Cookability {
+    pub fn of(cov: Coverage) -> Cookability {
+        match cov.not_covered() {
+            0 => Cookability::Now,
+            n if n <= ALMOST_MAX_MISSING => Cookability::Almost,
+            _ => Cookability::NeedsShopping,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        let s = i18n::strings();
+        match self {
+            Cookability::Now => s.group_cookable_now,
+            Cookability::Almost => s.group_almost,
+            Cookability::NeedsShopping => s.group_needs_shopping,
+        }
+    }
+}
+
+/// The Recipes tab's filter menu. Its controls are built once and brought in
+/// line with the filter by [`sync_recipe_filters`], which also rebuilds the
+/// tag choices.
+#[derive(Clone)]
+pub struct FilterPanel {
+    pub button: gtk::MenuButton,
+    cookable: gtk::Switch,
+    missing: gtk::Scale,
+    max_prep: gtk::DropDown,
+    max_total: gtk::DropDown,
+    min_servings: gtk::DropDown,
+    max_servings: gtk::DropDown,
+    tags: gtk::Box,
+}
 
 /// Build the full Recipes tab widget.
 ///
-/// Returns `(tab_widget, recipe_list_box, recipe_detail_box)`.
+/// Returns `(tab_widget, recipe_list_box, recipe_detail_box, filter_panel)`.
 pub fn build_recipes_tab(
     dm: &Option<Rc<RefCell<DataManager>>>,
     sender: ComponentSender<App>,
-) -> (gtk::Widget, gtk::ListBox, gtk::Box) {
+) -> (gtk::Widget, gtk::ListBox, gtk::Box, FilterPanel) {
     let s = i18n::strings();
 
     let paned = gtk::Paned::new(gtk::Orientation::Horizontal);
@@ -30,11 +196,46 @@ pub fn build_recipes_tab(
     let search = gtk::SearchEntry::new();
     search.set_placeholder_text(Some(s.search_recipes));
     search.set_margin_top(DEFAULT_MARGIN);
-    search.set_margin_bottom(DEFAULT_MARGIN);
+    search.set_margin_bottom(ROW_SPACING);
     search.set_margin_start(DEFAULT_MARGIN);
     search.set_margin_end(DEFAULT_MARGIN);
     left.append(&search);
 
+    // Sort order and filters
+    let sort_row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    sort_row.set_margin_start(DEFAULT_MARGIN);
+    sort_row.set_margin_end(DEFAULT_MARGIN);
+    sort_row.set_margin_bottom(DEFAULT_MARGIN);
+
+    let labels: Vec<&str> = RecipeSort::ALL.iter().map(|o| o.label()).collect();
+    let sort_dropdown = gtk::DropDown::from_strings(&labels);
+    sort_dropdown.set_tooltip_text(Some(s.sort_tooltip));
+    sort_dropdown.set_hexpand(true);
+    {
+        let sender_sort = sender.clone();
+        sort_dropdown.connect_selected_notify(move |dd| {
+            if let Some(&order) = RecipeSort::ALL.get(dd.selected() as usize) {
+                sender_sort.input(AppMsg::SetRecipeSort(order));
+            }
+        });
+    }
+    sort_row.append(&sort_dropdown);
+
+    let group_btn = gtk::ToggleButton::new();
+    group_btn.set_icon_name("view-list-bullet-symbolic");
+    group_btn.set_tooltip_text(Some(s.group_by_cookability_tooltip));
+    {
+        let sender_group = sender.clone();
+        group_btn.connect_toggled(move |btn| {
+            sender_group.input(AppMsg::ToggleRecipeGrouping(btn.is_active()));
+        });
+    }
+    sort_row.append(&group_btn);
+
+    let filter_panel = build_filter_panel(&sender);
+    sort_row.append(&filter_panel.button);
+    left.append(&sort_row);
+
     left.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
 
     let list_scroll = gtk::ScrolledWindow::new();
@@ -47,15 +248,65 @@ pub fn build_recipes_tab(
     list_scroll.set_child(Some(&recipe_list));
     left.append(&list_scroll);
 
-    // Add recipe button
+    // Cooklang files and saved recipe pages dropped on the list are imported
+    let drop =
+        gtk::DropTarget::new(gtk::gdk::FileList::static_type(), gtk::gdk::DragAction::COPY);
+    {
+        let sender_drop = sender.clone();
+        drop.connect_drop(move |_, value, _, _| {
+            let Ok(files) = value.get::<gtk::gdk::FileList>() else {
+                return false;
+            };
+            let paths: Vec<PathBuf> = files
+                .files()
+                .iter()
+                .filter_map(|f| f.path())
+                .filter(|p| {
+                    p.extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|e| IMPORT_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                })
+                .collect();
+            if paths.is_empty() {
+                return false;
+            }
+            sender_drop.input(AppMsg::ImportRecipeFiles(paths));
+            true
+        });
+    }
+    list_scroll.add_controller(drop);
+
+    // Add and import recipe buttons
+    let btn_row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    btn_row.set_margin_all(DEFAULT_MARGIN);
+
     let add_btn = gtk::Button::with_label(s.add_recipe);
     add_btn.add_css_class("flat");
-    add_btn.set_margin_all(DEFAULT_MARGIN);
+    add_btn.set_hexpand(true);
     {
         let sender_add = sender.clone();
         add_btn.connect_clicked(move |_| sender_add.input(AppMsg::AddRecipe));
     }
-    left.append(&add_btn);
+    btn_row.append(&add_btn);
+
+    let import_btn = gtk::Button::from_icon_name("document-open-symbolic");
+    import_btn.add_css_class("flat");
+    import_btn.set_tooltip_text(Some(s.import_recipe));
+    {
+        let sender_import = sender.clone();
+        import_btn.connect_clicked(move |_| sender_import.input(AppMsg::ShowImportRecipe));
+    }
+    btn_row.append(&import_btn);
+
+    let cookbook_btn = gtk::Button::from_icon_name("x-office-document-symbolic");
+    cookbook_btn.add_css_class("flat");
+    cookbook_btn.set_tooltip_text(Some(s.export_cookbook_tooltip));
+    {
+        let sender_cookbook = sender.clone();
+        cookbook_btn.connect_clicked(move |_| sender_cookbook.input(AppMsg::ShowExportCookbook));
+    }
+    btn_row.append(&cookbook_btn);
+    left.append(&btn_row);
 
     // ── Right: recipe detail ──────────────────────────────────────────────────
     let detail_scroll = gtk::ScrolledWindow::new();
@@ -72,7 +323,18 @@ pub fn build_recipes_tab(
     paned.set_end_child(Some(&detail_scroll));
 
     // Populate initial list
-    populate_recipe_list(&recipe_list, dm, "", &sender);
+    populate_recipe_list(
+        &recipe_list,
+        dm,
+        Path::new(""),
+        "",
+        RecipeSort::default(),
+        &CookingLog::default(),
+        &BTreeSet::new(),
+        &RecipeFilter::default(),
+        false,
+        &sender,
+    );
 
     // Search handler
     {
@@ -88,22 +350,258 @@ pub fn build_recipes_tab(
         recipe_list.connect_row_selected(move |_, row| {
             if let Some(row) = row {
                 let title = row.widget_name().to_string();
-                if !title.is_empty() && title != "__empty__" {
+                if !title.is_empty() && title != "__empty__" && title != "__header__" {
                     sender_select.input(AppMsg::SelectRecipe(Some(title)));
                 }
             }
         });
     }
 
-    (paned.upcast(), recipe_list, recipe_detail)
+    (paned.upcast(), recipe_list, recipe_detail, filter_panel)
 }
 
-/// Rebuild the recipe list with an optional search query.
+/// Build the filter menu button and its popover: cookable now, missing at
+/// most N, maximum prep and total time, servings range and tags. Every control
+/// sends `AppMsg::UpdateRecipeFilter`.
+fn build_filter_panel(sender: &ComponentSender<App>) -> FilterPanel {
+    let s = i18n::strings();
+    let send = |change: fn(u32) -> FilterChange| {
+        let sender = sender.clone();
+        move |dd: &gtk::DropDown| sender.input(AppMsg::UpdateRecipeFilter(change(dd.selected())))
+    };
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, ROW_SPACING);
+    content.set_margin_all(ROW_SPACING);
+
+    // Cookable now
+    let cookable_row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    let cookable_label = gtk::Label::new(Some(s.cookable_now_label));
+    cookable_label.set_hexpand(true);
+    cookable_label.set_halign(gtk::Align::Start);
+    let cookable = gtk::Switch::new();
+    cookable.set_valign(gtk::Align::Center);
+    {
+        let sender = sender.clone();
+        cookable.connect_active_notify(move |sw| {
+            sender.input(AppMsg::UpdateRecipeFilter(FilterChange::CookableOnly(sw.is_active())));
+        });
+    }
+    cookable_row.append(&cookable_label);
+    cookable_row.append(&cookable);
+    content.append(&cookable_row);
+
+    // Missing at most N; the last step means any number
+    let missing_label = gtk::Label::new(Some(s.missing_at_most_label));
+    missing_label.set_halign(gtk::Align::Start);
+    content.append(&missing_label);
+    let any = (MAX_MISSING_CHOICE + 1) as f64;
+    let missing = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, any, 1.0);
+    missing.set_round_digits(0);
+    missing.set_draw_value(true);
+    missing.set_value(any);
+    missing.set_format_value_func(move |_, v| {
+        if v >= any {
+            i18n::strings().any_choice.to_string()
+        } else {
+            format!("{}", v as usize)
+        }
+    });
+    {
+        let sender = sender.clone();
+        missing.connect_value_changed(move |scale| {
+            let v = scale.value().round() as usize;
+            let max = (v <= MAX_MISSING_CHOICE).then_some(v);
+            sender.input(AppMsg::UpdateRecipeFilter(FilterChange::MaxMissing(max)));
+        });
+    }
+    content.append(&missing);
+
+    // Times and servings
+    let grid = gtk::Grid::new();
+    grid.set_row_spacing(ROW_SPACING as u32);
+    grid.set_column_spacing(ROW_SPACING as u32);
+    let mut times = vec![s.any_choice.to_string()];
+    times.extend(TIME_CHOICES.iter().map(|m| format!("{} min", m)));
+    let times: Vec<&str> = times.iter().map(String::as_str).collect();
+    let mut servings = vec![s.any_choice.to_string()];
+    servings.extend((1..=MAX_SERVINGS_CHOICE).map(|n| n.to_string()));
+    let servings: Vec<&str> = servings.iter().map(String::as_str).collect();
+
+    let max_prep = gtk::DropDown::from_strings(&times);
+    max_prep.connect_selected_notify(send(|i| FilterChange::MaxPrep(time_choice(i))));
+    let max_total = gtk::DropDown::from_strings(&times);
+    max_total.connect_selected_notify(send(|i| FilterChange::MaxTotal(time_choice(i))));
+    let min_servings = gtk::DropDown::from_strings(&servings);
+    min_servings.connect_selected_notify(send(|i| FilterChange::MinServings((i > 0).then_some(i))));
+    let max_servings = gtk::DropDown::from_strings(&servings);
+    max_servings.connect_selected_notify(send(|i| FilterChange::MaxServings((i > 0).then_some(i))));
+
+    let servings_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    servings_box.append(&min_servings);
+    servings_box.append(&gtk::Label::new(Some("–")));
+    servings_box.append(&max_servings);
+
+    for (row, (label, control)) in [
+        (s.max_prep_label, max_prep.clone().upcast::<gtk::Widget>()),
+        (s.max_total_label, max_total.clone().upcast()),
+        (s.servings_range_label, servings_box.upcast()),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let label = gtk::Label::new(Some(label));
+        label.set_halign(gtk::Align::Start);
+        label.set_hexpand(true);
+        grid.attach(&label, 0, row as i32, 1, 1);
+        grid.attach(&control, 1, row as i32, 1, 1);
+    }
+    content.append(&grid);
+
+    // Tags, filled in by sync_recipe_filters
+    let tags_scroll = gtk::ScrolledWindow::new();
+    tags_scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    tags_scroll.set_max_content_height(200);
+    tags_scroll.set_propagate_natural_height(true);
+    let tags = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    tags_scroll.set_child(Some(&tags));
+    content.append(&tags_scroll);
+
+    let button = gtk::MenuButton::new();
+    button.set_label(s.filter_label);
+    button.set_tooltip_text(Some(s.recipe_filter_tooltip));
+
+    let clear_btn = gtk::Button::with_label(s.clear_filters);
+    clear_btn.add_css_class("flat");
+    {
+        let sender = sender.clone();
+        let button = button.clone();
+        clear_btn.connect_clicked(move |_| {
+            button.popdown();
+            sender.input(AppMsg::UpdateRecipeFilter(FilterChange::Clear));
+        });
+    }
+    content.append(&clear_btn);
+
+    let popover = gtk::Popover::new();
+    popover.set_child(Some(&content));
+    button.set_popover(Some(&popover));
+
+    FilterPanel {
+        button,
+        cookable,
+        missing,
+        max_prep,
+        max_total,
+        min_servings,
+        max_servings,
+        tags,
+    }
+}
+
+/// Minutes for the `index`th entry of a time menu; the first is "any".
+fn time_choice(index: u32) -> Option<u32> {
+    index.checked_sub(1).and_then(|i| TIME_CHOICES.get(i as usize)).copied()
+}
+
+/// Bring the filter panel's controls in line with `filter` and rebuild the tag
+/// choices, each with its number of recipes.
+pub fn sync_recipe_filters(
+    panel: &FilterPanel,
+    dm: &Option<Rc<RefCell<DataManager>>>,
+    filter: &RecipeFilter,
+    sender: &ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    match filter.active_count() {
+        0 => {
+            panel.button.set_label(s.filter_label);
+            panel.button.remove_css_class("accent");
+        }
+        n => {
+            panel.button.set_label(&i18n::fmt_active_filters(n));
+            panel.button.add_css_class("accent");
+        }
+    }
+
+    if panel.cookable.is_active() != filter.cookable_only {
+        panel.cookable.set_active(filter.cookable_only);
+    }
+    let missing = filter.max_missing.unwrap_or(MAX_MISSING_CHOICE + 1) as f64;
+    if panel.missing.value() != missing {
+        panel.missing.set_value(missing);
+    }
+    let time_index = |t: Option<u32>| {
+        t.and_then(|t| TIME_CHOICES.iter().position(|&c| c == t))
+            .map_or(0, |i| i as u32 + 1)
+    };
+    for (dropdown, index) in [
+        (&panel.max_prep, time_index(filter.max_prep)),
+        (&panel.max_total, time_index(filter.max_total)),
+        (&panel.min_servings, filter.min_servings.unwrap_or(0)),
+        (&panel.max_servings, filter.max_servings.unwrap_or(0)),
+    ] {
+        if dropdown.selected() != index {
+            dropdown.set_selected(index);
+        }
+    }
+
+    crate::utils::clear_box(&panel.tags);
+    let Some(dm) = dm else {
+        return;
+    };
+    let dm = dm.borrow();
+    let recipes = dm.get_all_recipes();
+    let mut tags = all_tags(recipes.iter());
+    for tag in &filter.tags {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    if tags.is_empty() {
+        return;
+    }
+    let heading = gtk::Label::new(Some(s.tags_heading));
+    heading.add_css_class("heading");
+    heading.set_halign(gtk::Align::Start);
+    heading.set_margin_top(ROW_SPACING);
+    heading.set_margin_bottom(ROW_SPACING);
+    panel.tags.append(&heading);
+    for tag in tags {
+        let count = recipes.iter().filter(|r| has_tag(r, &tag)).count();
+        let check = gtk::CheckButton::with_label(&format!("{} ({})", tag, count));
+        check.set_active(filter.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)));
+        let sender = sender.clone();
+        check.connect_toggled(move |c| {
+            sender.input(AppMsg::UpdateRecipeFilter(FilterChange::Tag {
+                tag: tag.clone(),
+                selected: c.is_active(),
+            }));
+        });
+        panel.tags.append(&check);
+    }
+}
+
+/// Rebuild the recipe list with an optional search query, in `sort` order.
+/// `expiring` holds the slugs of pantry items that expire soon. Recipes that
+/// don't pass `filter` are left out; when that leaves nothing, the empty state
+/// lists how many recipes each active filter allows on its own. With `grouped`
+/// the list is split into "Cookable now", "Almost" and "Needs shopping"
+/// sections, each in `sort` order.
+///
+/// Thumbnails of recipe images that aren't cached yet are generated in the
+/// background; the list is rebuilt when they are ready.
+#[allow(clippy::too_many_arguments)]
 pub fn populate_recipe_list(
     list: &gtk::ListBox,
     dm: &Option<Rc<RefCell<DataManager>>>,
+    data_dir: &Path,
     search: &str,
-    _sender: &ComponentSender<App>,
+    sort: RecipeSort,
+    log: &CookingLog,
+    expiring: &BTreeSet<String>,
+    filter: &RecipeFilter,
+    grouped: bool,
+    sender: &ComponentSender<App>,
 ) {
     let s = i18n::strings();
     crate::utils::clear_list_box(list);
@@ -114,33 +612,103 @@ pub fn populate_recipe_list(
     };
 
     let dm = dm.borrow();
-    let recipes = if search.is_empty() {
-        let mut all: Vec<_> = dm.get_all_recipes().iter().collect();
-        all.sort_by(|a, b| a.title.cmp(&b.title));
-        all
+    let recipes: Vec<&Recipe> = if search.is_empty() {
+        dm.get_all_recipes().iter().collect()
     } else {
         dm.search_recipes(search)
     };
+    let search_hits = recipes.len();
+    let mut recipes: Vec<(&Recipe, Coverage)> = recipes
+        .into_iter()
+        .map(|r| (r, recipe_coverage(&dm, r, 1.0)))
+        .filter(|(r, cov)| filter.matches(r, *cov))
+        .collect();
+    if search.is_empty() || sort != RecipeSort::Name {
+        let modified = if sort == RecipeSort::RecentlyModified {
+            recipes
+                .iter()
+                .filter_map(|(r, _)| Some((r.title.clone(), modified_time(data_dir, r)?)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let keys = SortKeys { log, expiring, modified: &modified };
+        sort_recipes(&mut recipes, sort, &keys);
+    }
+    if grouped {
+        // Stable, so each section keeps the chosen order
+        recipes.sort_by_key(|(_, cov)| Cookability::of(*cov));
+    }
 
     if recipes.is_empty() {
-        list.append(&empty_state_row(s.no_recipes_found));
+        if filter.is_active() {
+            let all: Vec<(&Recipe, Coverage)> = dm
+                .get_all_recipes()
+                .iter()
+                .map(|r| (r, recipe_coverage(&dm, r, 1.0)))
+                .collect();
+            let reasons = filter.explain(search, search_hits, &all);
+            list.append(&filtered_empty_row(&reasons, sender));
+        } else {
+            list.append(&empty_state_row(s.no_recipes_found));
+        }
         return;
     }
 
-    for recipe in recipes {
-        let cov = recipe.pantry_coverage(&dm);
-        let row = build_recipe_row(recipe, cov);
+    let today = chrono::Local::now().date_naive();
+    let mut missing_thumbnails = Vec::new();
+    let mut current_group = None;
+    for (recipe, cov) in recipes {
+        if grouped && current_group != Some(Cookability::of(cov)) {
+            let group = Cookability::of(cov);
+            list.append(&build_group_header_row(group.label()));
+            current_group = Some(group);
+        }
+        let cooked = log
+            .stats(&recipe.title)
+            .and_then(|st| Some(i18n::fmt_last_cooked(days_since(&st.last, today)?, st.count)));
+        let thumbnail = images::recipe_image_path(data_dir, recipe).and_then(|source| {
+            let cached = images::cached_thumbnail(&source, images::THUMBNAIL_SIZE);
+            if cached.is_none() {
+                missing_thumbnails.push((source, images::THUMBNAIL_SIZE));
+            }
+            cached
+        });
+        let row = build_recipe_row(recipe, cov, cooked.as_deref(), thumbnail.as_deref());
         list.append(&row);
     }
+
+    if !missing_thumbnails.is_empty() {
+        let sender = sender.clone();
+        images::generate_thumbnails(missing_thumbnails, move || {
+            sender.input(AppMsg::ThumbnailsReady)
+        });
+    }
 }
 
-fn build_recipe_row(
-    recipe: &janus_engine::Recipe,
-    cov: janus_engine::PantryCoverage,
+/// One list row: coverage pie, image thumbnail (if cached), title and (if cooked
+/// before) a "last cooked" caption.
+///
+/// Rows can be dragged onto the planner; the drag payload is the recipe title.
+pub fn build_recipe_row(
+    recipe: &Recipe,
+    cov: Coverage,
+    cooked: Option<&str>,
+    thumbnail: Option<&Path>,
 ) -> gtk::ListBoxRow {
     let row = gtk::ListBoxRow::new();
     row.set_widget_name(&recipe.title);
 
+    let drag = gtk::DragSource::new();
+    drag.set_actions(gtk::gdk::DragAction::COPY);
+    {
+        let title = recipe.title.clone();
+        drag.connect_prepare(move |_, _, _| {
+            Some(gtk::gdk::ContentProvider::for_value(&title.to_value()))
+        });
+    }
+    row.add_controller(drag);
+
     let hbox = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
     hbox.set_margin_top(ROW_SPACING);
     hbox.set_margin_bottom(ROW_SPACING);
@@ -148,25 +716,84 @@ fn build_recipe_row(
     hbox.set_margin_end(DEFAULT_MARGIN);
 
     // Pie-chart availability indicator
-    let tooltip = i18n::fmt_required_tooltip(cov.required_in_stock, cov.required_total);
-    let pie = crate::utils::build_coverage_pie(cov.required_ratio(), &tooltip);
+    let tooltip = i18n::fmt_required_tooltip(cov.covered, cov.partial, cov.required_total);
+    let pie =
+        crate::utils::build_coverage_pie(cov.covered_ratio(), cov.partial_ratio(), &tooltip);
     hbox.append(&pie);
 
+    if let Some(path) = thumbnail {
+        let image = gtk::Image::from_file(path);
+        image.set_pixel_size(images::THUMBNAIL_SIZE / 2);
+        image.set_valign(gtk::Align::Center);
+        hbox.append(&image);
+    }
+
+    let text_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    text_box.set_hexpand(true);
+    text_box.set_valign(gtk::Align::Center);
+
     let title_label = gtk::Label::new(Some(&recipe.title));
-    title_label.set_hexpand(true);
     title_label.set_halign(gtk::Align::Start);
     title_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
-    hbox.append(&title_label);
+    text_box.append(&title_label);
+
+    if let Some(cooked) = cooked {
+        let cooked_label = gtk::Label::new(Some(cooked));
+        cooked_label.add_css_class("caption");
+        cooked_label.add_css_class("dim-label");
+        cooked_label.set_halign(gtk::Align::Start);
+        cooked_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        text_box.append(&cooked_label);
+    }
+    hbox.append(&text_box);
 
     row.set_child(Some(&hbox));
     row
 }
 
+/// One ingredient line as the recipe view lists it, e.g. "200 g spaghetti (thin)",
+/// scaled by `factor`.
+pub fn ingredient_text(dm: &DataManager, ing: &RecipeIngredient, factor: f64) -> String {
+    let s = i18n::strings();
+    let (qty, unit) = match &ing.quantity {
+        Some(q) => {
+            let (q, u) = display_quantity(q, ing.quantity_type.as_deref(), factor);
+            (Some(q), u)
+        }
+        None => (None, ing.quantity_type.clone()),
+    };
+    let display_name = dm.recipe_ingredient_display_name(ing);
+    let mut text = match (&qty, &unit) {
+        (Some(q), Some(u)) if !u.is_empty() => format!("{} {} {}", q, u, display_name),
+        (Some(q), _) => format!("{} {}", q, display_name),
+        _ => display_name,
+    };
+    if let Some(note) = &ing.note {
+        text = format!("{} ({})", text, note);
+    }
+    if ing.optional {
+        text = format!("{}{}", text, s.optional_suffix);
+    }
+    text
+}
+
+/// Quiet time after the last serving change before it is stored.
+const SERVINGS_DEBOUNCE: Duration = Duration::from_millis(500);
+
 /// Update the recipe detail panel for the selected recipe title.
+///
+/// `servings` is the session's chosen serving count for this recipe (from the
+/// scaler in the header); `None` shows the recipe as written. `in_basket` sets
+/// the state of the shopping basket toggle; `cooked` comes from the cooking log.
+/// The recipe image and images in the instructions are looked up under `data_dir`.
 pub fn update_recipe_detail(
     detail: &gtk::Box,
     dm: &Option<Rc<RefCell<DataManager>>>,
+    data_dir: &Path,
     title: &str,
+    servings: Option<u32>,
+    in_basket: bool,
+    cooked: Option<CookStats>,
     sender: &ComponentSender<App>,
 ) {
     let s = i18n::strings();
@@ -183,6 +810,25 @@ pub fn update_recipe_detail(
         return;
     };
 
+    // ── Hero image ────────────────────────────────────────────────────────────
+    if let Some(source) = images::recipe_image_path(data_dir, recipe) {
+        match images::cached_thumbnail(&source, images::HERO_SIZE) {
+            Some(path) => {
+                let picture = gtk::Picture::for_filename(&path);
+                picture.set_can_shrink(true);
+                picture.set_size_request(-1, 240);
+                picture.add_css_class("card");
+                detail.append(&picture);
+            }
+            None => {
+                let sender = sender.clone();
+                images::generate_thumbnails(vec![(source, images::HERO_SIZE)], move || {
+                    sender.input(AppMsg::ThumbnailsReady)
+                });
+            }
+        }
+    }
+
     // ── Header ────────────────────────────────────────────────────────────────
     let header_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
     header_box.set_halign(gtk::Align::Fill);
@@ -194,10 +840,88 @@ pub fn update_recipe_detail(
     title_label.set_wrap(true);
     header_box.append(&title_label);
 
-    // Edit/Delete buttons
+    // Cook mode/Cooked/Basket/Print/Export/Edit/Delete buttons
     let btn_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
     btn_box.set_valign(gtk::Align::Start);
 
+    let basket_btn = gtk::ToggleButton::new();
+    basket_btn.set_icon_name("view-list-bullet-symbolic");
+    basket_btn.add_css_class("flat");
+    basket_btn.set_active(in_basket);
+    basket_btn.set_tooltip_text(Some(if in_basket {
+        s.remove_from_basket
+    } else {
+        s.add_to_basket
+    }));
+    {
+        let sender_basket = sender.clone();
+        let title_clone = title.to_string();
+        basket_btn.connect_toggled(move |_| {
+            sender_basket.input(AppMsg::ToggleBasket(title_clone.clone()));
+        });
+    }
+
+    let cooked_btn = gtk::Button::with_label(s.cooked_it);
+    cooked_btn.add_css_class("flat");
+    cooked_btn.set_tooltip_text(Some(s.cooked_it_tooltip));
+    {
+        let sender_cooked = sender.clone();
+        let title_clone = title.to_string();
+        cooked_btn.connect_clicked(move |_| {
+            sender_cooked.input(AppMsg::CookRecipe(title_clone.clone()));
+        });
+    }
+
+    let cook_mode_btn = gtk::Button::from_icon_name("media-playback-start-symbolic");
+    cook_mode_btn.add_css_class("flat");
+    cook_mode_btn.set_tooltip_text(Some(s.cook_mode_tooltip));
+    cook_mode_btn.set_visible(!recipe.instructions.trim().is_empty());
+    {
+        let sender_cook_mode = sender.clone();
+        let title_clone = title.to_string();
+        cook_mode_btn.connect_clicked(move |_| {
+            sender_cook_mode.input(AppMsg::StartCookMode(title_clone.clone()));
+        });
+    }
+
+    let print_btn = gtk::Button::from_icon_name("document-print-symbolic");
+    print_btn.add_css_class("flat");
+    print_btn.set_tooltip_text(Some(s.print_tooltip));
+    {
+        let sender_print = sender.clone();
+        let title_clone = title.to_string();
+        print_btn.connect_clicked(move |_| {
+            sender_print.input(AppMsg::PrintRecipe(title_clone.clone()));
+        });
+    }
+
+    // Export menu: Cooklang or PDF
+    let export_btn = gtk::MenuButton::new();
+    export_btn.set_icon_name("document-save-as-symbolic");
+    export_btn.add_css_class("flat");
+    export_btn.set_tooltip_text(Some(s.export_menu_tooltip));
+    let export_popover = gtk::Popover::new();
+    let export_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    for (label, msg) in [
+        (s.export_cooklang_tooltip, AppMsg::ExportRecipe(title.to_string())),
+        (s.export_pdf, AppMsg::ExportRecipePdf(title.to_string())),
+    ] {
+        let item_label = gtk::Label::new(Some(label));
+        item_label.set_xalign(0.0);
+        let item = gtk::Button::new();
+        item.set_child(Some(&item_label));
+        item.add_css_class("flat");
+        let sender_export = sender.clone();
+        let popover = export_popover.clone();
+        item.connect_clicked(move |_| {
+            popover.popdown();
+            sender_export.input(msg.clone());
+        });
+        export_box.append(&item);
+    }
+    export_popover.set_child(Some(&export_box));
+    export_btn.set_popover(Some(&export_popover));
+
     let edit_btn = gtk::Button::with_label(s.edit);
     edit_btn.add_css_class("flat");
     {
@@ -220,6 +944,11 @@ pub fn update_recipe_detail(
         });
     }
 
+    btn_box.append(&cook_mode_btn);
+    btn_box.append(&cooked_btn);
+    btn_box.append(&basket_btn);
+    btn_box.append(&print_btn);
+    btn_box.append(&export_btn);
     btn_box.append(&edit_btn);
     btn_box.append(&delete_btn);
     header_box.append(&btn_box);
@@ -242,16 +971,56 @@ pub fn update_recipe_detail(
         meta_box.append(&label);
         has_meta = true;
     }
-    if let Some(servings) = recipe.servings {
-        let label = gtk::Label::new(Some(&i18n::fmt_servings(servings)));
-        label.add_css_class("caption");
-        meta_box.append(&label);
-        has_meta = true;
+    if let Some(st) = &cooked {
+        if let Some(days) = days_since(&st.last, chrono::Local::now().date_naive()) {
+            let label = gtk::Label::new(Some(&i18n::fmt_last_cooked(days, st.count)));
+            label.add_css_class("caption");
+            label.set_tooltip_text(Some(&st.last));
+            meta_box.append(&label);
+            has_meta = true;
+        }
     }
     if has_meta {
         detail.append(&meta_box);
     }
 
+    // ── Serving scaler ────────────────────────────────────────────────────────
+    // Recipes without a serving count scale as batches (×1, ×2, …).
+    let base_servings = recipe.servings.unwrap_or(1).max(1);
+    let target_servings = servings.unwrap_or(base_servings).max(1);
+    let factor = target_servings as f64 / base_servings as f64;
+
+    let scale_box = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    scale_box.set_halign(gtk::Align::Start);
+    let scale_label = gtk::Label::new(Some(if recipe.servings.is_some() {
+        s.servings_label
+    } else {
+        s.batches_label
+    }));
+    scale_label.add_css_class("caption");
+    scale_box.append(&scale_label);
+
+    let spin = gtk::SpinButton::with_range(1.0, 99.0, 1.0);
+    spin.set_value(target_servings as f64);
+    spin.set_valign(gtk::Align::Center);
+    spin.set_tooltip_text(Some(s.scale_tooltip));
+    scale_box.append(&spin);
+
+    let reset_btn = gtk::Button::from_icon_name("edit-undo-symbolic");
+    reset_btn.add_css_class("flat");
+    reset_btn.set_tooltip_text(Some(&i18n::fmt_reset_servings(base_servings)));
+    reset_btn.set_visible(target_servings != base_servings);
+    {
+        let spin = spin.downgrade();
+        reset_btn.connect_clicked(move |_| {
+            if let Some(spin) = spin.upgrade() {
+                spin.set_value(base_servings as f64);
+            }
+        });
+    }
+    scale_box.append(&reset_btn);
+    detail.append(&scale_box);
+
     // Tags
     if let Some(tags) = &recipe.tags {
         if !tags.is_empty() {
@@ -271,78 +1040,144 @@ pub fn update_recipe_detail(
     ing_header.set_halign(gtk::Align::Start);
     detail.append(&ing_header);
 
-    let cov = recipe.pantry_coverage(&dm);
+    // Coverage and amounts are refilled in place when the serving count
+    // changes, so the spin button keeps its focus and any half-typed number.
+    let ingredients_box = gtk::Box::new(gtk::Orientation::Vertical, detail.spacing());
+    fill_recipe_ingredients(&ingredients_box, &dm, recipe, factor);
+    detail.append(&ingredients_box);
+    {
+        let dm_rc = dm_rc.clone();
+        let title = title.to_string();
+        let sender = sender.clone();
+        let pending: Rc<RefCell<Option<glib::SourceId>>> = Rc::default();
+        spin.connect_value_changed(move |sp| {
+            let servings = sp.value_as_int().max(1) as u32;
+            reset_btn.set_visible(servings != base_servings);
+            if let Some(recipe) = dm_rc.borrow().get_recipe(&title) {
+                let factor = servings as f64 / base_servings as f64;
+                fill_recipe_ingredients(&ingredients_box, &dm_rc.borrow(), recipe, factor);
+            }
+            // Stored (and written to the shopping list) once the number settles
+            if let Some(id) = pending.borrow_mut().take() {
+                id.remove();
+            }
+            let sender = sender.clone();
+            let title = title.clone();
+            let fired = pending.clone();
+            let id = glib::timeout_add_local_once(SERVINGS_DEBOUNCE, move || {
+                fired.borrow_mut().take();
+                sender.input(AppMsg::SetRecipeServings { title, servings });
+            });
+            *pending.borrow_mut() = Some(id);
+        });
+    }
+
+    // ── Instructions ──────────────────────────────────────────────────────────
+    if !recipe.instructions.is_empty() {
+        detail.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        let instr_header = gtk::Label::new(Some(s.instructions_heading));
+        instr_header.add_css_class("heading");
+        instr_header.set_halign(gtk::Align::Start);
+        detail.append(&instr_header);
+
+        // Ingredients that exist in the database link to the Pantry tab, found
+        // by their stored, displayed and plural name
+        let mut links = Vec::new();
+        for ing in &recipe.ingredients {
+            if let Some(known) = dm.get_ingredient(&ing.ingredient) {
+                links.push((ing.ingredient.clone(), ing.ingredient.clone()));
+                links.push((dm.recipe_ingredient_display_name(ing), ing.ingredient.clone()));
+                if let Some(plural) = &known.plural {
+                    links.push((plural.clone(), ing.ingredient.clone()));
+                }
+            }
+        }
+        let sender_link = sender.clone();
+        let instructions = crate::markdown::render_markdown(
+            &recipe.instructions,
+            &links,
+            &data_dir.join("recipes"),
+            Rc::new(move |name| sender_link.input(AppMsg::ShowIngredient(name))),
+        );
+        detail.append(&instructions);
+    }
+}
+
+/// Fill `container` with the coverage summary and ingredient rows of `recipe`,
+/// scaled by `factor`.
+fn fill_recipe_ingredients(container: &gtk::Box, dm: &DataManager, recipe: &Recipe, factor: f64) {
+    let s = i18n::strings();
+    crate::utils::clear_box(container);
+
+    // Coverage against the (possibly scaled) amounts
+    let cov = recipe_coverage(dm, recipe, factor);
     let status_label = if cov.is_cookable() {
         let lbl = gtk::Label::new(Some(s.all_required_available));
         lbl.add_css_class("success");
         lbl
     } else {
-        let missing = cov.required_total - cov.required_in_stock;
-        let lbl = gtk::Label::new(Some(&i18n::fmt_missing_required(missing)));
+        let text = i18n::fmt_missing_required(cov.not_covered(), cov.partial);
+        let lbl = gtk::Label::new(Some(&text));
         lbl.add_css_class("dim-label");
         lbl
     };
     status_label.set_halign(gtk::Align::Start);
-    detail.append(&status_label);
+    container.append(&status_label);
 
     for ing in &recipe.ingredients {
-        let in_pantry = dm.is_in_pantry(&ing.ingredient);
+        let stock = ingredient_stock(dm, ing, factor);
+        let in_pantry = stock != Stock::Missing;
         let row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
         row.set_margin_start(DEFAULT_MARGIN);
 
-        let dot = if in_pantry {
-            let d = gtk::Label::new(Some("●"));
-            d.add_css_class("success");
-            d
-        } else if ing.optional {
-            let d = gtk::Label::new(Some("○"));
-            d.add_css_class("dim-label");
-            d
-        } else {
-            let d = gtk::Label::new(Some("○"));
-            d.add_css_class("error");
-            d
+        let (qty, unit) = match &ing.quantity {
+            Some(q) => {
+                let (q, u) = display_quantity(q, ing.quantity_type.as_deref(), factor);
+                (Some(q), u)
+            }
+            None => (None, ing.quantity_type.clone()),
         };
-        row.append(&dot);
 
-        let display_name = dm.recipe_ingredient_display_name(ing);
-        let mut qty_str = match (&ing.quantity, &ing.quantity_type) {
-            (Some(q), Some(u)) if !u.is_empty() => format!("{} {} {}", q, u, display_name),
-            (Some(q), _) => format!("{} {}", q, display_name),
-            _ => display_name,
+        let dot = match stock {
+            Stock::Covered => {
+                let d = gtk::Label::new(Some("●"));
+                d.add_css_class("success");
+                d
+            }
+            Stock::Partial => {
+                let d = gtk::Label::new(Some("◐"));
+                d.add_css_class("warning");
+                if let (Some(item), Some(q)) = (dm.get_pantry_item(&ing.ingredient), &qty) {
+                    let (have_qty, have_unit) =
+                        localize(item.quantity.unwrap_or(0.0), &item.quantity_type);
+                    let have = format!("{} {}", format_amount(have_qty), have_unit);
+                    let need = format!("{} {}", q, unit.as_deref().unwrap_or(""));
+                    d.set_tooltip_text(Some(&i18n::fmt_partial_stock(have.trim(), need.trim())));
+                }
+                d
+            }
+            Stock::Missing if ing.optional => {
+                let d = gtk::Label::new(Some("○"));
+                d.add_css_class("dim-label");
+                d
+            }
+            Stock::Missing => {
+                let d = gtk::Label::new(Some("○"));
+                d.add_css_class("error");
+                d
+            }
         };
-        if let Some(note) = &ing.note {
-            qty_str = format!("{} ({})", qty_str, note);
-        }
-        if ing.optional {
-            qty_str = format!("{}{}", qty_str, s.optional_suffix);
-        }
-        let label = gtk::Label::new(Some(&qty_str));
+        row.append(&dot);
+
+        let label = gtk::Label::new(Some(&ingredient_text(dm, ing, factor)));
         label.set_halign(gtk::Align::Start);
         if !in_pantry {
             label.add_css_class("dim-label");
         }
         row.append(&label);
 
-        detail.append(&row);
-    }
-
-    // ── Instructions ──────────────────────────────────────────────────────────
-    if !recipe.instructions.is_empty() {
-        detail.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
-
-        let instr_header = gtk::Label::new(Some(s.instructions_heading));
-        instr_header.add_css_class("heading");
-        instr_header.set_halign(gtk::Align::Start);
-        detail.append(&instr_header);
-
-        let instr_label = gtk::Label::new(Some(&recipe.instructions));
-        instr_label.set_halign(gtk::Align::Start);
-        instr_label.set_wrap(true);
-        instr_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
-        instr_label.set_selectable(true);
-        instr_label.set_xalign(0.0);
-        detail.append(&instr_label);
+        container.append(&row);
     }
 }
 
@@ -386,6 +1221,66 @@ pub fn show_recipe_placeholder(detail: &gtk::Box) {
     detail.append(&status);
 }
 
+/// Section header of the grouped list, styled like the pantry's category headers.
+fn build_group_header_row(title: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    row.set_widget_name("__header__");
+    row.set_activatable(false);
+    row.set_selectable(false);
+
+    let label = gtk::Label::new(Some(title));
+    label.add_css_class("heading");
+    label.set_halign(gtk::Align::Start);
+    label.set_margin_top(DEFAULT_MARGIN);
+    label.set_margin_bottom(ROW_SPACING);
+    label.set_margin_start(DEFAULT_MARGIN);
+    label.set_margin_end(DEFAULT_MARGIN);
+    row.set_child(Some(&label));
+    row
+}
+
+/// Empty state when the filters leave nothing: each active filter with how many
+/// recipes it allows on its own, and a way to clear them.
+fn filtered_empty_row(
+    reasons: &[(crate::recipe_filter::Criterion, usize)],
+    sender: &ComponentSender<App>,
+) -> gtk::ListBoxRow {
+    let s = i18n::strings();
+    let row = gtk::ListBoxRow::new();
+    row.set_widget_name("__empty__");
+    row.set_activatable(false);
+    row.set_selectable(false);
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, ROW_SPACING);
+    content.set_margin_all(DEFAULT_MARGIN);
+    let heading = gtk::Label::new(Some(s.no_recipes_match));
+    heading.add_css_class("dim-label");
+    content.append(&heading);
+    for (criterion, count) in reasons {
+        let label = gtk::Label::new(Some(&i18n::fmt_filter_reason(criterion, *count)));
+        label.add_css_class("caption");
+        label.add_css_class("dim-label");
+        if *count == 0 {
+            label.add_css_class("error");
+        }
+        label.set_wrap(true);
+        label.set_halign(gtk::Align::Start);
+        content.append(&label);
+    }
+
+    let clear_btn = gtk::Button::with_label(s.clear_filters);
+    clear_btn.set_halign(gtk::Align::Center);
+    {
+        let sender = sender.clone();
+        clear_btn.connect_clicked(move |_| {
+            sender.input(AppMsg::UpdateRecipeFilter(FilterChange::Clear));
+        });
+    }
+    content.append(&clear_btn);
+    row.set_child(Some(&content));
+    row
+}
+
 fn empty_state_row(text: &str) -> gtk::ListBoxRow {
     let row = gtk::ListBoxRow::new();
     row.set_widget_name("__empty__");
@@ -1,5 +1,5 @@
 use crate::app::AppMsg;
-use crate::config::{Theme, UserSettings};
+use crate::config::{MeasurementSystem, Theme, UserSettings};
 use crate::i18n;
 use libadwaita as adw;
 use relm4::gtk;
@@ -131,6 +131,33 @@ pub fn build_settings_page(sender: &ComponentSender<crate::app::App>) -> gtk::Wi
     lang_group.add(&lang_row);
     page.add(&lang_group);
 
+    // ── Units group ───────────────────────────────────────────────────────────
+    let units_group = adw::PreferencesGroup::new();
+    units_group.set_title(s.settings_group_units);
+
+    let system_row = adw::ComboRow::new();
+    system_row.set_title(s.settings_measurement_system);
+    let system_model = gtk::StringList::new(&[s.system_metric, s.system_imperial]);
+    system_row.set_model(Some(&system_model));
+    system_row.set_selected(match settings.measurement_system {
+        MeasurementSystem::Metric => 0,
+        MeasurementSystem::Imperial => 1,
+    });
+
+    {
+        let sender_system = sender.clone();
+        system_row.connect_selected_notify(move |row| {
+            let system = match row.selected() {
+                1 => "Imperial",
+                _ => "Metric",
+            };
+            sender_system.input(AppMsg::SetMeasurementSystem(system.to_string()));
+        });
+    }
+
+    units_group.add(&system_row);
+    page.add(&units_group);
+
     scroll.set_child(Some(&page));
     scroll.upcast()
 }
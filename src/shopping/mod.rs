@@ -0,0 +1,186 @@
+/// Shopping tab: the "cooking this week" basket and the shopping list built from it.
+use crate::app::{App, AppMsg};
+use crate::i18n;
+use crate::quantity::format_amount;
+use crate::shopping_list::{ShoppingItem, ShoppingList};
+use crate::ui_constants::*;
+use libadwaita as adw;
+use relm4::gtk;
+use relm4::{gtk::prelude::*, ComponentSender, RelmWidgetExt};
+
+/// Build the full Shopping tab widget.
+///
+/// Returns `(tab_widget, content_box)`; the content is filled by `update_shopping_view`.
+pub fn build_shopping_tab() -> (gtk::Widget, gtk::Box) {
+    let scroll = gtk::ScrolledWindow::new();
+    scroll.set_hexpand(true);
+    scroll.set_vexpand(true);
+    scroll.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+    content.set_margin_all(DEFAULT_MARGIN);
+    scroll.set_child(Some(&content));
+
+    (scroll.upcast(), content)
+}
+
+/// Rebuild the Shopping tab from the current list.
+pub fn update_shopping_view(
+    content: &gtk::Box,
+    list: &ShoppingList,
+    sender: &ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    crate::utils::clear_box(content);
+
+    if list.basket.is_empty() && list.items.is_empty() {
+        let status = adw::StatusPage::new();
+        status.set_icon_name(Some("view-list-bullet-symbolic"));
+        status.set_title(s.basket_empty_title);
+        status.set_description(Some(s.basket_empty_desc));
+        status.set_vexpand(true);
+        content.append(&status);
+        return;
+    }
+
+    // ── Basket ────────────────────────────────────────────────────────────────
+    let basket_header = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    let basket_label = gtk::Label::new(Some(s.basket_heading));
+    basket_label.add_css_class("heading");
+    basket_label.set_halign(gtk::Align::Start);
+    basket_label.set_hexpand(true);
+    basket_header.append(&basket_label);
+
+    let clear_btn = gtk::Button::with_label(s.clear_basket);
+    clear_btn.add_css_class("flat");
+    {
+        let sender_clear = sender.clone();
+        clear_btn.connect_clicked(move |_| sender_clear.input(AppMsg::ClearShoppingList));
+    }
+    basket_header.append(&clear_btn);
+    content.append(&basket_header);
+
+    for entry in &list.basket {
+        let row = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+        row.set_margin_start(DEFAULT_MARGIN);
+
+        let title = gtk::Label::new(Some(&entry.title));
+        title.set_halign(gtk::Align::Start);
+        row.append(&title);
+
+        if let Some(servings) = entry.servings {
+            let caption = gtk::Label::new(Some(&i18n::fmt_servings(servings)));
+            caption.add_css_class("caption");
+            caption.add_css_class("dim-label");
+            row.append(&caption);
+        }
+
+        let spacer = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        spacer.set_hexpand(true);
+        row.append(&spacer);
+
+        let remove_btn = gtk::Button::from_icon_name("list-remove-symbolic");
+        remove_btn.add_css_class("flat");
+        remove_btn.set_tooltip_text(Some(s.remove_from_basket));
+        {
+            let sender_remove = sender.clone();
+            let title_clone = entry.title.clone();
+            remove_btn.connect_clicked(move |_| {
+                sender_remove.input(AppMsg::RemoveFromBasket(title_clone.clone()));
+            });
+        }
+        row.append(&remove_btn);
+        content.append(&row);
+    }
+
+    content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+    // ── Shopping list ─────────────────────────────────────────────────────────
+    let list_header = gtk::Box::new(gtk::Orientation::Horizontal, ROW_SPACING);
+    let list_label = gtk::Label::new(Some(s.shopping_list_heading));
+    list_label.add_css_class("heading");
+    list_label.set_halign(gtk::Align::Start);
+    list_label.set_hexpand(true);
+    list_header.append(&list_label);
+
+    let rebuild_btn = gtk::Button::from_icon_name("view-refresh-symbolic");
+    rebuild_btn.add_css_class("flat");
+    rebuild_btn.set_tooltip_text(Some(s.rebuild_list));
+    {
+        let sender_rebuild = sender.clone();
+        rebuild_btn.connect_clicked(move |_| sender_rebuild.input(AppMsg::RebuildShoppingList));
+    }
+    list_header.append(&rebuild_btn);
+    content.append(&list_header);
+
+    if list.items.is_empty() {
+        let done = gtk::Label::new(Some(s.shopping_all_covered));
+        done.add_css_class("success");
+        done.set_halign(gtk::Align::Start);
+        content.append(&done);
+        return;
+    }
+
+    for (category, indices) in list.grouped() {
+        let cat = if category.is_empty() { s.uncategorised } else { category.as_str() };
+        let cat_label = gtk::Label::new(Some(cat));
+        cat_label.add_css_class("caption-heading");
+        cat_label.add_css_class("dim-label");
+        cat_label.set_halign(gtk::Align::Start);
+        cat_label.set_margin_top(ROW_SPACING);
+        content.append(&cat_label);
+
+        for idx in indices {
+            content.append(&build_item_row(idx, &list.items[idx], sender));
+        }
+    }
+
+    let move_btn = gtk::Button::with_label(s.move_to_pantry);
+    move_btn.add_css_class("suggested-action");
+    move_btn.set_halign(gtk::Align::End);
+    move_btn.set_sensitive(list.items.iter().any(|i| i.checked));
+    {
+        let sender_move = sender.clone();
+        move_btn.connect_clicked(move |_| sender_move.input(AppMsg::MoveCheckedToPantry));
+    }
+    content.append(&move_btn);
+}
+
+fn build_item_row(idx: usize, item: &ShoppingItem, sender: &ComponentSender<App>) -> gtk::Box {
+    let row = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    row.set_margin_start(DEFAULT_MARGIN);
+
+    let text = match item.quantity {
+        Some(q) => {
+            let (q, unit) = crate::units::localize(q, &item.quantity_type);
+            if unit.is_empty() {
+                format!("{} {}", format_amount(q), item.ingredient)
+            } else {
+                format!("{} {} {}", format_amount(q), unit, item.ingredient)
+            }
+        }
+        None => item.ingredient.clone(),
+    };
+    let check = gtk::CheckButton::with_label(&text);
+    check.set_active(item.checked);
+    {
+        let sender_check = sender.clone();
+        check.connect_toggled(move |c| {
+            sender_check.input(AppMsg::SetShoppingItemChecked(idx, c.is_active()));
+        });
+    }
+    row.append(&check);
+
+    let from_text = if item.recipes.is_empty() {
+        i18n::strings().restock_caption.to_string()
+    } else {
+        item.recipes.join(", ")
+    };
+    let from = gtk::Label::new(Some(&from_text));
+    from.add_css_class("caption");
+    from.add_css_class("dim-label");
+    from.set_halign(gtk::Align::Start);
+    from.set_margin_start(28);
+    row.append(&from);
+    row
+}
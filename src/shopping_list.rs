@@ -0,0 +1,308 @@
+/// Shopping list built from a basket of recipes ("cooking this week").
+///
+/// The basket and the generated list are stored together in `shopping.yaml` in
+/// the data directory, so they sync between devices like the rest of the data.
+/// Each item is a required ingredient the pantry does not cover, with amounts
+/// summed across the basket's recipes and the pantry amount subtracted, or a
+/// restock of an ingredient that has fallen below its low-stock threshold.
+use crate::quantity::{parse_amount, round_for_unit};
+use crate::units::{convert_with, ingredient_units};
+use janus_engine::DataManager;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// File name of the shopping list inside the data directory.
+pub const SHOPPING_FILE: &str = "shopping.yaml";
+
+/// A recipe planned for cooking, with the serving count to shop for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BasketEntry {
+    pub title: String,
+    /// `None` = as written in the recipe.
+    #[serde(default)]
+    pub servings: Option<u32>,
+}
+
+/// One line of the shopping list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShoppingItem {
+    pub ingredient: String,
+    #[serde(default)]
+    pub quantity: Option<f64>,
+    #[serde(default)]
+    pub quantity_type: String,
+    /// Ingredient category, for grouping (empty = uncategorised).
+    #[serde(default)]
+    pub category: String,
+    /// Titles of the basket recipes that need this item (empty for restocks).
+    #[serde(default)]
+    pub recipes: Vec<String>,
+    #[serde(default)]
+    pub checked: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShoppingList {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub basket: Vec<BasketEntry>,
+    #[serde(default)]
+    pub items: Vec<ShoppingItem>,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for ShoppingList {
+    fn default() -> Self {
+        ShoppingList {
+            version: default_version(),
+            basket: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+}
+
+impl ShoppingList {
+    /// Load the list from `data_dir`. A missing file is an empty list.
+    pub fn load(data_dir: &Path) -> Result<Self, String> {
+        let path = data_dir.join(SHOPPING_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                serde_yaml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ShoppingList::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join(SHOPPING_FILE);
+        let text = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
+        crate::utils::write_atomically(&path, &text)
+    }
+
+    pub fn contains(&self, title: &str) -> bool {
+        self.basket.iter().any(|b| b.title == title)
+    }
+
+    /// Add a recipe to the basket, or update its serving count if already there.
+    pub fn add_recipe(&mut self, title: &str, servings: Option<u32>) {
+        match self.basket.iter_mut().find(|b| b.title == title) {
+            Some(entry) => entry.servings = servings,
+            None => self.basket.push(BasketEntry {
+                title: title.to_string(),
+                servings,
+            }),
+        }
+    }
+
+    pub fn remove_recipe(&mut self, title: &str) {
+        self.basket.retain(|b| b.title != title);
+    }
+
+    /// Follow a recipe rename.
+    pub fn rename_recipe(&mut self, old: &str, new: &str) {
+        for entry in self.basket.iter_mut().filter(|b| b.title == old) {
+            entry.title = new.to_string();
+        }
+        for item in &mut self.items {
+            for title in item.recipes.iter_mut().filter(|t| *t == old) {
+                *title = new.to_string();
+            }
+        }
+    }
+
+    /// Add an item that no basket recipe asked for (a restock). Restocks stay
+    /// separate from the basket's lines, so regenerating keeps them; adding one
+    /// that is already listed keeps the larger amount.
+    pub fn add_restock(&mut self, item: ShoppingItem) {
+        let props = ingredient_units(&item.ingredient);
+        let existing = self.items.iter_mut().find(|i| {
+            i.recipes.is_empty()
+                && i.ingredient == item.ingredient
+                && match (i.quantity, item.quantity) {
+                    (Some(_), Some(q)) => {
+                        convert_with(q, &item.quantity_type, &i.quantity_type, props).is_some()
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+        });
+        match existing {
+            Some(i) => {
+                if let (Some(have), Some(q)) = (i.quantity, item.quantity) {
+                    let q = convert_with(q, &item.quantity_type, &i.quantity_type, props)
+                        .unwrap_or(q);
+                    i.quantity = Some(have.max(q));
+                }
+            }
+            None => self.items.push(item),
+        }
+    }
+
+    /// Rebuild the items from the basket and the current pantry.
+    ///
+    /// Items that were checked off keep their check mark, and restocks are kept
+    /// as they are. Basket recipes that no longer exist are skipped.
+    pub fn regenerate(&mut self, dm: &DataManager) {
+        let restocks: Vec<ShoppingItem> =
+            self.items.iter().filter(|i| i.recipes.is_empty()).cloned().collect();
+        let checked: BTreeSet<String> = self
+            .items
+            .iter()
+            .filter(|i| i.checked)
+            .map(|i| i.ingredient.to_lowercase())
+            .collect();
+
+        // Sum what the basket needs, per ingredient and (convertible) unit
+        let mut needs: Vec<ShoppingItem> = Vec::new();
+        for entry in &self.basket {
+            let Some(recipe) = dm.get_recipe(&entry.title) else {
+                continue;
+            };
+            let base = recipe.servings.unwrap_or(1).max(1);
+            let factor = entry.servings.unwrap_or(base).max(1) as f64 / base as f64;
+            for ing in recipe.ingredients.iter().filter(|i| !i.optional) {
+                let unit = ing.quantity_type.clone().unwrap_or_default();
+                let amount = ing
+                    .quantity
+                    .as_deref()
+                    .and_then(parse_amount)
+                    .map(|a| a.max() * factor);
+                add_need(&mut needs, &ing.ingredient, amount, &unit, &recipe.title);
+            }
+        }
+
+        // Subtract what the pantry already has
+        self.items = needs
+            .into_iter()
+            .filter_map(|mut item| {
+                let remaining = remaining_need(dm, &item)?;
+                item.quantity = remaining.map(|q| round_for_unit(q, Some(&item.quantity_type)));
+                item.category = dm
+                    .get_ingredient(&item.ingredient)
+                    .map(|i| i.category.clone())
+                    .unwrap_or_default();
+                item.checked = checked.contains(&item.ingredient.to_lowercase());
+                Some(item)
+            })
+            .collect();
+        self.items.extend(restocks);
+    }
+
+    /// Items grouped by category, categories sorted with uncategorised last.
+    /// Indices refer to `self.items`.
+    pub fn grouped(&self) -> Vec<(String, Vec<usize>)> {
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for (idx, item) in self.items.iter().enumerate() {
+            match groups.iter_mut().find(|(c, _)| *c == item.category) {
+                Some((_, indices)) => indices.push(idx),
+                None => groups.push((item.category.clone(), vec![idx])),
+            }
+        }
+        groups.sort_by_key(|(c, _)| (c.is_empty(), c.to_lowercase()));
+        for (_, indices) in &mut groups {
+            indices.sort_by_key(|&i| self.items[i].ingredient.to_lowercase());
+        }
+        groups
+    }
+
+    /// Remove and return the checked-off items.
+    pub fn take_checked(&mut self) -> Vec<ShoppingItem> {
+        let (checked, open): (Vec<_>, Vec<_>) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition(|i| i.checked);
+        self.items = open;
+        checked
+    }
+}
+
+/// Pantry quantity and unit for `item` once it has been bought.
+///
+/// The bought amount is added to what the pantry holds when the units convert;
+/// otherwise the pantry entry is replaced with the bought amount.
+pub fn pantry_after_purchase(dm: &DataManager, item: &ShoppingItem) -> (Option<f64>, String) {
+    let bought = (item.quantity, item.quantity_type.clone());
+    if !dm.is_in_pantry(&item.ingredient) {
+        return bought;
+    }
+    let Some(pantry) = dm.get_pantry_item(&item.ingredient) else {
+        return bought;
+    };
+    let (Some(have), Some(q)) = (pantry.quantity, item.quantity) else {
+        return bought;
+    };
+    match convert_with(
+        q,
+        &item.quantity_type,
+        &pantry.quantity_type,
+        ingredient_units(&item.ingredient),
+    ) {
+        Some(extra) => (
+            Some(round_for_unit(have + extra, Some(&pantry.quantity_type))),
+            pantry.quantity_type.clone(),
+        ),
+        None => bought,
+    }
+}
+
+/// Add `amount` `unit` of `ingredient` for `recipe` to `needs`, merging with an
+/// existing entry whose unit it converts to.
+fn add_need(
+    needs: &mut Vec<ShoppingItem>,
+    ingredient: &str,
+    amount: Option<f64>,
+    unit: &str,
+    recipe: &str,
+) {
+    let props = ingredient_units(ingredient);
+    let existing = needs.iter_mut().find(|n| {
+        n.ingredient == ingredient
+            && match (n.quantity, amount) {
+                (Some(_), Some(a)) => convert_with(a, unit, &n.quantity_type, props).is_some(),
+                (None, None) => true,
+                _ => false,
+            }
+    });
+    match existing {
+        Some(n) => {
+            if let (Some(q), Some(a)) = (n.quantity, amount) {
+                n.quantity = convert_with(a, unit, &n.quantity_type, props).map(|a| q + a);
+            }
+            if !n.recipes.iter().any(|r| r == recipe) {
+                n.recipes.push(recipe.to_string());
+            }
+        }
+        None => needs.push(ShoppingItem {
+            ingredient: ingredient.to_string(),
+            quantity: amount,
+            quantity_type: unit.to_string(),
+            category: String::new(),
+            recipes: vec![recipe.to_string()],
+            checked: false,
+        }),
+    }
+}
+
+/// What is still to buy after the pantry: `None` if the pantry covers it,
+/// `Some(None)` for an item without an amount, `Some(Some(q))` for `q` more.
+fn remaining_need(dm: &DataManager, item: &ShoppingItem) -> Option<Option<f64>> {
+    if !dm.is_in_pantry(&item.ingredient) {
+        return Some(item.quantity);
+    }
+    let need = item.quantity?;
+    let pantry = dm.get_pantry_item(&item.ingredient)?;
+    let have = pantry.quantity.and_then(|q| {
+        convert_with(
+            q,
+            &pantry.quantity_type,
+            &item.quantity_type,
+            ingredient_units(&item.ingredient),
+        )
+    })?;
+    (have < need).then_some(Some(need - have))
+}
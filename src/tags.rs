@@ -0,0 +1,26 @@
+/// Tags on recipes and ingredients, compared the way ingredient names are:
+/// through `fold_for_matching`, so case variants are one tag.
+use janus_engine::fold_for_matching;
+use std::collections::HashSet;
+
+/// Whether the tag list of a recipe or ingredient holds `tag`, ignoring case
+/// (also of non-ASCII letters, like "Æg" and "æg").
+pub fn has_tag(tags: &Option<Vec<String>>, tag: &str) -> bool {
+    let tag = fold_for_matching(tag);
+    tags.as_ref()
+        .is_some_and(|tags| tags.iter().any(|t| fold_for_matching(t) == tag))
+}
+
+/// Every tag in the tag lists of some recipes or ingredients, sorted, with case
+/// variants merged (the first spelling wins).
+pub fn all_tags<'a>(lists: impl IntoIterator<Item = &'a Option<Vec<String>>>) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for tag in lists.into_iter().flatten().flatten() {
+        if seen.insert(fold_for_matching(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags
+}
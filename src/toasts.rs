@@ -0,0 +1,113 @@
+/// In-app notifications shown through the window's `adw::ToastOverlay`.
+///
+/// `update()` queues `ToastRequest`s on the model; `update_view()` drains the queue
+/// and turns each request into an `adw::Toast`. The overlay shows one toast at a
+/// time and keeps the rest in its own queue, with high-priority toasts jumping ahead.
+use crate::app::{App, AppMsg};
+use crate::i18n;
+use libadwaita as adw;
+use relm4::ComponentSender;
+
+/// Seconds an informational toast stays visible.
+const INFO_TIMEOUT: u32 = 3;
+/// Seconds an undo toast stays visible (long enough to react to a mis-click).
+const UNDO_TIMEOUT: u32 = 6;
+/// Seconds an error toast stays visible.
+const ERROR_TIMEOUT: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastKind {
+    /// Plain status message.
+    Info,
+    /// Offers an "Undo" button that sends `AppMsg::UndoChange` with the id of
+    /// the change the toast describes.
+    Undo { change: u64 },
+    /// High priority; offers a "Details" button showing the full error chain.
+    Error { details: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastRequest {
+    pub text: String,
+    pub kind: ToastKind,
+}
+
+impl ToastRequest {
+    pub fn info(text: impl Into<String>) -> Self {
+        ToastRequest { text: text.into(), kind: ToastKind::Info }
+    }
+
+    pub fn undo(text: impl Into<String>, change: u64) -> Self {
+        ToastRequest { text: text.into(), kind: ToastKind::Undo { change } }
+    }
+
+    pub fn error(text: impl Into<String>, details: impl Into<String>) -> Self {
+        ToastRequest {
+            text: text.into(),
+            kind: ToastKind::Error { details: details.into() },
+        }
+    }
+
+    /// Timeout in seconds for this kind of toast.
+    pub fn timeout(&self) -> u32 {
+        match self.kind {
+            ToastKind::Info => INFO_TIMEOUT,
+            ToastKind::Undo { .. } => UNDO_TIMEOUT,
+            ToastKind::Error { .. } => ERROR_TIMEOUT,
+        }
+    }
+
+    pub fn is_high_priority(&self) -> bool {
+        matches!(self.kind, ToastKind::Error { .. })
+    }
+}
+
+/// Show `request` in `overlay`. `window` is the parent for the error details dialog.
+pub fn present(
+    overlay: &adw::ToastOverlay,
+    window: &adw::ApplicationWindow,
+    request: ToastRequest,
+    sender: &ComponentSender<App>,
+) {
+    let s = i18n::strings();
+    let toast = adw::Toast::new(&request.text);
+    // Titles contain user data (recipe names, error text) — never parse as markup.
+    toast.set_use_markup(false);
+    toast.set_timeout(request.timeout());
+    toast.set_priority(if request.is_high_priority() {
+        adw::ToastPriority::High
+    } else {
+        adw::ToastPriority::Normal
+    });
+
+    match request.kind {
+        ToastKind::Info => {}
+        ToastKind::Undo { change } => {
+            toast.set_button_label(Some(s.undo));
+            let sender_undo = sender.clone();
+            toast.connect_button_clicked(move |_| sender_undo.input(AppMsg::UndoChange(change)));
+        }
+        ToastKind::Error { details } => {
+            toast.set_button_label(Some(s.details));
+            let window = window.clone();
+            let summary = request.text.clone();
+            toast.connect_button_clicked(move |_| {
+                crate::dialogs::show_error_details(&window, &summary, &details);
+            });
+        }
+    }
+
+    overlay.add_toast(toast);
+}
+
+/// Render an error and all of its `source()`s, one per line.
+pub fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut out = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        out.push_str("\n  caused by: ");
+        out.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    out
+}
@@ -0,0 +1,292 @@
+/// Unit registry: canonical units, their aliases, and conversions between them.
+///
+/// Mass converts via grams, volume via millilitres and count via pieces. An
+/// ingredient's density or piece weight, if set in its YAML file, bridges the
+/// dimensions ("1 dl flour" ↔ grams, "2 egg" ↔ grams). A global preferred
+/// `MeasurementSystem` (like the active language in `i18n`) decides whether
+/// metric or US customary amounts are shown.
+use crate::config::MeasurementSystem;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+// ── Registry ──────────────────────────────────────────────────────────────────
+
+/// What a unit measures; units of the same dimension convert into each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Mass,
+    Volume,
+    Count,
+}
+
+/// Alias → canonical unit. The metric canonical units are the ones offered by
+/// `i18n::suggested_units()`; English and Danish aliases keep their own language.
+const UNIT_ALIASES: &[(&str, &str)] = &[
+    // English
+    ("pcs", "pcs"), ("pc", "pcs"), ("piece", "pcs"), ("pieces", "pcs"),
+    ("kg", "kg"), ("kilo", "kg"), ("kilos", "kg"), ("kilogram", "kg"), ("kilograms", "kg"),
+    ("g", "g"), ("gr", "g"), ("gram", "g"), ("grams", "g"),
+    ("l", "l"), ("liter", "l"), ("litre", "l"), ("liters", "l"), ("litres", "l"),
+    ("dl", "dl"), ("deciliter", "dl"), ("decilitre", "dl"),
+    ("ml", "ml"), ("milliliter", "ml"), ("millilitre", "ml"),
+    ("can", "can"), ("cans", "can"), ("tin", "can"), ("tins", "can"),
+    ("pack", "pack"), ("packs", "pack"), ("package", "pack"), ("packages", "pack"),
+    ("bag", "bag"), ("bags", "bag"),
+    ("bottle", "bottle"), ("bottles", "bottle"),
+    ("carton", "carton"), ("cartons", "carton"),
+    ("tsp", "tsp"), ("teaspoon", "tsp"), ("teaspoons", "tsp"),
+    ("tbsp", "tbsp"), ("tbs", "tbsp"), ("tablespoon", "tbsp"), ("tablespoons", "tbsp"),
+    ("pinch", "pinch"), ("pinches", "pinch"),
+    // US customary
+    ("oz", "oz"), ("ounce", "oz"), ("ounces", "oz"),
+    ("lb", "lb"), ("lbs", "lb"), ("pound", "lb"), ("pounds", "lb"),
+    ("fl oz", "fl oz"), ("floz", "fl oz"), ("fluid ounce", "fl oz"), ("fluid ounces", "fl oz"),
+    ("cup", "cup"), ("cups", "cup"),
+    ("pt", "pint"), ("pint", "pint"), ("pints", "pint"),
+    ("qt", "quart"), ("quart", "quart"), ("quarts", "quart"),
+    ("gal", "gallon"), ("gallon", "gallon"), ("gallons", "gallon"),
+    // Danish
+    ("stk", "stk"), ("styk", "stk"), ("stykker", "stk"),
+    ("dåse", "dåse"), ("dåser", "dåse"),
+    ("pakke", "pakke"), ("pakker", "pakke"),
+    ("pose", "pose"), ("poser", "pose"),
+    ("flaske", "flaske"), ("flasker", "flaske"),
+    ("karton", "karton"), ("kartoner", "karton"),
+    ("tsk", "tsk"), ("teske", "tsk"), ("teskefuld", "tsk"),
+    ("spsk", "spsk"), ("spiseske", "spsk"), ("spiseskefuld", "spsk"),
+    ("nip", "nip"), ("knivspids", "nip"),
+];
+
+/// Canonical unit, dimension, size in the base unit (g, ml, pieces), and the
+/// measurement system it belongs to (`None` = used by both, like spoons).
+type UnitDef = (&'static str, Dimension, f64, Option<MeasurementSystem>);
+
+/// Units with a fixed size. Containers (can, pack, …) and pinches are left out.
+const UNIT_SIZES: &[UnitDef] = &[
+    ("g", Dimension::Mass, 1.0, Some(MeasurementSystem::Metric)),
+    ("kg", Dimension::Mass, 1000.0, Some(MeasurementSystem::Metric)),
+    ("oz", Dimension::Mass, 28.349_523_125, Some(MeasurementSystem::Imperial)),
+    ("lb", Dimension::Mass, 453.592_37, Some(MeasurementSystem::Imperial)),
+    ("ml", Dimension::Volume, 1.0, Some(MeasurementSystem::Metric)),
+    ("dl", Dimension::Volume, 100.0, Some(MeasurementSystem::Metric)),
+    ("l", Dimension::Volume, 1000.0, Some(MeasurementSystem::Metric)),
+    ("fl oz", Dimension::Volume, 29.573_529_6, Some(MeasurementSystem::Imperial)),
+    ("cup", Dimension::Volume, 236.588_236_5, Some(MeasurementSystem::Imperial)),
+    ("pint", Dimension::Volume, 473.176_473, Some(MeasurementSystem::Imperial)),
+    ("quart", Dimension::Volume, 946.352_946, Some(MeasurementSystem::Imperial)),
+    ("gallon", Dimension::Volume, 3785.411_784, Some(MeasurementSystem::Imperial)),
+    ("tsp", Dimension::Volume, 5.0, None),
+    ("tsk", Dimension::Volume, 5.0, None),
+    ("tbsp", Dimension::Volume, 15.0, None),
+    ("spsk", Dimension::Volume, 15.0, None),
+    ("pcs", Dimension::Count, 1.0, None),
+    ("stk", Dimension::Count, 1.0, None),
+];
+
+/// Look up the canonical form of a unit ("Tablespoons" → "tbsp", "kg." → "kg").
+pub fn canonical_unit(unit: &str) -> Option<&'static str> {
+    let key = unit.trim().trim_end_matches('.').to_lowercase();
+    UNIT_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map(|(_, canonical)| *canonical)
+}
+
+/// Key used to compare units: the canonical form if known, an empty unit counts
+/// as pieces ("2 tomato"), anything else is compared case-insensitively.
+fn unit_key(unit: &str) -> String {
+    if unit.trim().is_empty() {
+        return "pcs".to_string();
+    }
+    canonical_unit(unit)
+        .map(str::to_string)
+        .unwrap_or_else(|| unit.trim().to_lowercase())
+}
+
+fn unit_def(unit: &str) -> Option<&'static UnitDef> {
+    let key = unit_key(unit);
+    UNIT_SIZES.iter().find(|(u, ..)| *u == key)
+}
+
+/// Dimension and base-unit size of a unit, if it has a fixed size.
+pub fn unit_size(unit: &str) -> Option<(Dimension, f64)> {
+    unit_def(unit).map(|(_, dim, size, _)| (*dim, *size))
+}
+
+/// Measurement system a unit belongs to (`None` for spoons, pieces and unknown units).
+pub fn unit_system(unit: &str) -> Option<MeasurementSystem> {
+    unit_def(unit).and_then(|(.., system)| *system)
+}
+
+/// Convert `value` from one unit to another ("200 g" → 0.2 "kg").
+///
+/// Returns `None` when the units measure different things (kg vs dl) or one of
+/// them has no fixed size and they are not the same unit.
+pub fn convert(value: f64, from: &str, to: &str) -> Option<f64> {
+    if unit_key(from) == unit_key(to) {
+        return Some(value);
+    }
+    let (from_dim, from_size) = unit_size(from)?;
+    let (to_dim, to_size) = unit_size(to)?;
+    (from_dim == to_dim).then(|| value * from_size / to_size)
+}
+
+/// Like `convert`, but also crosses between mass, volume and count using the
+/// ingredient's density and piece weight when they are known.
+pub fn convert_with(value: f64, from: &str, to: &str, props: IngredientUnits) -> Option<f64> {
+    if let Some(v) = convert(value, from, to) {
+        return Some(v);
+    }
+    let grams = props.grams_per(from)? * value;
+    Some(grams / props.grams_per(to)?)
+}
+
+// ── Per-ingredient conversion data ────────────────────────────────────────────
+
+/// Optional conversion data from an ingredient's YAML file:
+///
+/// ```yaml
+/// name: flour
+/// density: 0.55      # grams per millilitre
+/// piece_weight: 60   # grams per piece
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct IngredientUnits {
+    #[serde(default)]
+    pub density: Option<f64>,
+    #[serde(default)]
+    pub piece_weight: Option<f64>,
+}
+
+impl IngredientUnits {
+    /// Grams in one `unit` of this ingredient, if that can be worked out.
+    fn grams_per(&self, unit: &str) -> Option<f64> {
+        let (dim, size) = unit_size(unit)?;
+        match dim {
+            Dimension::Mass => Some(size),
+            Dimension::Volume => self.density.map(|d| size * d),
+            Dimension::Count => self.piece_weight.map(|w| size * w),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct IngredientFile {
+    name: String,
+    #[serde(flatten)]
+    units: IngredientUnits,
+}
+
+/// Density / piece weight among an ingredient file's extra entries.
+pub fn units_in(extra: &Mapping) -> IngredientUnits {
+    serde_yaml::from_value(Value::Mapping(extra.clone())).unwrap_or_default()
+}
+
+/// Read density / piece weight from every ingredient file in `data_dir`.
+/// Ingredients without either are left out; unreadable files are skipped.
+pub fn load_ingredient_units(data_dir: &Path) -> BTreeMap<String, IngredientUnits> {
+    let mut map = BTreeMap::new();
+    let Ok(entries) = std::fs::read_dir(data_dir.join("ingredients")) else {
+        return map;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("yaml") {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        match serde_yaml::from_str::<IngredientFile>(&text) {
+            Ok(file) if file.units != IngredientUnits::default() => {
+                map.insert(file.name.to_lowercase(), file.units);
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("Skipping unit data in {}: {}", path.display(), e),
+        }
+    }
+    map
+}
+
+// ── Global state ──────────────────────────────────────────────────────────────
+
+static PREFERRED: RwLock<MeasurementSystem> = RwLock::new(MeasurementSystem::Metric);
+static INGREDIENT_UNITS: RwLock<BTreeMap<String, IngredientUnits>> = RwLock::new(BTreeMap::new());
+
+pub fn set_preferred_system(system: MeasurementSystem) {
+    if let Ok(mut w) = PREFERRED.write() {
+        *w = system;
+    }
+}
+
+pub fn preferred_system() -> MeasurementSystem {
+    PREFERRED.read().map(|s| *s).unwrap_or_default()
+}
+
+/// Replace the per-ingredient conversion data (after a data directory load).
+pub fn set_ingredient_units(units: BTreeMap<String, IngredientUnits>) {
+    if let Ok(mut w) = INGREDIENT_UNITS.write() {
+        *w = units;
+    }
+}
+
+/// Replace the conversion data of the ingredient `name` (after it was saved).
+pub fn set_units_of(name: &str, units: IngredientUnits) {
+    if let Ok(mut w) = INGREDIENT_UNITS.write() {
+        if units == IngredientUnits::default() {
+            w.remove(&name.to_lowercase());
+        } else {
+            w.insert(name.to_lowercase(), units);
+        }
+    }
+}
+
+/// Conversion data for the ingredient `name` (empty if none is set).
+pub fn ingredient_units(name: &str) -> IngredientUnits {
+    INGREDIENT_UNITS
+        .read()
+        .ok()
+        .and_then(|m| m.get(&name.to_lowercase()).copied())
+        .unwrap_or_default()
+}
+
+// ── Display ───────────────────────────────────────────────────────────────────
+
+/// Unit to show `value` `unit` in when the preferred system is `system`, or
+/// `None` if it should stay as written (already in `system`, or system-neutral
+/// like spoons and pieces). Picks a unit that keeps the number readable.
+pub fn unit_in_system(value: f64, unit: &str, system: MeasurementSystem) -> Option<&'static str> {
+    if unit_system(unit)? == system {
+        return None;
+    }
+    let (dim, size) = unit_size(unit)?;
+    let base = value * size;
+    Some(match (system, dim) {
+        (MeasurementSystem::Metric, Dimension::Mass) if base < 1000.0 => "g",
+        (MeasurementSystem::Metric, Dimension::Mass) => "kg",
+        (MeasurementSystem::Metric, Dimension::Volume) if base < 100.0 => "ml",
+        (MeasurementSystem::Metric, Dimension::Volume) if base < 1000.0 => "dl",
+        (MeasurementSystem::Metric, Dimension::Volume) => "l",
+        (MeasurementSystem::Imperial, Dimension::Mass) if base < 453.0 => "oz",
+        (MeasurementSystem::Imperial, Dimension::Mass) => "lb",
+        (MeasurementSystem::Imperial, Dimension::Volume) if base < 59.0 => "fl oz",
+        (MeasurementSystem::Imperial, Dimension::Volume) if base < 3785.0 => "cup",
+        (MeasurementSystem::Imperial, Dimension::Volume) => "gallon",
+        (_, Dimension::Count) => return None,
+    })
+}
+
+/// `value` `unit` in the preferred measurement system, rounded for display.
+/// Amounts that need no conversion are returned unchanged.
+pub fn localize(value: f64, unit: &str) -> (f64, String) {
+    match unit_in_system(value, unit, preferred_system()) {
+        Some(target) => {
+            let converted = convert(value, unit, target).unwrap_or(value);
+            (crate::quantity::round_for_unit(converted, Some(target)), target.to_string())
+        }
+        None => (value, unit.to_string()),
+    }
+}
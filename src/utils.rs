@@ -1,10 +1,11 @@
 use relm4::gtk;
 use gtk::prelude::*;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Small pie chart showing `ratio` (0.0–1.0) of required ingredients in stock.
-pub fn build_coverage_pie(ratio: f64, tooltip: &str) -> gtk::DrawingArea {
+/// Small pie chart showing the share of required ingredients fully in stock
+/// (`covered`, green) and in stock but short (`partial`, amber), both 0.0–1.0.
+pub fn build_coverage_pie(covered: f64, partial: f64, tooltip: &str) -> gtk::DrawingArea {
     let area = gtk::DrawingArea::new();
     area.set_size_request(18, 18);
     area.set_valign(gtk::Align::Center);
@@ -21,15 +22,25 @@ pub fn build_coverage_pie(ratio: f64, tooltip: &str) -> gtk::DrawingArea {
         let _ = cr.fill();
 
         // Green filled portion (in-stock), clockwise from top
-        if ratio > 0.0 {
-            let end = -tau / 4.0 + ratio * tau;
+        let start = -tau / 4.0;
+        let covered_end = start + covered * tau;
+        if covered > 0.0 {
             cr.move_to(cx, cy);
-            cr.arc(cx, cy, r, -tau / 4.0, end);
+            cr.arc(cx, cy, r, start, covered_end);
             cr.close_path();
             cr.set_source_rgb(0.149, 0.635, 0.412); // Adwaita #26A269
             let _ = cr.fill();
         }
 
+        // Amber portion (in stock, not enough), following the green
+        if partial > 0.0 {
+            cr.move_to(cx, cy);
+            cr.arc(cx, cy, r, covered_end, covered_end + partial * tau);
+            cr.close_path();
+            cr.set_source_rgb(0.898, 0.647, 0.039); // Adwaita #E5A50A
+            let _ = cr.fill();
+        }
+
         // Thin border
         cr.arc(cx, cy, r, 0.0, tau);
         cr.set_source_rgba(0.0, 0.0, 0.0, 0.15);
@@ -53,6 +64,16 @@ pub fn clear_list_box(container: &gtk::ListBox) {
     }
 }
 
+/// Write `contents` to `path` through a temporary file renamed into place, so an
+/// interrupted write never leaves a truncated file behind.
+pub fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".part");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, contents).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
 /// Ensures the required data directory structure exists, creating subdirectories
 /// and a minimal pantry.yaml if they are missing.
 pub fn validate_and_create_data_dir<P: AsRef<Path>>(data_dir: P) {
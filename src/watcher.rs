@@ -0,0 +1,219 @@
+/// Watches the data directory for changes made by other devices or sync clients.
+///
+/// Uses `gio::FileMonitor` (one per directory — GIO monitors are not recursive) so
+/// events arrive on the GTK main loop. Bursts of events are debounced into a single
+/// `AppMsg::DataChangedExternally` carrying the changed paths, which the app
+/// filters for its own writes and [`summarize`]s.
+use crate::app::{App, AppMsg};
+use relm4::gtk::{gio, glib};
+use relm4::gtk::prelude::*;
+use relm4::ComponentSender;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Quiet period after the last event before a reload is triggered.
+/// Sync clients (Nextcloud, pCloud) typically write a temp file, rename it and
+/// touch metadata, so a single edit produces several events in quick succession.
+const DEBOUNCE: Duration = Duration::from_millis(750);
+
+/// What kind of data a changed path belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Pantry,
+    Ingredient,
+    Recipe,
+    Shopping,
+    CookingLog,
+    MealPlan,
+    PantryDetails,
+}
+
+/// Summary of the changes seen during one debounce window.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSummary {
+    pub pantry: bool,
+    pub ingredients: usize,
+    pub recipes: usize,
+    pub shopping: bool,
+    pub cooking_log: bool,
+    pub meal_plan: bool,
+    pub pantry_details: bool,
+}
+
+impl ChangeSummary {
+    pub fn is_empty(&self) -> bool {
+        !self.engine_data()
+            && !self.shopping
+            && !self.cooking_log
+            && !self.meal_plan
+            && !self.pantry_details
+    }
+
+    /// Whether anything the `DataManager` reads changed.
+    pub fn engine_data(&self) -> bool {
+        self.pantry || self.ingredients > 0 || self.recipes > 0
+    }
+
+    /// Only the app's own files (shopping list, cooking log, meal plan, pantry
+    /// details) changed, so the `DataManager` need not be reloaded.
+    pub fn only_app_files(&self) -> bool {
+        !self.engine_data() && !self.is_empty()
+    }
+}
+
+/// Keeps the directory monitors alive; dropping it stops watching.
+pub struct DataDirWatcher {
+    monitors: Vec<gio::FileMonitor>,
+    timeout: Rc<RefCell<Option<glib::SourceId>>>,
+}
+
+impl Drop for DataDirWatcher {
+    fn drop(&mut self) {
+        // A pending debounce would report changes in a directory no longer watched
+        if let Some(id) = self.timeout.borrow_mut().take() {
+            id.remove();
+        }
+        for monitor in &self.monitors {
+            monitor.cancel();
+        }
+    }
+}
+
+/// Classify a changed path relative to the data directory.
+///
+/// Returns `None` for files the engine does not read (editor backups, sync-client
+/// temp files, images) so they never trigger a reload.
+pub fn classify_path(data_dir: &Path, path: &Path) -> Option<ChangeKind> {
+    let name = path.file_name()?.to_str()?;
+    if name.starts_with('.')
+        || name.ends_with('~')
+        || name.ends_with(".tmp")
+        || name.ends_with(".part")
+        || name.ends_with(".swp")
+    {
+        return None;
+    }
+    let parent = path.parent()?;
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if parent == data_dir.join("recipes") && ext == "md" {
+        return Some(ChangeKind::Recipe);
+    }
+    if parent == data_dir.join("ingredients") && (ext == "yaml" || ext == "yml") {
+        return Some(ChangeKind::Ingredient);
+    }
+    // Checked before the pantry files, whose prefix it shares
+    if parent == data_dir && name == crate::pantry_details::PANTRY_DETAILS_FILE {
+        return Some(ChangeKind::PantryDetails);
+    }
+    // pantry.yaml plus the per-device pantry files (pantry.<device>.yaml)
+    if parent == data_dir && name.starts_with("pantry") && (ext == "yaml" || ext == "yml") {
+        return Some(ChangeKind::Pantry);
+    }
+    if parent == data_dir && name == crate::shopping_list::SHOPPING_FILE {
+        return Some(ChangeKind::Shopping);
+    }
+    if parent == data_dir && name == crate::cooking::COOKING_LOG_FILE {
+        return Some(ChangeKind::CookingLog);
+    }
+    if parent == data_dir && name == crate::meal_plan::MEAL_PLAN_FILE {
+        return Some(ChangeKind::MealPlan);
+    }
+    None
+}
+
+/// Build a summary from the set of changed paths collected during a debounce window.
+pub fn summarize(data_dir: &Path, paths: &BTreeSet<PathBuf>) -> ChangeSummary {
+    let mut summary = ChangeSummary::default();
+    for path in paths {
+        match classify_path(data_dir, path) {
+            Some(ChangeKind::Pantry) => summary.pantry = true,
+            Some(ChangeKind::Ingredient) => summary.ingredients += 1,
+            Some(ChangeKind::Recipe) => summary.recipes += 1,
+            Some(ChangeKind::Shopping) => summary.shopping = true,
+            Some(ChangeKind::CookingLog) => summary.cooking_log = true,
+            Some(ChangeKind::MealPlan) => summary.meal_plan = true,
+            Some(ChangeKind::PantryDetails) => summary.pantry_details = true,
+            None => {}
+        }
+    }
+    summary
+}
+
+/// Start watching `data_dir`, `data_dir/ingredients` and `data_dir/recipes`.
+///
+/// Directories that do not exist (or cannot be monitored) are skipped with a warning.
+pub fn watch_data_dir(data_dir: &Path, sender: ComponentSender<App>) -> DataDirWatcher {
+    let pending: Rc<RefCell<BTreeSet<PathBuf>>> = Rc::new(RefCell::new(BTreeSet::new()));
+    let timeout: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+    let mut monitors = Vec::new();
+
+    for dir in [
+        data_dir.to_path_buf(),
+        data_dir.join("ingredients"),
+        data_dir.join("recipes"),
+    ] {
+        let file = gio::File::for_path(&dir);
+        let monitor = match file
+            .monitor_directory(gio::FileMonitorFlags::WATCH_MOVES, gio::Cancellable::NONE)
+        {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!("Cannot watch {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+
+        let data_dir = data_dir.to_path_buf();
+        let pending = Rc::clone(&pending);
+        let timeout = Rc::clone(&timeout);
+        let sender = sender.clone();
+        monitor.connect_changed(move |_, file, other, event| {
+            use gio::FileMonitorEvent as E;
+            if !matches!(
+                event,
+                E::Changed
+                    | E::ChangesDoneHint
+                    | E::Created
+                    | E::Deleted
+                    | E::Renamed
+                    | E::MovedIn
+                    | E::MovedOut
+            ) {
+                return;
+            }
+
+            let mut relevant = false;
+            for f in std::iter::once(file).chain(other) {
+                if let Some(path) = f.path() {
+                    if classify_path(&data_dir, &path).is_some() {
+                        pending.borrow_mut().insert(path);
+                        relevant = true;
+                    }
+                }
+            }
+            if !relevant {
+                return;
+            }
+
+            // Restart the debounce timer
+            if let Some(id) = timeout.borrow_mut().take() {
+                id.remove();
+            }
+            let pending_fire = Rc::clone(&pending);
+            let timeout_fire = Rc::clone(&timeout);
+            let sender_fire = sender.clone();
+            let id = glib::timeout_add_local_once(DEBOUNCE, move || {
+                timeout_fire.borrow_mut().take();
+                let paths = std::mem::take(&mut *pending_fire.borrow_mut());
+                sender_fire.input(AppMsg::DataChangedExternally(paths));
+            });
+            *timeout.borrow_mut() = Some(id);
+        });
+        monitors.push(monitor);
+    }
+
+    DataDirWatcher { monitors, timeout }
+}
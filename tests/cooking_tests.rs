@@ -0,0 +1,135 @@
+// Tests for "Cooked it" pantry deductions and the cooking log (no GTK required).
+use chrono::NaiveDate;
+use janus_engine::DataManager;
+use pantryman_linux::cooking::{
+    days_since, planned_deductions, remaining_after, Consume, CookStats, CookingLog,
+    COOKING_LOG_FILE,
+};
+use pantryman_linux::coverage::Coverage;
+use pantryman_linux::recipes::{sort_recipes, RecipeSort, SortKeys};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+#[test]
+fn partial_use_leaves_the_rest() {
+    assert_eq!(remaining_after(Some(5.0), "", Consume::Amount(2.0)), Some(Some(3.0)));
+    assert_eq!(remaining_after(Some(1.0), "kg", Consume::Amount(0.25)), Some(Some(0.75)));
+}
+
+#[test]
+fn using_everything_removes_the_item() {
+    assert_eq!(remaining_after(Some(2.0), "kg", Consume::Amount(2.0)), None);
+    assert_eq!(remaining_after(Some(2.0), "kg", Consume::Amount(3.0)), None);
+    assert_eq!(remaining_after(Some(2.0), "kg", Consume::All), None);
+    assert_eq!(remaining_after(None, "", Consume::All), None);
+}
+
+#[test]
+fn untracked_amounts_stay_in_stock() {
+    assert_eq!(remaining_after(None, "", Consume::Amount(1.0)), Some(None));
+}
+
+#[test]
+fn fixture_deductions() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let lasagna = dm.get_recipe("Lasagna").unwrap();
+    let plan = planned_deductions(&dm, lasagna, 1.0);
+    assert_eq!(plan.len(), lasagna.ingredients.len());
+
+    let potato = plan.iter().find(|d| d.ingredient == "potato").unwrap();
+    assert!(!potato.in_pantry);
+    assert!(!potato.selected);
+
+    // Pantry tomatoes are counted in pieces, the recipe wants kg: no pre-fill
+    let tomato = plan.iter().find(|d| d.ingredient == "tomato").unwrap();
+    assert!(tomato.in_pantry);
+    assert!(tomato.selected);
+    assert_eq!(tomato.have, Some(2.0));
+    assert_eq!(tomato.amount, None);
+}
+
+#[test]
+fn cooking_log_roundtrip() {
+    let dir = tempdir().unwrap();
+    assert_eq!(CookingLog::load(dir.path()).unwrap(), CookingLog::default());
+
+    let mut log = CookingLog::default();
+    log.record("Spaghetti Aglio e Olio", "2025-07-24", Some(4));
+    log.save(dir.path()).unwrap();
+    assert!(dir.path().join(COOKING_LOG_FILE).exists());
+
+    let loaded = CookingLog::load(dir.path()).unwrap();
+    assert_eq!(loaded, log);
+    let slug = janus_engine::slugify("Spaghetti Aglio e Olio");
+    assert_eq!(loaded.recipes[&slug].title, "Spaghetti Aglio e Olio");
+    assert_eq!(loaded.recipes[&slug].cooked[0].date, "2025-07-24");
+}
+
+#[test]
+fn stats_count_cooks_and_find_the_latest() {
+    let mut log = CookingLog::default();
+    assert_eq!(log.stats("Lasagna"), None);
+    log.record("Lasagna", "2025-07-24", None);
+    log.record("Lasagna", "2025-05-01", None);
+    assert_eq!(
+        log.stats("Lasagna"),
+        Some(CookStats { count: 2, last: "2025-07-24".to_string() })
+    );
+}
+
+#[test]
+fn history_lists_most_recent_first() {
+    let mut log = CookingLog::default();
+    log.record("Lasagna", "2025-05-01", None);
+    log.record("Spaghetti Aglio e Olio", "2025-07-24", Some(2));
+    log.record("Lasagna", "2025-06-10", None);
+    let dates: Vec<&str> = log.history().iter().map(|e| e.date.as_str()).collect();
+    assert_eq!(dates, ["2025-07-24", "2025-06-10", "2025-05-01"]);
+    assert_eq!(log.history()[0].servings, Some(2));
+}
+
+#[test]
+fn rename_moves_the_cooks() {
+    let mut log = CookingLog::default();
+    log.record("Lasagna", "2025-05-01", None);
+    log.rename_recipe("Lasagna", "Potato Lasagna");
+    assert_eq!(log.stats("Lasagna"), None);
+    assert_eq!(log.stats("Potato Lasagna").unwrap().count, 1);
+    assert_eq!(log.history()[0].title, "Potato Lasagna");
+}
+
+#[test]
+fn days_since_counts_whole_days() {
+    let today = NaiveDate::from_ymd_opt(2025, 7, 24).unwrap();
+    assert_eq!(days_since("2025-07-24", today), Some(0));
+    assert_eq!(days_since("2025-07-01", today), Some(23));
+    assert_eq!(days_since("not a date", today), None);
+}
+
+#[test]
+fn sort_by_cooking_log() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let mut log = CookingLog::default();
+    log.record("Lasagna", "2025-07-24", None);
+    log.record("Lasagna", "2025-07-20", None);
+
+    let (expiring, modified) = (BTreeSet::new(), HashMap::new());
+    let keys = SortKeys { log: &log, expiring: &expiring, modified: &modified };
+
+    let mut recipes: Vec<_> = dm
+        .get_all_recipes()
+        .iter()
+        .map(|r| (r, Coverage::default()))
+        .collect();
+    sort_recipes(&mut recipes, RecipeSort::MostCooked, &keys);
+    assert_eq!(recipes[0].0.title, "Lasagna");
+
+    // Never-cooked recipes come first
+    sort_recipes(&mut recipes, RecipeSort::LeastRecentlyCooked, &keys);
+    assert_eq!(recipes.last().unwrap().0.title, "Lasagna");
+}
@@ -0,0 +1,157 @@
+// Tests for Cooklang import and export (no GTK required).
+use janus_engine::{DataManager, Recipe, RecipeIngredient};
+use pantryman_linux::cooklang::{import_cook_file, parse_cooklang, parse_minutes, to_cooklang};
+use pantryman_linux::dialogs::IngredientInfo;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+const SOUP: &str = "---
+title: Leek Soup
+servings: 4 bowls
+tags: [soup, vegetarian]
+prep time: 15 minutes
+---
+-- Adapted from grandma's recipe
+Fry @leeks{2} in @butter{25%g}(salted) in a #large pot{}.
+
+Add @potatoes{1%kg} and @water{1.5%l}, then simmer for ~{20%minutes}.
+Season with @salt and @?chives{}.
+
+= To serve =
+> Best the day after.
+Top with more @butter{10%g}. [- or cream -]
+";
+
+fn known() -> Vec<IngredientInfo> {
+    let info = |name: &str, forms: &[&str]| IngredientInfo {
+        name: name.to_string(),
+        forms: forms.iter().map(|f| f.to_string()).collect(),
+        in_pantry: false,
+    };
+    vec![
+        info("leek", &["leek", "leeks"]),
+        info("potato", &["potato", "potatoes"]),
+        info("salt", &["salt"]),
+    ]
+}
+
+#[test]
+fn imports_metadata_steps_and_ingredients() {
+    let imported = parse_cooklang(SOUP, "soup", &known(), &[]);
+    let recipe = imported.recipe;
+    assert_eq!(recipe.title, "Leek Soup");
+    assert_eq!(recipe.servings, Some(4));
+    assert_eq!(recipe.prep_time, Some(15));
+    assert_eq!(recipe.tags, Some(vec!["soup".to_string(), "vegetarian".to_string()]));
+    assert_eq!(
+        recipe.instructions,
+        "1. Fry leeks in butter in a large pot.\n\
+         2. Add potatoes and water, then simmer for 20 minutes. Season with salt and chives.\n\n\
+         ## To serve\n\n\
+         > Best the day after.\n\n\
+         1. Top with more butter."
+    );
+
+    let names: Vec<&str> = recipe.ingredients.iter().map(|i| i.ingredient.as_str()).collect();
+    assert_eq!(names, ["leek", "butter", "potato", "water", "salt", "chives"]);
+    let butter = &recipe.ingredients[1];
+    // Both uses of the butter are summed
+    assert_eq!(butter.quantity.as_deref(), Some("35"));
+    assert_eq!(butter.quantity_type.as_deref(), Some("g"));
+    assert_eq!(butter.note.as_deref(), Some("salted"));
+    assert_eq!(recipe.ingredients[3].quantity.as_deref(), Some("1.5"));
+    assert_eq!(recipe.ingredients[3].quantity_type.as_deref(), Some("l"));
+    assert!(recipe.ingredients[5].optional);
+
+    assert_eq!(imported.unmatched, ["butter", "water", "chives"]);
+}
+
+#[test]
+fn old_style_metadata_and_file_names() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("Pancakes.cook");
+    std::fs::write(&path, ">> servings: 2\n>> time: 1h 10m\nWhisk @eggs{3} and @milk{5%dl}.\n")
+        .unwrap();
+    std::fs::write(dir.path().join("Pancakes.jpg"), b"").unwrap();
+
+    let imported = import_cook_file(&path, &[], &[]).unwrap();
+    assert_eq!(imported.recipe.title, "Pancakes");
+    assert_eq!(imported.recipe.servings, Some(2));
+    assert_eq!(imported.recipe.prep_time, Some(70));
+    assert_eq!(imported.recipe.ingredients.len(), 2);
+    assert_eq!(imported.image_file, Some(dir.path().join("Pancakes.jpg")));
+}
+
+#[test]
+fn example_recipes_survive_export_and_import() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    for recipe in dm.get_all_recipes() {
+        let text = to_cooklang(recipe);
+        let back = parse_cooklang(&text, "", &[], &[]).recipe;
+        assert_eq!(back.title, recipe.title);
+        assert_eq!(back.servings, recipe.servings);
+        assert_eq!(back.prep_time, recipe.prep_time);
+        assert_eq!(back.downtime, recipe.downtime);
+        assert_eq!(back.tags, recipe.tags);
+
+        let sorted = |ings: &[janus_engine::RecipeIngredient]| {
+            let mut v: Vec<String> = ings.iter().map(|i| format!("{:?}", i)).collect();
+            v.sort();
+            v
+        };
+        assert_eq!(sorted(&back.ingredients), sorted(&recipe.ingredients), "{}", text);
+    }
+}
+
+#[test]
+fn export_marks_the_first_mention() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let text = to_cooklang(dm.get_recipe("Lasagna").unwrap());
+    assert!(text.starts_with("---\ntitle: Lasagna\n"));
+    assert!(text.contains("boiling the @potato{"));
+    assert_eq!(text.matches("@potato{").count(), 1);
+}
+
+#[test]
+fn export_keeps_overlapping_names_apart() {
+    let ing = |name: &str, quantity: &str, unit: &str| RecipeIngredient {
+        ingredient: name.to_string(),
+        quantity: Some(quantity.to_string()),
+        quantity_type: Some(unit.to_string()),
+        note: None,
+        optional: false,
+    };
+    let recipe = Recipe {
+        title: "Sauce".to_string(),
+        slug: String::new(),
+        file_stem: String::new(),
+        ingredients: vec![ing("tomato", "4", ""), ing("tomato paste", "2", "tbsp")],
+        prep_time: None,
+        downtime: None,
+        servings: None,
+        tags: None,
+        image: None,
+        instructions: "Stir the tomato paste into the chopped tomatoes.".to_string(),
+    };
+    let text = to_cooklang(&recipe);
+    assert!(
+        text.contains("Stir the @tomato paste{2%tbsp} into the chopped @tomato{4}."),
+        "{}",
+        text
+    );
+    let back = parse_cooklang(&text, "", &[], &[]).recipe;
+    let names: Vec<&str> = back.ingredients.iter().map(|i| i.ingredient.as_str()).collect();
+    assert_eq!(names, ["tomato paste", "tomato"]);
+}
+
+#[test]
+fn times() {
+    assert_eq!(parse_minutes("45"), Some(45));
+    assert_eq!(parse_minutes("1 hour 30 minutes"), Some(90));
+    assert_eq!(parse_minutes("1h30m"), Some(90));
+    assert_eq!(parse_minutes("a while"), None);
+}
@@ -0,0 +1,77 @@
+// Tests for quantity-aware pantry coverage (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::coverage::{compare_amounts, recipe_coverage, Coverage, Stock};
+use pantryman_linux::units::IngredientUnits;
+use std::path::PathBuf;
+
+const NONE: IngredientUnits = IngredientUnits { density: None, piece_weight: None };
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+#[test]
+fn enough_in_same_unit_is_covered() {
+    assert_eq!(compare_amounts(Some(2.0), "kg", Some("2"), "kg", 1.0, NONE), Stock::Covered);
+    assert_eq!(compare_amounts(Some(3.0), "stk", Some("2-3"), "stk", 1.0, NONE), Stock::Covered);
+}
+
+#[test]
+fn converts_between_units_of_the_same_kind() {
+    assert_eq!(compare_amounts(Some(200.0), "g", Some("2"), "kg", 1.0, NONE), Stock::Partial);
+    assert_eq!(compare_amounts(Some(2.0), "kg", Some("500"), "g", 1.0, NONE), Stock::Covered);
+    assert_eq!(compare_amounts(Some(1.0), "l", Some("3"), "dl", 2.0, NONE), Stock::Covered);
+    assert_eq!(compare_amounts(Some(1.0), "tsp", Some("1"), "tbsp", 1.0, NONE), Stock::Partial);
+}
+
+#[test]
+fn scaling_raises_the_requirement() {
+    assert_eq!(compare_amounts(Some(3.0), "", Some("2"), "stk", 1.0, NONE), Stock::Covered);
+    assert_eq!(compare_amounts(Some(3.0), "", Some("2"), "stk", 2.0, NONE), Stock::Partial);
+}
+
+#[test]
+fn incomparable_amounts_count_as_covered() {
+    assert_eq!(compare_amounts(None, "", Some("2"), "kg", 1.0, NONE), Stock::Covered);
+    assert_eq!(compare_amounts(Some(1.0), "kg", None, "", 1.0, NONE), Stock::Covered);
+    assert_eq!(compare_amounts(Some(1.0), "kg", Some("2"), "dl", 1.0, NONE), Stock::Covered);
+    assert_eq!(compare_amounts(Some(1.0), "dåse", Some("2"), "g", 1.0, NONE), Stock::Covered);
+}
+
+#[test]
+fn density_and_piece_weight_bridge_dimensions() {
+    let flour = IngredientUnits { density: Some(0.5), piece_weight: None };
+    assert_eq!(compare_amounts(Some(1.0), "kg", Some("1"), "l", 1.0, flour), Stock::Covered);
+    assert_eq!(compare_amounts(Some(200.0), "g", Some("5"), "dl", 1.0, flour), Stock::Partial);
+    let egg = IngredientUnits { density: None, piece_weight: Some(60.0) };
+    assert_eq!(compare_amounts(Some(3.0), "stk", Some("150"), "g", 1.0, egg), Stock::Covered);
+}
+
+#[test]
+fn empty_pantry_amount_is_missing() {
+    assert_eq!(compare_amounts(Some(0.0), "g", Some("100"), "g", 1.0, NONE), Stock::Missing);
+}
+
+#[test]
+fn coverage_ratios() {
+    let cov = Coverage { required_total: 4, covered: 2, partial: 1 };
+    assert_eq!(cov.not_covered(), 2);
+    assert!(!cov.is_cookable());
+    assert_eq!(cov.covered_ratio(), 0.5);
+    assert_eq!(cov.partial_ratio(), 0.25);
+    assert_eq!(Coverage::default().covered_ratio(), 1.0);
+}
+
+#[test]
+fn fixture_recipe_coverage() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let lasagna = dm.get_recipe("Lasagna").unwrap();
+    // 2 kg potato in the pantry covers the recipe's 2 kg; the tomatoes are
+    // counted without a unit, can't be compared with 1 kg and count as covered
+    let cov = recipe_coverage(&dm, lasagna, 1.0);
+    assert_eq!(cov, Coverage { required_total: 2, covered: 2, partial: 0 });
+    // Doubled, the potatoes fall short
+    let cov = recipe_coverage(&dm, lasagna, 2.0);
+    assert_eq!(cov, Coverage { required_total: 2, covered: 1, partial: 1 });
+    assert_eq!(cov.not_covered(), 1);
+}
@@ -0,0 +1,273 @@
+// Undo/redo replay through a real DataManager (no GTK required).
+use chrono::NaiveDate;
+use janus_engine::DataManager;
+use pantryman_linux::history::{Change, History, Snapshot};
+use pantryman_linux::images::{import_image, IMAGE_DIR};
+use pantryman_linux::ingredient_file::{ingredient_path, read_extra_keys, write_with_extra_keys};
+use pantryman_linux::low_stock::{set_threshold_keys, threshold, threshold_in, Threshold};
+use pantryman_linux::pantry_details::PantryDetails;
+use std::path::Path;
+
+fn copy_dir(src: &Path, dst: &Path) {
+    std::fs::create_dir_all(dst).unwrap();
+    for entry in std::fs::read_dir(src).unwrap() {
+        let entry = entry.unwrap();
+        let target = dst.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+}
+
+fn scratch_data_dir() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().unwrap();
+    copy_dir(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("example/data"),
+        tmp.path(),
+    );
+    tmp
+}
+
+#[test]
+fn undo_restores_deleted_recipe_and_redo_deletes_again() {
+    let tmp = scratch_data_dir();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+
+    let before = Snapshot::recipe(&dm, tmp.path(), "Lasagna");
+    dm.delete_recipe("Lasagna").unwrap();
+    history.record(Change {
+        label: "Deleted \"Lasagna\"".to_string(),
+        before,
+        after: Snapshot::Recipe(None),
+    });
+    assert!(dm.get_recipe("Lasagna").is_none());
+
+    assert_eq!(
+        history
+            .undo(&mut dm, tmp.path(), &mut details)
+            .unwrap()
+            .as_deref(),
+        Some("Deleted \"Lasagna\"")
+    );
+    let restored = dm.get_recipe("Lasagna").expect("recipe restored");
+    assert_eq!(restored.ingredients.len(), 2);
+    assert!(history.can_redo());
+
+    history.redo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert!(dm.get_recipe("Lasagna").is_none());
+    assert!(history.can_undo());
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn undo_keeps_unknown_frontmatter() {
+    let tmp = scratch_data_dir();
+    let file = tmp.path().join("recipes/Lasagna.md");
+    let text = std::fs::read_to_string(&file).unwrap();
+    let text = text.replacen("image: null\n", "image: null\nrating: 5\n", 1);
+    std::fs::write(&file, text).unwrap();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+
+    let before = Snapshot::recipe(&dm, tmp.path(), "Lasagna");
+    dm.delete_recipe("Lasagna").unwrap();
+    history.record(Change {
+        label: "Deleted \"Lasagna\"".to_string(),
+        before,
+        after: Snapshot::Recipe(None),
+    });
+
+    history.undo(&mut dm, tmp.path(), &mut details).unwrap();
+    let restored = std::fs::read_to_string(&file).unwrap();
+    assert!(restored.contains("rating: 5"), "{}", restored);
+}
+
+#[test]
+fn undo_restores_deleted_ingredient_with_pantry_entry_and_details() {
+    let tmp = scratch_data_dir();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+    let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+    details.set_best_before("potato", Some(date));
+    details.set_location("potato", Some("Cellar".to_string()));
+
+    let before = Snapshot::ingredient(&dm, tmp.path(), &details, "potato");
+    dm.delete_ingredient("potato").unwrap();
+    details.prune(&dm);
+    history.record(Change {
+        label: "Deleted \"potato\"".to_string(),
+        before,
+        after: Snapshot::Ingredient(None),
+    });
+
+    history.undo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert!(dm.get_ingredient("potato").is_some());
+    let item = dm.get_pantry_item("potato").expect("pantry entry restored");
+    assert_eq!(item.quantity, Some(2.0));
+    assert_eq!(item.quantity_type, "kg");
+    assert_eq!(details.best_before("potato"), Some(date));
+    assert_eq!(details.location("potato"), Some("Cellar"));
+
+    history.redo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(details.best_before("potato"), None);
+}
+
+#[test]
+fn undo_and_redo_restore_the_threshold() {
+    let tmp = scratch_data_dir();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+
+    let before = Snapshot::ingredient(&dm, tmp.path(), &details, "potato");
+    let min = Threshold {
+        quantity: 1.0,
+        unit: "kg".to_string(),
+    };
+    let mut extra = serde_yaml::Mapping::new();
+    set_threshold_keys(&mut extra, Some(&min));
+    let potato = dm.get_ingredient("potato").unwrap().clone();
+    write_with_extra_keys(&mut dm, tmp.path(), "potato", "potato", &extra, |dm| {
+        let unit = Some("kg".to_string());
+        dm.update_ingredient_with_pantry("potato", potato, Some(2.0), unit, false)
+    })
+    .unwrap();
+    history.record(Change {
+        label: "Saved \"potato\"".to_string(),
+        before,
+        after: Snapshot::ingredient(&dm, tmp.path(), &details, "potato"),
+    });
+    assert_eq!(threshold("potato"), Some(min.clone()));
+
+    let path = ingredient_path(tmp.path(), &dm, "potato");
+    history.undo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(threshold_in(&read_extra_keys(&path)), None);
+    assert_eq!(threshold("potato"), None);
+
+    history.redo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(threshold_in(&read_extra_keys(&path)), Some(min.clone()));
+    assert_eq!(threshold("potato"), Some(min));
+}
+
+#[test]
+fn grouped_change_is_undone_and_redone_as_one() {
+    let tmp = scratch_data_dir();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+
+    let names = ["potato", "flour"];
+    let snapshots = |dm: &DataManager, details: &PantryDetails| {
+        let all = names
+            .iter()
+            .map(|n| Snapshot::ingredient(dm, tmp.path(), details, n));
+        Snapshot::Group(all.collect())
+    };
+    let before = snapshots(&dm, &details);
+    dm.update_pantry_item("potato", Some(5.0), Some("kg".to_string()))
+        .unwrap();
+    dm.update_pantry_item("flour", Some(500.0), Some("g".to_string()))
+        .unwrap();
+    history.record(Change {
+        label: "Imported 2 pantry item(s)".to_string(),
+        before,
+        after: snapshots(&dm, &details),
+    });
+
+    history.undo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(dm.get_pantry_item("potato").unwrap().quantity, Some(2.0));
+    assert!(!dm.is_in_pantry("flour"));
+    assert!(!history.can_undo());
+
+    history.redo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(dm.get_pantry_item("potato").unwrap().quantity, Some(5.0));
+    assert_eq!(dm.get_pantry_item("flour").unwrap().quantity, Some(500.0));
+}
+
+#[test]
+fn empty_history_is_a_no_op() {
+    let tmp = scratch_data_dir();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+    assert_eq!(
+        history.undo(&mut dm, tmp.path(), &mut details).unwrap(),
+        None
+    );
+    assert_eq!(
+        history.redo(&mut dm, tmp.path(), &mut details).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn change_ids_tell_a_stale_undo_apart() {
+    let tmp = scratch_data_dir();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+    let change = |label: &str| Change {
+        label: label.to_string(),
+        before: Snapshot::Recipe(None),
+        after: Snapshot::Recipe(None),
+    };
+
+    let first = history.record(change("first"));
+    assert_eq!(history.undo_id(), Some(first));
+    let second = history.record(change("second"));
+    assert_ne!(first, second);
+    assert_eq!(history.undo_id(), Some(second));
+
+    // The id travels with the change through undo and redo
+    history.undo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(history.undo_id(), Some(first));
+    history.redo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(history.undo_id(), Some(second));
+}
+
+#[test]
+fn undo_puts_a_replaced_image_back() {
+    let tmp = scratch_data_dir();
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut history = History::default();
+    let mut details = PantryDetails::default();
+    let picked = tempfile::tempdir().unwrap();
+    let (jpg, png) = (picked.path().join("a.jpg"), picked.path().join("b.png"));
+    std::fs::write(&jpg, b"jpeg").unwrap();
+    std::fs::write(&png, b"png").unwrap();
+    let set_image = |dm: &mut DataManager, source: &Path| {
+        let mut recipe = dm.get_recipe("Lasagna").unwrap().clone();
+        recipe.image = Some(import_image(tmp.path(), source, "lasagna").unwrap());
+        dm.update_recipe("Lasagna", recipe).unwrap();
+    };
+
+    set_image(&mut dm, &jpg);
+    let before = Snapshot::recipe(&dm, tmp.path(), "Lasagna");
+    set_image(&mut dm, &png);
+    let old = tmp.path().join(IMAGE_DIR).join("lasagna.jpg");
+    assert!(!old.exists());
+    history.record(Change {
+        label: "Saved \"Lasagna\"".to_string(),
+        before,
+        after: Snapshot::recipe(&dm, tmp.path(), "Lasagna"),
+    });
+
+    history.undo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(
+        dm.get_recipe("Lasagna").unwrap().image.as_deref(),
+        Some("lasagna.jpg")
+    );
+    assert_eq!(std::fs::read(&old).unwrap(), b"jpeg");
+
+    history.redo(&mut dm, tmp.path(), &mut details).unwrap();
+    assert_eq!(
+        dm.get_recipe("Lasagna").unwrap().image.as_deref(),
+        Some("lasagna.png")
+    );
+}
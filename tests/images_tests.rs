@@ -0,0 +1,88 @@
+// Tests for importing recipe images and naming cached thumbnails (no GTK required).
+use janus_engine::Recipe;
+use pantryman_linux::images::{import_image, recipe_image_path, thumbnail_path, IMAGE_DIR};
+use tempfile::tempdir;
+
+fn recipe_with_image(image: Option<&str>) -> Recipe {
+    Recipe {
+        title: "Lasagna".to_string(),
+        slug: String::new(),
+        file_stem: String::new(),
+        ingredients: Vec::new(),
+        prep_time: None,
+        downtime: None,
+        servings: None,
+        tags: None,
+        image: image.map(str::to_string),
+        instructions: String::new(),
+    }
+}
+
+#[test]
+fn import_copies_under_the_slug() {
+    let data = tempdir().unwrap();
+    let picked = tempdir().unwrap();
+    let source = picked.path().join("IMG_0042.JPG");
+    std::fs::write(&source, b"jpeg").unwrap();
+
+    let name = import_image(data.path(), &source, "lasagna").unwrap();
+    assert_eq!(name, "lasagna.jpg");
+    assert_eq!(std::fs::read(data.path().join(IMAGE_DIR).join("lasagna.jpg")).unwrap(), b"jpeg");
+    assert!(source.exists());
+}
+
+#[test]
+fn a_new_image_replaces_the_old_one() {
+    let data = tempdir().unwrap();
+    let picked = tempdir().unwrap();
+    let jpg = picked.path().join("a.jpg");
+    let png = picked.path().join("b.png");
+    std::fs::write(&jpg, b"jpeg").unwrap();
+    std::fs::write(&png, b"png").unwrap();
+
+    import_image(data.path(), &jpg, "lasagna").unwrap();
+    import_image(data.path(), &jpg, "lasagna-2").unwrap();
+    assert_eq!(import_image(data.path(), &png, "lasagna").unwrap(), "lasagna.png");
+
+    let img_dir = data.path().join(IMAGE_DIR);
+    assert!(!img_dir.join("lasagna.jpg").exists());
+    assert!(img_dir.join("lasagna.png").exists());
+    // Other recipes' images are left alone
+    assert!(img_dir.join("lasagna-2.jpg").exists());
+}
+
+#[test]
+fn recipe_images_resolve_in_the_image_folder() {
+    let data = tempdir().unwrap();
+    std::fs::create_dir_all(data.path().join(IMAGE_DIR)).unwrap();
+    std::fs::write(data.path().join(IMAGE_DIR).join("lasagna.jpg"), b"jpeg").unwrap();
+
+    let expected = data.path().join(IMAGE_DIR).join("lasagna.jpg");
+    assert_eq!(
+        recipe_image_path(data.path(), &recipe_with_image(Some("lasagna.jpg"))),
+        Some(expected.clone())
+    );
+    assert_eq!(
+        recipe_image_path(data.path(), &recipe_with_image(Some("img/lasagna.jpg"))),
+        Some(expected)
+    );
+    assert_eq!(recipe_image_path(data.path(), &recipe_with_image(Some("gone.jpg"))), None);
+    assert_eq!(recipe_image_path(data.path(), &recipe_with_image(None)), None);
+}
+
+#[test]
+fn thumbnail_names_follow_the_source() {
+    let cache = tempdir().unwrap();
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("lasagna.jpg");
+    std::fs::write(&source, b"jpeg").unwrap();
+
+    let small = thumbnail_path(cache.path(), &source, 64);
+    assert_eq!(small, thumbnail_path(cache.path(), &source, 64));
+    assert_ne!(small, thumbnail_path(cache.path(), &source, 960));
+    assert!(small.starts_with(cache.path()));
+
+    // A replaced file (different size) gets a new thumbnail
+    std::fs::write(&source, b"a bigger jpeg").unwrap();
+    assert_ne!(small, thumbnail_path(cache.path(), &source, 64));
+}
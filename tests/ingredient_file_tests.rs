@@ -0,0 +1,72 @@
+// Tests for keeping extra ingredient file keys across engine writes (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::ingredient_file::{
+    extra_keys, ingredient_path, keep_extra_keys, with_extra_keys,
+};
+use pantryman_linux::units::load_ingredient_units;
+use std::path::Path;
+
+fn copy_dir(src: &Path, dst: &Path) {
+    std::fs::create_dir_all(dst).unwrap();
+    for entry in std::fs::read_dir(src).unwrap() {
+        let entry = entry.unwrap();
+        let target = dst.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+}
+
+fn scratch_data_dir() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().unwrap();
+    copy_dir(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("example/data"),
+        tmp.path(),
+    );
+    tmp
+}
+
+#[test]
+fn extra_keys_are_replaced_in_place() {
+    let text = "name: milk\ndensity: 1.03\ncategory: dairy\nmin_quantity: 2.0\n";
+    let extra = extra_keys(text);
+    assert_eq!(extra.len(), 2);
+
+    let mut changed = extra.clone();
+    changed.insert("density".into(), 1.04.into());
+    changed.insert("shelf".into(), "top".into());
+    let updated = with_extra_keys("name: milk\ncategory: dairy\n", &changed).unwrap();
+    assert_eq!(
+        updated,
+        "name: milk\ncategory: dairy\ndensity: 1.04\nmin_quantity: 2.0\nshelf: top\n"
+    );
+    assert_eq!(with_extra_keys(text, &extra).unwrap(), text);
+}
+
+#[test]
+fn saving_an_ingredient_keeps_its_extra_keys() {
+    let tmp = scratch_data_dir();
+    let milk = tmp.path().join("ingredients/milk.yaml");
+    let mut text = std::fs::read_to_string(&milk).unwrap();
+    text.push_str("density: 1.03\nmin_quantity: 2.0\nmin_quantity_type: l\n");
+    std::fs::write(&milk, text).unwrap();
+
+    let mut dm = DataManager::new(tmp.path(), "test-device").unwrap();
+    let mut ingredient = dm.get_ingredient("milk").unwrap().clone();
+    ingredient.category = "drinks".to_string();
+    ingredient.name = "whole milk".to_string();
+    keep_extra_keys(&mut dm, tmp.path(), "milk", "whole milk", |dm| {
+        let unit = Some("l".to_string());
+        dm.update_ingredient_with_pantry("milk", ingredient, Some(1.0), unit, false)
+    })
+    .unwrap();
+
+    let saved = std::fs::read_to_string(ingredient_path(tmp.path(), &dm, "whole milk")).unwrap();
+    assert!(saved.contains("category: drinks"), "{}", saved);
+    assert!(saved.contains("min_quantity: 2.0"), "{}", saved);
+    assert!(saved.contains("min_quantity_type: l"), "{}", saved);
+    let units = load_ingredient_units(tmp.path());
+    assert_eq!(units["whole milk"].density, Some(1.03));
+}
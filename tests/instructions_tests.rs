@@ -0,0 +1,85 @@
+// Tests for splitting instructions into cook mode steps and detecting timers (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::instructions::{
+    detect_timers, format_countdown, mentioned_ingredients, split_steps, TimerSpec,
+};
+use std::path::PathBuf;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+#[test]
+fn numbered_lists_are_one_step_per_item() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let spaghetti = dm.get_recipe("Spaghetti Aglio e Olio").unwrap();
+    let steps = split_steps(&spaghetti.instructions);
+    assert_eq!(steps.len(), 6);
+    assert!(steps[0].starts_with("Cook spaghetti"));
+    assert_eq!(steps[2], "Add chili flakes and stir for 30 seconds.");
+}
+
+#[test]
+fn a_single_paragraph_splits_into_sentences() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let lasagna = dm.get_recipe("Lasagna").unwrap();
+    let steps = split_steps(&lasagna.instructions);
+    assert_eq!(
+        steps,
+        [
+            "Start by boiling the potatoes.",
+            "Then, layer them with the tomatoes and cheese in a baking dish.",
+            "Bake for 60 minutes at 180C.",
+        ]
+    );
+}
+
+#[test]
+fn paragraphs_bullets_and_headings() {
+    assert_eq!(split_steps("Boil water.\n\nAdd pasta.\nStir."), ["Boil water.", "Add pasta. Stir."]);
+    assert_eq!(
+        split_steps("## Sauce\n- Fry onions\n  until soft\n- Add tomatoes"),
+        ["Fry onions until soft", "Add tomatoes"]
+    );
+    assert!(split_steps("").is_empty());
+}
+
+#[test]
+fn timers_are_found_in_english_and_danish() {
+    assert_eq!(
+        detect_timers("Bake for 60 minutes at 180C."),
+        [TimerSpec { label: "60 minutes".to_string(), seconds: 3600 }]
+    );
+    assert_eq!(detect_timers("Stir for 30 seconds.")[0].seconds, 30);
+    assert_eq!(detect_timers("Simmer (1½ hours), covered")[0].seconds, 5400);
+    assert_eq!(detect_timers("Kog i 10 minutter")[0].seconds, 600);
+}
+
+#[test]
+fn ranges_use_the_upper_bound() {
+    let timers = detect_timers("Rest 10-15 min, then slice");
+    assert_eq!(timers, [TimerSpec { label: "10-15 min".to_string(), seconds: 900 }]);
+}
+
+#[test]
+fn other_numbers_are_not_timers() {
+    assert!(detect_timers("Heat the oven to 180C and use 2 cups of flour").is_empty());
+    assert!(detect_timers("Season to taste").is_empty());
+}
+
+#[test]
+fn ingredients_match_at_word_starts() {
+    let names = ["potato".to_string(), "tomato".to_string(), "oil".to_string()];
+    assert_eq!(mentioned_ingredients("Boil the Potatoes", &names), [0]);
+    assert_eq!(mentioned_ingredients("Add tomatoes and oil", &names), [1, 2]);
+    // "oil" only appears inside another word here
+    assert!(mentioned_ingredients("Cover with foil", &names).is_empty());
+}
+
+#[test]
+fn countdowns() {
+    assert_eq!(format_countdown(0), "0:00");
+    assert_eq!(format_countdown(65), "1:05");
+    assert_eq!(format_countdown(3599), "59:59");
+    assert_eq!(format_countdown(3900), "1:05:00");
+}
@@ -0,0 +1,118 @@
+// Tests for low-stock thresholds and restock suggestions (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::ingredient_file::{read_extra_keys, write_extra_keys};
+use pantryman_linux::low_stock::{
+    load_thresholds, parse_threshold, restock_item, running_low, set_threshold_keys,
+    threshold_text, with_threshold, Threshold,
+};
+use pantryman_linux::shopping_list::ShoppingList;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+fn threshold(quantity: f64, unit: &str) -> Threshold {
+    Threshold {
+        quantity,
+        unit: unit.to_string(),
+    }
+}
+
+#[test]
+fn threshold_is_written_into_the_ingredient_file() {
+    let text = "name: milk\ncategory: dairy\ndensity: 1.03\n";
+    let with = with_threshold(text, Some(&threshold(2.0, "l"))).unwrap();
+    assert!(with.starts_with("name: milk\ncategory: dairy\ndensity: 1.03\n"), "{}", with);
+    assert!(with.contains("min_quantity: 2.0"), "{}", with);
+    assert!(with.contains("min_quantity_type: l"), "{}", with);
+
+    let without = with_threshold(&with, None).unwrap();
+    assert_eq!(without, text);
+}
+
+#[test]
+fn thresholds_load_from_the_data_dir() {
+    let dir = tempdir().unwrap();
+    let ingredients = dir.path().join("ingredients");
+    std::fs::create_dir(&ingredients).unwrap();
+    let milk = ingredients.join("milk.yaml");
+    std::fs::write(&milk, "name: Milk\ncategory: dairy\ndensity: 1.03\n").unwrap();
+    std::fs::write(ingredients.join("salt.yaml"), "name: salt\n").unwrap();
+
+    let mut extra = read_extra_keys(&milk);
+    set_threshold_keys(&mut extra, Some(&threshold(1.5, "l")));
+    write_extra_keys(&milk, &extra).unwrap();
+    let loaded = load_thresholds(dir.path());
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded["milk"], threshold(1.5, "l"));
+
+    // The other keys stay
+    let text = std::fs::read_to_string(&milk).unwrap();
+    assert!(text.starts_with("name: Milk\ncategory: dairy\ndensity: 1.03\n"));
+
+    // The fixture ingredients have none
+    assert!(load_thresholds(&fixture_data_dir()).is_empty());
+}
+
+#[test]
+fn parses_dialog_input() {
+    assert_eq!(parse_threshold("2 l"), Some(threshold(2.0, "l")));
+    assert_eq!(parse_threshold("500 gram"), Some(threshold(500.0, "g")));
+    assert_eq!(parse_threshold("3"), Some(threshold(3.0, "")));
+    assert_eq!(parse_threshold("2-3 l"), None);
+    assert_eq!(parse_threshold("0"), None);
+    assert_eq!(parse_threshold("l"), None);
+    assert_eq!(parse_threshold("2 buckets"), None);
+
+    assert_eq!(threshold_text(&threshold(1.5, "l")), "1½ l");
+    assert_eq!(parse_threshold(&threshold_text(&threshold(1.5, "l"))), Some(threshold(1.5, "l")));
+}
+
+#[test]
+fn running_low_compares_pantry_amounts() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let thresholds = BTreeMap::from([
+        // 2 in the pantry
+        ("tomato".to_string(), threshold(3.0, "")),
+        // 5 in the pantry
+        ("egg".to_string(), threshold(2.0, "")),
+        // Not in the pantry
+        ("potato".to_string(), threshold(1.0, "kg")),
+    ]);
+    let low = running_low(&dm, &thresholds);
+    let names: Vec<&str> = low.iter().map(|l| l.ingredient.as_str()).collect();
+    assert_eq!(names, ["potato", "tomato"]);
+    assert_eq!(low[0].have, None);
+    assert_eq!(low[1].have, Some(2.0));
+
+    let restock = restock_item(&dm, &low[1]);
+    assert_eq!(restock.quantity, Some(1.0));
+    assert!(restock.recipes.is_empty());
+    let restock = restock_item(&dm, &low[0]);
+    assert_eq!((restock.quantity, restock.quantity_type.as_str()), (Some(1.0), "kg"));
+    assert_eq!(restock.category, "vegetable");
+}
+
+#[test]
+fn restocks_survive_regenerating_the_list() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let thresholds = BTreeMap::from([("potato".to_string(), threshold(1.0, "kg"))]);
+    let low = running_low(&dm, &thresholds);
+
+    let mut list = ShoppingList::default();
+    list.add_recipe("Lasagna", None);
+    list.regenerate(&dm);
+    list.add_restock(restock_item(&dm, &low[0]));
+    // Adding it again doesn't add a second line
+    list.add_restock(restock_item(&dm, &low[0]));
+    assert_eq!(list.items.len(), 2);
+
+    list.regenerate(&dm);
+    assert_eq!(list.items.len(), 2);
+    assert_eq!(list.items[0].recipes, vec!["Lasagna".to_string()]);
+    assert!(list.items[1].recipes.is_empty());
+    assert_eq!(list.items[1].quantity, Some(1.0));
+}
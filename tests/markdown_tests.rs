@@ -0,0 +1,142 @@
+// Tests for the Markdown parser behind the recipe instructions view (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::markdown::{inline_markup, parse_blocks, resolve_image, Block};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+fn links(names: &[&str]) -> Vec<(String, String)> {
+    names.iter().map(|n| (n.to_string(), n.to_string())).collect()
+}
+
+#[test]
+fn fixture_numbered_list() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let spaghetti = dm.get_recipe("Spaghetti Aglio e Olio").unwrap();
+    let blocks = parse_blocks(&spaghetti.instructions);
+    assert_eq!(blocks.len(), 1);
+    let Block::List { ordered, start, items } = &blocks[0] else {
+        panic!("expected a list, got {:?}", blocks[0]);
+    };
+    assert!(*ordered);
+    assert_eq!(*start, 1);
+    assert_eq!(items.len(), 6);
+    assert_eq!(items[4], "Season with salt and black pepper to taste.");
+}
+
+#[test]
+fn headings_paragraphs_and_rules() {
+    let blocks = parse_blocks("# Sauce\nFry the onions\nuntil soft.\n\n---\n\n## Pasta ##\nBoil.");
+    assert_eq!(
+        blocks,
+        [
+            Block::Heading { level: 1, text: "Sauce".to_string() },
+            Block::Paragraph("Fry the onions until soft.".to_string()),
+            Block::Rule,
+            Block::Heading { level: 2, text: "Pasta".to_string() },
+            Block::Paragraph("Boil.".to_string()),
+        ]
+    );
+    // No space after the hashes: not a heading
+    assert_eq!(parse_blocks("#1 tip"), [Block::Paragraph("#1 tip".to_string())]);
+}
+
+#[test]
+fn lists_continue_across_wrapped_lines() {
+    let blocks = parse_blocks("- Salt\n- Pepper,\n  freshly ground\n\n3) Mix\n4) Serve");
+    assert_eq!(
+        blocks,
+        [
+            Block::List {
+                ordered: false,
+                start: 1,
+                items: vec!["Salt".to_string(), "Pepper, freshly ground".to_string()],
+            },
+            Block::List {
+                ordered: true,
+                start: 3,
+                items: vec!["Mix".to_string(), "Serve".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn quotes_code_and_images() {
+    let blocks = parse_blocks("> Tip: *warm* plates\n\n```\nlet x = 1;\n```\n![Finished dish](img/lasagna.jpg)");
+    assert_eq!(
+        blocks,
+        [
+            Block::Quote(vec![Block::Paragraph("Tip: *warm* plates".to_string())]),
+            Block::Code("let x = 1;".to_string()),
+            Block::Image { alt: "Finished dish".to_string(), src: "img/lasagna.jpg".to_string() },
+        ]
+    );
+}
+
+#[test]
+fn hard_line_breaks_are_kept() {
+    assert_eq!(parse_blocks("One  \nTwo"), [Block::Paragraph("One\nTwo".to_string())]);
+}
+
+#[test]
+fn emphasis_code_and_links() {
+    assert_eq!(
+        inline_markup("**Hot** pan, *not* smoking", &[]),
+        "<b>Hot</b> pan, <i>not</i> smoking"
+    );
+    assert_eq!(inline_markup("Use `<tt>` & go", &[]), "Use <tt>&lt;tt&gt;</tt> &amp; go");
+    assert_eq!(
+        inline_markup("See [this](https://example.com)", &[]),
+        "See <a href=\"https://example.com\">this</a>"
+    );
+    // Unclosed markers and underscores inside words stay literal
+    assert_eq!(inline_markup("2 * 3 and snake_case", &[]), "2 * 3 and snake_case");
+}
+
+#[test]
+fn overlapping_emphasis_stays_well_nested() {
+    assert_eq!(inline_markup("**a *b** c*", &[]), "<b>a <i>b</i></b><i> c</i>");
+}
+
+#[test]
+fn ingredient_names_become_links() {
+    let mut names = links(&["potato", "oil", "olive oil"]);
+    names.push(("potatoes".to_string(), "potato".to_string()));
+    let markup = inline_markup("Boil the Potatoes in olive oil.", &names);
+    assert!(markup.contains(">Potatoes</a>"));
+    assert!(markup.contains("href=\"ingredient:potato\""));
+    // The longer name wins
+    assert!(markup.contains("href=\"ingredient:olive oil\""));
+    assert!(!markup.contains("href=\"ingredient:oil\""));
+
+    // Only at the start of a word, and not inside code or links
+    let markup = inline_markup("Wrap in foil, then `oil` the [oil pan](x)", &links(&["oil"]));
+    assert!(!markup.contains("ingredient:"));
+}
+
+#[test]
+fn ingredient_links_stop_at_the_listed_forms() {
+    // A name is not stretched over a longer word
+    let markup = inline_markup("Fry the eggplant, then add the egg.", &links(&["egg"]));
+    assert!(markup.contains("eggplant, then"));
+    assert!(markup.contains(">egg</a>."));
+    // A plural without its own entry isn't linked
+    assert!(!inline_markup("Peel the potatoes", &links(&["potato"])).contains("ingredient:"));
+}
+
+#[test]
+fn images_are_found_in_the_img_folder() {
+    let dir = tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("img")).unwrap();
+    std::fs::write(dir.path().join("img/lasagna.jpg"), b"").unwrap();
+
+    let expected = dir.path().join("img/lasagna.jpg");
+    assert_eq!(resolve_image(dir.path(), "img/lasagna.jpg"), Some(expected.clone()));
+    assert_eq!(resolve_image(dir.path(), "lasagna.jpg"), Some(expected));
+    assert_eq!(resolve_image(dir.path(), "missing.jpg"), None);
+    assert_eq!(resolve_image(dir.path(), "https://example.com/a.jpg"), None);
+}
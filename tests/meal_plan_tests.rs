@@ -0,0 +1,89 @@
+// Tests for the weekly meal plan (no GTK required).
+use chrono::NaiveDate;
+use janus_engine::DataManager;
+use pantryman_linux::meal_plan::{week_days, week_start, Meal, MealPlan, MEAL_PLAN_FILE};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn weeks_start_on_monday() {
+    // 2025-07-24 is a Thursday
+    assert_eq!(week_start(date(2025, 7, 24)), date(2025, 7, 21));
+    assert_eq!(week_start(date(2025, 7, 21)), date(2025, 7, 21));
+    assert_eq!(week_start(date(2025, 7, 27)), date(2025, 7, 21));
+    let days = week_days(date(2025, 7, 21));
+    assert_eq!(days.len(), 7);
+    assert_eq!(days[6], date(2025, 7, 27));
+}
+
+#[test]
+fn add_remove_and_query_slots() {
+    let mut plan = MealPlan::default();
+    let day = date(2025, 7, 22);
+    plan.add(day, Meal::Dinner, "Lasagna");
+    plan.add(day, Meal::Dinner, "Lasagna");
+    plan.add(day, Meal::Lunch, "Spaghetti Aglio e Olio");
+    assert_eq!(plan.recipes_for(day, Meal::Dinner), ["Lasagna"]);
+    assert_eq!(plan.recipes_for(day, Meal::Breakfast), Vec::<&str>::new());
+
+    plan.remove(day, Meal::Dinner, "Lasagna");
+    assert!(plan.recipes_for(day, Meal::Dinner).is_empty());
+    assert!(plan.contains_recipe("Spaghetti Aglio e Olio"));
+}
+
+#[test]
+fn week_recipes_only_cover_that_week() {
+    let mut plan = MealPlan::default();
+    plan.add(date(2025, 7, 21), Meal::Dinner, "Lasagna");
+    plan.add(date(2025, 7, 27), Meal::Dinner, "Lasagna");
+    plan.add(date(2025, 7, 28), Meal::Dinner, "Spaghetti Aglio e Olio");
+    assert_eq!(plan.week_recipes(date(2025, 7, 21)), ["Lasagna", "Lasagna"]);
+    assert_eq!(plan.week_recipes(date(2025, 7, 28)), ["Spaghetti Aglio e Olio"]);
+}
+
+#[test]
+fn rename_follows_the_recipe() {
+    let mut plan = MealPlan::default();
+    plan.add(date(2025, 7, 21), Meal::Dinner, "Lasagna");
+    plan.rename_recipe("Lasagna", "Potato Lasagna");
+    assert_eq!(plan.recipes_for(date(2025, 7, 21), Meal::Dinner), ["Potato Lasagna"]);
+}
+
+#[test]
+fn save_and_load_roundtrip() {
+    let dir = tempdir().unwrap();
+    assert_eq!(MealPlan::load(dir.path()).unwrap(), MealPlan::default());
+
+    let mut plan = MealPlan::default();
+    plan.add(date(2025, 7, 21), Meal::Breakfast, "Lasagna");
+    plan.save(dir.path()).unwrap();
+    let text = std::fs::read_to_string(dir.path().join(MEAL_PLAN_FILE)).unwrap();
+    assert!(text.contains("2025-07-21"));
+    assert!(text.contains("meal: breakfast"));
+    assert_eq!(MealPlan::load(dir.path()).unwrap(), plan);
+}
+
+#[test]
+fn missing_for_week_sums_repeated_recipes() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let mut plan = MealPlan::default();
+    let monday = date(2025, 7, 21);
+    assert!(plan.missing_for_week(&dm, monday).is_empty());
+    assert!(!plan.day_cookable(&dm, monday));
+
+    plan.add(monday, Meal::Dinner, "Lasagna");
+    plan.add(date(2025, 7, 23), Meal::Dinner, "Lasagna");
+    let missing = plan.missing_for_week(&dm, monday);
+    let potato = missing.iter().find(|i| i.ingredient == "potato").unwrap();
+    assert_eq!(potato.quantity, Some(4.0));
+    // Lasagna needs potatoes, which the fixture pantry lacks
+    assert!(!plan.day_cookable(&dm, monday));
+}
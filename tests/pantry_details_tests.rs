@@ -0,0 +1,141 @@
+// Tests for best-before dates and storage locations of pantry items (no GTK required).
+use chrono::NaiveDate;
+use janus_engine::DataManager;
+use pantryman_linux::cooking::CookingLog;
+use pantryman_linux::coverage::Coverage;
+use pantryman_linux::pantry_details::{
+    days_left, is_expiring_soon, parse_date, PantryDetails, PANTRY_DETAILS_FILE,
+};
+use pantryman_linux::recipes::{sort_recipes, RecipeSort, SortKeys};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn details_roundtrip() {
+    let dir = tempdir().unwrap();
+    assert_eq!(PantryDetails::load(dir.path()).unwrap(), PantryDetails::default());
+
+    let mut details = PantryDetails::default();
+    details.set_best_before("Crème fraîche", Some(date(2025, 7, 30)));
+    details.save(dir.path()).unwrap();
+
+    let text = std::fs::read_to_string(dir.path().join(PANTRY_DETAILS_FILE)).unwrap();
+    assert!(text.contains("best_before: 2025-07-30"), "{}", text);
+    let loaded = PantryDetails::load(dir.path()).unwrap();
+    assert_eq!(loaded.best_before("crème fraîche"), Some(date(2025, 7, 30)));
+}
+
+#[test]
+fn clearing_and_renaming() {
+    let mut details = PantryDetails::default();
+    details.set_best_before("milk", Some(date(2025, 7, 30)));
+    details.rename("milk", "oat milk");
+    assert_eq!(details.best_before("milk"), None);
+    assert_eq!(details.best_before("oat milk"), Some(date(2025, 7, 30)));
+
+    details.set_best_before("oat milk", None);
+    assert!(details.items.is_empty());
+}
+
+#[test]
+fn expiring_soon_only_counts_pantry_items() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let today = date(2025, 7, 24);
+    let mut details = PantryDetails::default();
+    details.set_best_before("tomato", Some(date(2025, 7, 26)));
+    details.set_best_before("egg", Some(date(2025, 8, 30)));
+    // Not in the pantry
+    details.set_best_before("flour", Some(date(2025, 7, 20)));
+
+    let expiring = details.expiring_soon(&dm, today);
+    assert_eq!(expiring, BTreeSet::from(["tomato".to_string()]));
+
+    assert!(details.prune(&dm));
+    assert_eq!(details.best_before("flour"), None);
+    assert!(!details.prune(&dm));
+}
+
+#[test]
+fn days_left_and_expiry() {
+    let today = date(2025, 7, 24);
+    assert_eq!(days_left(date(2025, 7, 27), today), 3);
+    assert_eq!(days_left(date(2025, 7, 23), today), -1);
+    assert!(is_expiring_soon(3));
+    assert!(is_expiring_soon(-10));
+    assert!(!is_expiring_soon(4));
+}
+
+#[test]
+fn parses_iso_and_danish_dates() {
+    assert_eq!(parse_date("2025-07-30"), Some(date(2025, 7, 30)));
+    assert_eq!(parse_date(" 30.07.2025 "), Some(date(2025, 7, 30)));
+    assert_eq!(parse_date("30-07-2025"), Some(date(2025, 7, 30)));
+    assert_eq!(parse_date("30/07/2025"), Some(date(2025, 7, 30)));
+    assert_eq!(parse_date("next week"), None);
+    assert_eq!(parse_date("2025-02-30"), None);
+}
+
+#[test]
+fn use_it_up_ranks_recipes_by_expiring_ingredients() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let log = CookingLog::default();
+    let modified = HashMap::new();
+    let mut recipes: Vec<_> = dm
+        .get_all_recipes()
+        .iter()
+        .map(|r| (r, Coverage::default()))
+        .collect();
+
+    let expiring = BTreeSet::from(["salt".to_string()]);
+    let keys = SortKeys { log: &log, expiring: &expiring, modified: &modified };
+    sort_recipes(&mut recipes, RecipeSort::UseItUp, &keys);
+    assert_eq!(recipes[0].0.title, "Spaghetti Aglio e Olio");
+
+    let expiring = BTreeSet::from(["tomato".to_string()]);
+    let keys = SortKeys { log: &log, expiring: &expiring, modified: &modified };
+    sort_recipes(&mut recipes, RecipeSort::UseItUp, &keys);
+    assert_eq!(recipes[0].0.title, "Lasagna");
+}
+
+#[test]
+fn locations_roundtrip_with_dates() {
+    let dir = tempdir().unwrap();
+    let mut details = PantryDetails::default();
+    assert_eq!(details.locations_or(&["Fridge", "Freezer"]), ["Fridge", "Freezer"]);
+
+    details.set_locations(vec!["Freezer".to_string(), "Basement shelf".to_string()]);
+    details.set_location("Peas", Some("Freezer".to_string()));
+    details.set_best_before("peas", Some(date(2026, 1, 31)));
+    details.save(dir.path()).unwrap();
+
+    let loaded = PantryDetails::load(dir.path()).unwrap();
+    assert_eq!(loaded.locations_or(&["Fridge"]), ["Freezer", "Basement shelf"]);
+    assert_eq!(loaded.location("peas"), Some("Freezer"));
+    assert_eq!(loaded.best_before("peas"), Some(date(2026, 1, 31)));
+}
+
+#[test]
+fn removing_a_location_clears_its_items() {
+    let mut details = PantryDetails::default();
+    details.set_locations(vec!["Fridge".to_string(), "Freezer".to_string()]);
+    details.set_location("milk", Some("Fridge".to_string()));
+    details.set_location("peas", Some("Freezer".to_string()));
+    details.set_best_before("peas", Some(date(2026, 1, 31)));
+
+    details.set_locations(vec!["Fridge".to_string()]);
+    assert_eq!(details.location("milk"), Some("Fridge"));
+    assert_eq!(details.location("peas"), None);
+    assert_eq!(details.best_before("peas"), Some(date(2026, 1, 31)));
+
+    details.set_location("milk", None);
+    assert_eq!(details.items.len(), 1);
+}
@@ -0,0 +1,82 @@
+// Tests for the Pantry tab's category and tag filters (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::pantry_filter::{
+    all_tags, category_options, matches_tags, set_selected, tag_options, FilterOption,
+};
+use std::path::PathBuf;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+fn count_of(options: &[FilterOption], name: &str) -> usize {
+    options.iter().find(|o| o.name == name).map_or(usize::MAX, |o| o.count)
+}
+
+#[test]
+fn tags_are_collected_and_matched_ignoring_case() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let all = dm.get_all_ingredients();
+    assert_eq!(all_tags(&all), ["grain", "protein", "starch", "vegetable"]);
+
+    let mut starchy = dm.filter_ingredients("", &[], false);
+    starchy.retain(|ing| matches_tags(ing, &strings(&["STARCH"])));
+    let names: Vec<&str> = starchy.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, ["potato"]);
+
+    // No tag selected keeps everything
+    assert!(all.iter().all(|ing| matches_tags(ing, &[])));
+}
+
+#[test]
+fn counts_honour_the_other_filter() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let listed = dm.filter_ingredients("", &[], false);
+    let categories = dm.get_all_ingredient_categories();
+    let tags = all_tags(&dm.get_all_ingredients());
+
+    let options = category_options(&categories, &listed, &[], &[]);
+    assert_eq!(count_of(&options, "vegetable"), 2);
+    assert_eq!(count_of(&options, "dairy"), 3);
+    assert!(options.iter().all(|o| !o.selected));
+
+    let options = category_options(&categories, &listed, &[], &strings(&["starch"]));
+    assert_eq!(count_of(&options, "vegetable"), 1);
+    assert_eq!(count_of(&options, "dairy"), 0);
+
+    let options = tag_options(&tags, &listed, &strings(&["vegetable"]), &strings(&["vegetable"]));
+    assert_eq!(count_of(&options, "vegetable"), 2);
+    assert_eq!(count_of(&options, "starch"), 1);
+    assert_eq!(count_of(&options, "protein"), 0);
+    assert!(options.iter().find(|o| o.name == "vegetable").unwrap().selected);
+}
+
+#[test]
+fn stale_selections_stay_clearable() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let listed = dm.filter_ingredients("", &[], false);
+    let options = category_options(
+        &dm.get_all_ingredient_categories(),
+        &listed,
+        &strings(&["frozen"]),
+        &[],
+    );
+    let frozen = options.iter().find(|o| o.name == "frozen").unwrap();
+    assert_eq!((frozen.count, frozen.selected), (0, true));
+}
+
+#[test]
+fn selecting_and_clearing_values() {
+    let mut values = Vec::new();
+    set_selected(&mut values, "vegetable", true);
+    set_selected(&mut values, "Dairy", true);
+    set_selected(&mut values, "dairy", true);
+    assert_eq!(values, strings(&["dairy", "vegetable"]));
+
+    set_selected(&mut values, "VEGETABLE", false);
+    assert_eq!(values, strings(&["dairy"]));
+}
@@ -0,0 +1,112 @@
+// Tests for pantry CSV/JSON import and export (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::pantry_io::{
+    export_rows, parse_csv, preview, raw_rows_from_csv, raw_rows_from_json, to_csv, to_json,
+    PantryRow, RowStatus,
+};
+use std::path::PathBuf;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+#[test]
+fn csv_quoting_and_delimiters() {
+    let records = parse_csv("name;amount\n\"Salt; coarse\";\"1,5\"\n\n\"say \"\"hi\"\"\";2\n");
+    assert_eq!(
+        records,
+        [
+            vec!["name".to_string(), "amount".to_string()],
+            vec!["Salt; coarse".to_string(), "1,5".to_string()],
+            vec!["say \"hi\"".to_string(), "2".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn export_round_trips() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let rows = export_rows(&dm);
+    assert!(rows.iter().any(|r| r.ingredient == "tomato"));
+
+    let from_csv = preview(&dm, raw_rows_from_csv(&to_csv(&rows)));
+    let from_json = preview(&dm, raw_rows_from_json(&to_json(&rows)).unwrap());
+    assert_eq!(from_csv.len(), rows.len());
+    let amounts = |rows: &[pantryman_linux::pantry_io::ImportRow]| {
+        rows.iter().map(|r| (r.ingredient.clone(), r.quantity, r.unit.clone())).collect::<Vec<_>>()
+    };
+    assert_eq!(amounts(&from_csv), amounts(&from_json));
+
+    // Re-importing the export changes nothing
+    for row in from_csv {
+        assert_eq!(row.status, RowStatus::Unchanged, "{:?}", row);
+    }
+}
+
+#[test]
+fn preview_flags_new_unknown_conflicting_and_invalid_rows() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let tomato = dm.get_pantry_item("tomato").unwrap();
+    assert_eq!(tomato.quantity, Some(2.0));
+
+    let csv = "Ingredient,Quantity,Unit,Category\n\
+               TOMATO,5,,\n\
+               flour,500 g,,\n\
+               dragon fruit,2,,Fruit\n\
+               milk,lots,l,\n\
+               ,1,,\n\
+               flour,1,kg,\n";
+    let rows = preview(&dm, raw_rows_from_csv(csv));
+    let statuses: Vec<&RowStatus> = rows.iter().map(|r| &r.status).collect();
+
+    assert_eq!(rows[0].ingredient, "tomato");
+    assert_eq!(*statuses[0], RowStatus::Conflict { quantity: Some(2.0), unit: String::new() });
+    assert_eq!(*statuses[1], RowStatus::New);
+    assert_eq!((rows[1].quantity, rows[1].unit.as_str()), (Some(500.0), "g"));
+    assert_eq!(*statuses[2], RowStatus::UnknownIngredient);
+    assert_eq!(rows[2].category, "Fruit");
+    assert!(matches!(statuses[3], RowStatus::Invalid(_)));
+    assert!(matches!(statuses[4], RowStatus::Invalid(_)));
+    // The second flour row is a duplicate
+    assert!(matches!(statuses[5], RowStatus::Invalid(_)));
+    assert_eq!(rows[3].line, 5);
+}
+
+#[test]
+fn preview_shows_dates_and_ignored_categories() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    assert_eq!(dm.get_ingredient("tomato").unwrap().category, "vegetable");
+
+    let csv = "ingredient,quantity,unit,last_updated,category\n\
+               tomato,5,,2025-01-02,Fruit\n\
+               potato,1,kg,,Vegetable\n\
+               dragon fruit,2,,,Fruit\n";
+    let rows = preview(&dm, raw_rows_from_csv(csv));
+    assert_eq!(rows[0].last_updated, "2025-01-02");
+    assert_eq!(rows[0].kept_category.as_deref(), Some("vegetable"));
+    // Same category in another case, and a new ingredient, take the row's
+    assert_eq!(rows[1].kept_category, None);
+    assert_eq!(rows[2].kept_category, None);
+}
+
+#[test]
+fn json_accepts_pantry_yaml_shape() {
+    let rows = raw_rows_from_json(
+        r#"{"items": [{"ingredient": "egg", "quantity": 6, "quantity_type": "stk"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(rows[0].ingredient, "egg");
+    assert_eq!(rows[0].quantity, "6");
+    assert_eq!(rows[0].unit, "stk");
+    assert!(raw_rows_from_json("{\"pantry\": 1}").is_err());
+
+    let exported: Vec<PantryRow> = serde_json::from_str(&to_json(&[PantryRow {
+        ingredient: "egg".to_string(),
+        quantity: Some(6.0),
+        unit: "stk".to_string(),
+        last_updated: "2025-07-24".to_string(),
+        category: String::new(),
+    }]))
+    .unwrap();
+    assert_eq!(exported[0].quantity, Some(6.0));
+}
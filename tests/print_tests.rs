@@ -0,0 +1,80 @@
+// Tests for the print and PDF layout of recipes (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::print::{
+    cookbook_blocks, number_contents, paginate, recipe_blocks, write_pdf, PrintBlock,
+};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+#[test]
+fn recipe_layout_is_scaled() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let lasagna = dm.get_recipe("Lasagna").unwrap();
+    let blocks = recipe_blocks(&dm, lasagna, Some(4));
+
+    assert_eq!(blocks[0], PrintBlock::Title("Lasagna".to_string()));
+    assert!(matches!(&blocks[1], PrintBlock::Meta(m) if m.contains("4")));
+    let items: Vec<&str> = blocks
+        .iter()
+        .filter_map(|b| match b {
+            PrintBlock::Item { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(items.len(), 2);
+    assert!(items[0].starts_with("4 kg"), "{}", items[0]);
+    assert!(matches!(blocks.last(), Some(PrintBlock::Paragraph(p)) if p.contains("Bake for 60")));
+}
+
+#[test]
+fn paginate_keeps_headings_with_their_text() {
+    let blocks = vec![
+        PrintBlock::Paragraph("a".to_string()),
+        PrintBlock::Heading("b".to_string()),
+        PrintBlock::Paragraph("c".to_string()),
+        PrintBlock::PageBreak,
+        PrintBlock::Paragraph("d".to_string()),
+    ];
+    // Each block is 40 high; the heading fits on the first page but its
+    // paragraph doesn't, so both move on
+    let pages = paginate(&blocks, 100.0, |_| 40.0);
+    let indices: Vec<Vec<usize>> =
+        pages.iter().map(|p| p.iter().map(|(i, _)| *i).collect()).collect();
+    assert_eq!(indices, [vec![0], vec![1, 2], vec![4]]);
+    assert_eq!(pages[1][0].1, 0.0);
+    assert!(pages[1][1].1 > 40.0);
+}
+
+#[test]
+fn cookbook_contents_point_at_recipe_pages() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let mut recipes: Vec<_> = dm.get_all_recipes().iter().collect();
+    recipes.sort_by(|a, b| a.title.cmp(&b.title));
+    let mut blocks = cookbook_blocks(&dm, "Cookbook", &recipes);
+
+    let pages = paginate(&blocks, 1000.0, |_| 10.0);
+    assert_eq!(pages.len(), recipes.len() + 1);
+    number_contents(&mut blocks, &pages);
+    let numbers: Vec<usize> = blocks
+        .iter()
+        .filter_map(|b| match b {
+            PrintBlock::Contents { page, .. } => Some(*page),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(numbers, (2..recipes.len() + 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn writes_a_pdf() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lasagna.pdf");
+    write_pdf(&path, recipe_blocks(&dm, dm.get_recipe("Lasagna").unwrap(), None)).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    assert!(bytes.starts_with(b"%PDF"));
+}
@@ -0,0 +1,129 @@
+// Tests for the recipe-editor quantity parser (no GTK required).
+use pantryman_linux::quantity::{
+    canonical_unit, convert, display_quantity, format_amount, parse_amount, parse_quantity,
+    parse_quantity_with, round_for_unit, scale_quantity, unit_size, Amount, Dimension,
+    QuantityError,
+};
+
+#[test]
+fn parses_decimal_with_unit() {
+    let p = parse_quantity("2.0 kg").unwrap();
+    assert_eq!(p.quantity.as_deref(), Some("2.0"));
+    assert_eq!(p.unit.as_deref(), Some("kg"));
+    assert_eq!(p.amount, Some(Amount::Single(2.0)));
+}
+
+#[test]
+fn parses_decimal_comma_and_glued_unit() {
+    let p = parse_quantity("2,5dl").unwrap();
+    assert_eq!(p.quantity.as_deref(), Some("2.5"));
+    assert_eq!(p.unit.as_deref(), Some("dl"));
+}
+
+#[test]
+fn parses_fractions() {
+    assert_eq!(parse_amount("1/2"), Some(Amount::Single(0.5)));
+    assert_eq!(parse_amount("1 1/2"), Some(Amount::Single(1.5)));
+    let p = parse_quantity("1½ tsk").unwrap();
+    assert_eq!(p.quantity.as_deref(), Some("1 1/2"));
+    assert_eq!(p.unit.as_deref(), Some("tsk"));
+    assert_eq!(p.amount, Some(Amount::Single(1.5)));
+    assert_eq!(parse_quantity("½").unwrap().quantity.as_deref(), Some("1/2"));
+}
+
+#[test]
+fn parses_ranges() {
+    let p = parse_quantity("2 – 3 stk").unwrap();
+    assert_eq!(p.quantity.as_deref(), Some("2-3"));
+    assert_eq!(p.amount, Some(Amount::Range(2.0, 3.0)));
+    assert_eq!(p.amount.unwrap().value(), 2.5);
+    assert_eq!(parse_amount("3-2"), None);
+}
+
+#[test]
+fn resolves_unit_aliases() {
+    assert_eq!(canonical_unit("Tablespoons"), Some("tbsp"));
+    assert_eq!(canonical_unit("spiseskefuld"), Some("spsk"));
+    assert_eq!(canonical_unit("kg."), Some("kg"));
+    assert_eq!(parse_quantity("3 grams").unwrap().unit.as_deref(), Some("g"));
+}
+
+#[test]
+fn empty_input_is_no_quantity() {
+    let p = parse_quantity("   ").unwrap();
+    assert_eq!(p.quantity, None);
+    assert_eq!(p.unit, None);
+}
+
+#[test]
+fn unit_without_number_is_allowed() {
+    let p = parse_quantity("pinch").unwrap();
+    assert_eq!(p.quantity, None);
+    assert_eq!(p.unit.as_deref(), Some("pinch"));
+}
+
+#[test]
+fn rejects_garbage() {
+    assert!(matches!(parse_quantity("1/0 kg"), Err(QuantityError::InvalidNumber(_))));
+    assert!(matches!(parse_quantity("2 1 3"), Err(QuantityError::InvalidNumber(_))));
+    assert!(matches!(parse_quantity("2 fed"), Err(QuantityError::UnknownUnit(_))));
+}
+
+#[test]
+fn extra_units_are_accepted_verbatim() {
+    let extra = vec!["fed".to_string()];
+    let p = parse_quantity_with("2 Fed", &extra).unwrap();
+    assert_eq!(p.unit.as_deref(), Some("fed"));
+}
+
+#[test]
+fn formats_common_fractions() {
+    assert_eq!(format_amount(0.5), "½");
+    assert_eq!(format_amount(1.5), "1½");
+    assert_eq!(format_amount(2.0), "2");
+    assert_eq!(format_amount(0.75), "¾");
+    assert_eq!(format_amount(1.2), "1.2");
+}
+
+#[test]
+fn rounds_per_unit() {
+    assert_eq!(round_for_unit(1.37, Some("stk")), 1.5);
+    assert_eq!(round_for_unit(1.37, None), 1.5);
+    assert_eq!(round_for_unit(0.66, Some("tsp")), 0.75);
+    assert_eq!(round_for_unit(333.3, Some("g")), 335.0);
+    assert_eq!(round_for_unit(0.333, Some("kg")), 0.33);
+}
+
+#[test]
+fn scales_stored_quantities() {
+    assert_eq!(scale_quantity("1/2", 2.0, Some("tsk")), "1");
+    assert_eq!(scale_quantity("3", 0.5, Some("pcs")), "1½");
+    assert_eq!(scale_quantity("2-3", 2.0, Some("stk")), "4-6");
+    assert_eq!(scale_quantity("250", 1.5, Some("g")), "375");
+    assert_eq!(scale_quantity("a handful", 2.0, None), "a handful");
+}
+
+#[test]
+fn converts_within_a_dimension() {
+    assert_eq!(convert(200.0, "g", "kg"), Some(0.2));
+    assert_eq!(convert(1.5, "l", "dl"), Some(15.0));
+    assert_eq!(convert(2.0, "spsk", "tsk"), Some(6.0));
+    assert_eq!(convert(3.0, "", "stk"), Some(3.0));
+    assert_eq!(convert(2.0, "Dåser", "dåse"), Some(2.0));
+    assert_eq!(convert(1.0, "kg", "l"), None);
+    assert_eq!(convert(1.0, "can", "g"), None);
+    assert_eq!(unit_size("Kilograms"), Some((Dimension::Mass, 1000.0)));
+}
+
+#[test]
+fn display_quantity_keeps_unscaled_metric_as_written() {
+    // Metric is the default preferred system
+    assert_eq!(
+        display_quantity("2.0", Some("kg"), 1.0),
+        ("2.0".to_string(), Some("kg".to_string()))
+    );
+    assert_eq!(
+        display_quantity("1/2", Some("tsk"), 2.0),
+        ("1".to_string(), Some("tsk".to_string()))
+    );
+}
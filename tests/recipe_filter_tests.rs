@@ -0,0 +1,124 @@
+// Tests for the Recipes tab's filter panel (no GTK required).
+use janus_engine::Recipe;
+use pantryman_linux::coverage::Coverage;
+use pantryman_linux::recipe_filter::{
+    all_tags, total_time, Criterion, FilterChange, RecipeFilter,
+};
+
+fn recipe(
+    title: &str,
+    prep: Option<u32>,
+    down: Option<u32>,
+    servings: Option<u32>,
+    tags: &[&str],
+) -> Recipe {
+    Recipe {
+        title: title.to_string(),
+        slug: String::new(),
+        file_stem: String::new(),
+        ingredients: Vec::new(),
+        prep_time: prep,
+        downtime: down,
+        servings,
+        tags: (!tags.is_empty()).then(|| tags.iter().map(|t| t.to_string()).collect()),
+        image: None,
+        instructions: String::new(),
+    }
+}
+
+fn coverage(required_total: usize, covered: usize, partial: usize) -> Coverage {
+    Coverage {
+        required_total,
+        covered,
+        partial,
+    }
+}
+
+fn filter(changes: Vec<FilterChange>) -> RecipeFilter {
+    let mut filter = RecipeFilter::default();
+    for change in changes {
+        filter.apply(change);
+    }
+    filter
+}
+
+#[test]
+fn times_and_servings() {
+    let lasagna = recipe("Lasagna", Some(30), Some(60), Some(4), &["pasta"]);
+    let salad = recipe("Salad", Some(10), None, Some(2), &[]);
+    let toast = recipe("Toast", None, None, None, &[]);
+    assert_eq!(total_time(&lasagna), Some(90));
+    assert_eq!(total_time(&salad), Some(10));
+    assert_eq!(total_time(&toast), None);
+
+    let cov = coverage(0, 0, 0);
+    let quick = filter(vec![FilterChange::MaxTotal(Some(45))]);
+    assert!(!quick.matches(&lasagna, cov));
+    assert!(quick.matches(&salad, cov));
+    // Recipes without times don't pass a time filter
+    assert!(!quick.matches(&toast, cov));
+
+    let prep = filter(vec![FilterChange::MaxPrep(Some(30))]);
+    assert!(prep.matches(&lasagna, cov));
+
+    let family = filter(vec![FilterChange::MinServings(Some(3))]);
+    assert!(family.matches(&lasagna, cov));
+    assert!(!family.matches(&salad, cov));
+    let small = filter(vec![FilterChange::MinServings(Some(1)), FilterChange::MaxServings(Some(2))]);
+    assert!(small.matches(&salad, cov));
+    assert!(!small.matches(&toast, cov));
+}
+
+#[test]
+fn tags_and_coverage() {
+    let lasagna = recipe("Lasagna", None, None, None, &["Pasta", "oven"]);
+    let salad = recipe("Salad", None, None, None, &["vegetarian"]);
+    assert_eq!(all_tags([&lasagna, &salad]), ["oven", "Pasta", "vegetarian"]);
+
+    let tagged = filter(vec![
+        FilterChange::Tag { tag: "pasta".to_string(), selected: true },
+        FilterChange::Tag { tag: "vegetarian".to_string(), selected: true },
+    ]);
+    let cov = coverage(3, 3, 0);
+    assert!(tagged.matches(&lasagna, cov));
+    assert!(tagged.matches(&salad, cov));
+    let untagged = filter(vec![FilterChange::Tag { tag: "pasta".to_string(), selected: false }]);
+    assert!(!untagged.is_active());
+
+    let cookable = filter(vec![FilterChange::CookableOnly(true)]);
+    assert!(cookable.matches(&salad, coverage(3, 3, 0)));
+    assert!(!cookable.matches(&salad, coverage(3, 2, 1)));
+    assert!(cookable.matches(&salad, coverage(0, 0, 0)));
+
+    // Partially covered ingredients count as missing
+    let almost = filter(vec![FilterChange::MaxMissing(Some(1))]);
+    assert!(almost.matches(&salad, coverage(3, 2, 1)));
+    assert!(!almost.matches(&salad, coverage(3, 1, 1)));
+}
+
+#[test]
+fn explains_an_empty_result() {
+    let lasagna = recipe("Lasagna", Some(30), Some(60), Some(4), &["pasta"]);
+    let salad = recipe("Salad", Some(10), None, Some(2), &[]);
+    let recipes = vec![(&lasagna, coverage(2, 1, 0)), (&salad, coverage(1, 1, 0))];
+
+    let f = filter(vec![
+        FilterChange::CookableOnly(true),
+        FilterChange::Tag { tag: "pasta".to_string(), selected: true },
+    ]);
+    assert!(recipes.iter().all(|(r, cov)| !f.matches(r, *cov)));
+    assert_eq!(f.active_count(), 2);
+    assert_eq!(
+        f.explain("las", 1, &recipes),
+        vec![
+            (Criterion::Search("las".to_string()), 1),
+            (Criterion::Tags(vec!["pasta".to_string()]), 1),
+            (Criterion::Cookable, 1),
+        ]
+    );
+
+    let mut cleared = f.clone();
+    cleared.apply(FilterChange::Clear);
+    assert_eq!(cleared, RecipeFilter::default());
+    assert!(cleared.explain("", 2, &recipes).is_empty());
+}
@@ -0,0 +1,138 @@
+// Tests for the recipe dialog's form model and frontmatter preservation (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::dialogs::IngredientInfo;
+use pantryman_linux::frontmatter::{
+    restore_unknown_keys, restore_unknown_keys_in_file, unknown_keys,
+};
+use pantryman_linux::recipe_form::{FormError, IngredientLine, RecipeForm};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+const WITH_EXTRA_KEYS: &str = "---\nTitle: Soup\nIngredients: []\nrating: 5\nsource:\n  url: https://example.com/soup\n---\nHeat it up.\n";
+
+#[test]
+fn every_example_recipe_round_trips_unchanged() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let recipes = dm.get_all_recipes();
+    assert!(!recipes.is_empty());
+    for recipe in recipes {
+        let saved = RecipeForm::from_recipe(recipe).to_recipe(Some(recipe), &[], &[]).unwrap();
+        assert_eq!(format!("{:?}", saved), format!("{:?}", recipe), "{}", recipe.title);
+    }
+}
+
+#[test]
+fn editing_the_title_keeps_everything_else() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let mut lasagna = dm.get_recipe("Lasagna").unwrap().clone();
+    lasagna.image = Some("lasagna.jpg".to_string());
+
+    let mut form = RecipeForm::from_recipe(&lasagna);
+    form.title = "  Potato Lasagna ".to_string();
+    let saved = form.to_recipe(Some(&lasagna), &[], &[]).unwrap();
+
+    assert_eq!(saved.title, "Potato Lasagna");
+    assert_eq!(saved.image.as_deref(), Some("lasagna.jpg"));
+    assert_eq!(saved.slug, lasagna.slug);
+    assert_eq!(saved.file_stem, lasagna.file_stem);
+    assert_eq!(format!("{:?}", saved.ingredients), format!("{:?}", lasagna.ingredients));
+    assert_eq!(saved.instructions, lasagna.instructions);
+}
+
+#[test]
+fn new_recipes_parse_their_rows() {
+    let known = [IngredientInfo {
+        name: "potato".to_string(),
+        forms: vec!["potato".to_string(), "kartoffel".to_string()],
+        in_pantry: false,
+    }];
+    let form = RecipeForm {
+        title: "Mash".to_string(),
+        servings: "4".to_string(),
+        tags: "side, , quick".to_string(),
+        ingredients: vec![
+            IngredientLine {
+                quantity: "1 kg".to_string(),
+                ingredient: "Kartoffel".to_string(),
+                note: " peeled ".to_string(),
+                ..Default::default()
+            },
+            // Rows without an ingredient are skipped
+            IngredientLine { quantity: "2".to_string(), ..Default::default() },
+        ],
+        ..Default::default()
+    };
+    let recipe = form.to_recipe(None, &known, &[]).unwrap();
+    assert_eq!(recipe.servings, Some(4));
+    assert_eq!(recipe.prep_time, None);
+    assert_eq!(recipe.tags, Some(vec!["side".to_string(), "quick".to_string()]));
+    assert_eq!(recipe.ingredients.len(), 1);
+    let potato = &recipe.ingredients[0];
+    assert_eq!(potato.ingredient, "potato");
+    assert_eq!(potato.quantity_type.as_deref(), Some("kg"));
+    assert_eq!(potato.note.as_deref(), Some("peeled"));
+}
+
+#[test]
+fn invalid_input_is_reported() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let lasagna = dm.get_recipe("Lasagna").unwrap();
+
+    let mut form = RecipeForm::from_recipe(lasagna);
+    form.ingredients[1].quantity = "2 fed".to_string();
+    assert_eq!(form.to_recipe(Some(lasagna), &[], &[]).unwrap_err(), FormError::Quantity(1));
+
+    form.title = "   ".to_string();
+    assert_eq!(form.to_recipe(Some(lasagna), &[], &[]).unwrap_err(), FormError::EmptyTitle);
+}
+
+#[test]
+fn unknown_frontmatter_keys_are_found() {
+    let unknown = unknown_keys(WITH_EXTRA_KEYS);
+    assert_eq!(unknown.len(), 2);
+    assert!(unknown.contains_key("rating"));
+    assert!(unknown.contains_key("source"));
+    assert!(unknown_keys("No frontmatter here").is_empty());
+}
+
+#[test]
+fn example_recipes_have_no_unknown_keys() {
+    let dir = fixture_data_dir().join("recipes");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|e| e == "md") {
+            let text = std::fs::read_to_string(&path).unwrap();
+            assert!(unknown_keys(&text).is_empty(), "{}", path.display());
+        }
+    }
+}
+
+#[test]
+fn unknown_keys_are_restored_once() {
+    let unknown = unknown_keys(WITH_EXTRA_KEYS);
+    let rewritten = "---\nTitle: Soup\nIngredients: []\nimage: null\n---\nHeat it up.\n";
+
+    let restored = restore_unknown_keys(rewritten, &unknown).unwrap();
+    assert!(restored.starts_with("---\nTitle: Soup\n"));
+    assert!(restored.ends_with("---\nHeat it up.\n"));
+    assert_eq!(unknown_keys(&restored), unknown);
+
+    // Already there: nothing to do
+    assert_eq!(restore_unknown_keys(&restored, &unknown), None);
+}
+
+#[test]
+fn unknown_keys_are_restored_in_the_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("Soup.md");
+    std::fs::write(&path, "---\nTitle: Soup\n---\nHeat it up.\n").unwrap();
+
+    restore_unknown_keys_in_file(&path, &unknown_keys(WITH_EXTRA_KEYS)).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(unknown_keys(&text), unknown_keys(WITH_EXTRA_KEYS));
+    assert!(text.ends_with("Heat it up.\n"));
+}
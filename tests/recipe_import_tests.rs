@@ -0,0 +1,156 @@
+// Tests for importing schema.org JSON-LD recipes (no GTK required).
+use pantryman_linux::dialogs::IngredientInfo;
+use pantryman_linux::recipe_import::{
+    import_json_ld, parse_duration, parse_ingredient_line, unmatched_names, ImportError,
+};
+use tempfile::tempdir;
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html><head>
+<script type="application/ld+json">{"@context": "https://schema.org", "@type": "WebSite", "name": "Food Blog"}</script>
+<SCRIPT TYPE="application/ld+json">
+{
+  "@context": "https://schema.org",
+  "@graph": [
+    {"@type": "BreadcrumbList", "itemListElement": []},
+    {
+      "@type": ["Recipe", "NewsArticle"],
+      "name": "Potato &amp; Leek Soup",
+      "image": [{"@type": "ImageObject", "url": "https://example.com/img/soup.jpg?w=1200"}],
+      "prepTime": "PT15M",
+      "cookTime": "PT1H",
+      "recipeYield": ["4", "4 bowls"],
+      "keywords": "soup, vegetarian, Soup",
+      "recipeIngredient": [
+        "1 kg potatoes, peeled",
+        "2 leeks (about 400 g)",
+        "Salt, optional"
+      ],
+      "recipeInstructions": [
+        {"@type": "HowToSection", "name": "Soup", "itemListElement": [
+          {"@type": "HowToStep", "text": "Fry the <b>leeks</b>."},
+          {"@type": "HowToStep", "text": "Add the potatoes and simmer for 30 minutes."}
+        ]},
+        {"@type": "HowToSection", "name": "To serve", "itemListElement": [
+          {"@type": "HowToStep", "text": "Blend until smooth."}
+        ]}
+      ]
+    }
+  ]
+}
+</script>
+</head><body></body></html>"#;
+
+fn known() -> Vec<IngredientInfo> {
+    let info = |name: &str, forms: &[&str]| IngredientInfo {
+        name: name.to_string(),
+        forms: forms.iter().map(|f| f.to_string()).collect(),
+        in_pantry: false,
+    };
+    vec![
+        info("potato", &["potato", "potatoes", "kartoffel"]),
+        info("leek", &["leek", "leeks", "porre", "porrer"]),
+        info("rice", &["rice"]),
+        info("egg", &["egg", "eggs"]),
+        info("chicken", &["chicken"]),
+    ]
+}
+
+#[test]
+fn maps_a_saved_page() {
+    let imported = import_json_ld(PAGE, None, &known(), &[]).unwrap();
+    let recipe = imported.recipe;
+    assert_eq!(recipe.title, "Potato & Leek Soup");
+    assert_eq!(recipe.prep_time, Some(15));
+    assert_eq!(recipe.downtime, Some(60));
+    assert_eq!(recipe.servings, Some(4));
+    assert_eq!(recipe.tags, Some(vec!["soup".to_string(), "vegetarian".to_string()]));
+    assert_eq!(recipe.image.as_deref(), Some("https://example.com/img/soup.jpg?w=1200"));
+    assert_eq!(imported.image_file, None);
+    assert_eq!(
+        recipe.instructions,
+        "## Soup\n\n1. Fry the leeks.\n2. Add the potatoes and simmer for 30 minutes.\n\n\
+         ## To serve\n\n1. Blend until smooth."
+    );
+
+    let ings = &recipe.ingredients;
+    assert_eq!(ings.len(), 3);
+    assert_eq!(ings[0].ingredient, "potato");
+    assert_eq!(ings[0].quantity.as_deref(), Some("1"));
+    assert_eq!(ings[0].quantity_type.as_deref(), Some("kg"));
+    assert_eq!(ings[0].note.as_deref(), Some("peeled"));
+    assert_eq!(ings[1].ingredient, "leek");
+    assert_eq!(ings[1].note.as_deref(), Some("about 400 g"));
+    assert_eq!(ings[2].ingredient, "Salt");
+    assert!(ings[2].optional);
+    assert_eq!(ings[2].note, None);
+}
+
+#[test]
+fn accepts_pasted_json_ld() {
+    let json = r#"{"@type": "Recipe", "name": "Toast", "recipeYield": 2,
+        "recipeInstructions": "Toast the bread.\nButter it."}"#;
+    let recipe = import_json_ld(json, None, &[], &[]).unwrap().recipe;
+    assert_eq!(recipe.title, "Toast");
+    assert_eq!(recipe.servings, Some(2));
+    assert_eq!(recipe.instructions, "1. Toast the bread.\n2. Butter it.");
+    assert!(recipe.ingredients.is_empty());
+    assert_eq!(recipe.tags, None);
+}
+
+#[test]
+fn images_saved_with_the_page_are_imported() {
+    let dir = tempdir().unwrap();
+    let page = dir.path().join("Soup.html");
+    std::fs::write(&page, PAGE).unwrap();
+    std::fs::create_dir(dir.path().join("Soup_files")).unwrap();
+    std::fs::write(dir.path().join("Soup_files/soup.jpg"), b"").unwrap();
+
+    let imported = import_json_ld(PAGE, Some(&page), &[], &[]).unwrap();
+    assert_eq!(imported.image_file, Some(dir.path().join("Soup_files/soup.jpg")));
+    assert_eq!(imported.recipe.image, None);
+}
+
+#[test]
+fn reports_pages_without_a_recipe() {
+    assert_eq!(
+        import_json_ld("<html><body>Hello</body></html>", None, &[], &[]).unwrap_err(),
+        ImportError::NoRecipe
+    );
+    assert!(matches!(
+        import_json_ld("{\"@type\": \"Recipe\",", None, &[], &[]),
+        Err(ImportError::InvalidJson(_))
+    ));
+}
+
+#[test]
+fn iso_durations() {
+    assert_eq!(parse_duration("PT1H30M"), Some(90));
+    assert_eq!(parse_duration("P0DT0H20M"), Some(20));
+    assert_eq!(parse_duration("PT90S"), Some(2));
+    assert_eq!(parse_duration("PT0M"), None);
+    assert_eq!(parse_duration("20 minutes"), None);
+}
+
+#[test]
+fn ingredient_lines() {
+    let ing = parse_ingredient_line("1 ½ dl Porrer, in cubes", &known(), &[]);
+    assert_eq!(ing.ingredient, "leek");
+    assert_eq!(ing.quantity.as_deref(), Some("1 1/2"));
+    assert_eq!(ing.quantity_type.as_deref(), Some("dl"));
+    assert_eq!(ing.note.as_deref(), Some("in cubes"));
+
+    // No amount, and nothing known: the text is the ingredient
+    let ing = parse_ingredient_line("Fresh basil", &known(), &[]);
+    assert_eq!(ing.ingredient, "Fresh basil");
+    assert_eq!(ing.quantity, None);
+}
+
+#[test]
+fn ingredient_names_must_match_a_whole_form() {
+    let lines = ["2 tbsp rice vinegar", "1 eggplant", "5 dl chicken stock", "3 eggs"];
+    let ings: Vec<_> = lines.iter().map(|l| parse_ingredient_line(l, &known(), &[])).collect();
+    let names: Vec<&str> = ings.iter().map(|i| i.ingredient.as_str()).collect();
+    assert_eq!(names, ["rice vinegar", "eggplant", "chicken stock", "egg"]);
+    assert_eq!(unmatched_names(&ings, &known()), ["rice vinegar", "eggplant", "chicken stock"]);
+}
@@ -0,0 +1,140 @@
+// Tests for the recipe list's orders and cookability sections (no GTK required).
+use janus_engine::Recipe;
+use pantryman_linux::cooking::CookingLog;
+use pantryman_linux::coverage::Coverage;
+use pantryman_linux::i18n::{self, Language};
+use pantryman_linux::recipe_filter::{FilterChange, RecipeFilter};
+use pantryman_linux::recipes::{modified_time, sort_recipes, Cookability, RecipeSort, SortKeys};
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, SystemTime};
+use tempfile::tempdir;
+
+fn recipe(title: &str, prep: Option<u32>, down: Option<u32>) -> Recipe {
+    Recipe {
+        title: title.to_string(),
+        slug: String::new(),
+        file_stem: title.to_string(),
+        ingredients: Vec::new(),
+        prep_time: prep,
+        downtime: down,
+        servings: None,
+        tags: None,
+        image: None,
+        instructions: String::new(),
+    }
+}
+
+fn coverage(required_total: usize, covered: usize, partial: usize) -> Coverage {
+    Coverage {
+        required_total,
+        covered,
+        partial,
+    }
+}
+
+fn sorted(recipes: &[(&Recipe, Coverage)], sort: RecipeSort, keys: &SortKeys) -> Vec<String> {
+    let mut recipes = recipes.to_vec();
+    sort_recipes(&mut recipes, sort, keys);
+    recipes.iter().map(|(r, _)| r.title.clone()).collect()
+}
+
+#[test]
+fn coverage_missing_and_time_orders() {
+    let lasagna = recipe("Lasagna", Some(30), Some(60));
+    let salad = recipe("Salad", Some(10), None);
+    let stew = recipe("Stew", None, None);
+    let toast = recipe("Toast", Some(5), None);
+    let recipes = vec![
+        (&lasagna, coverage(4, 2, 1)),
+        (&salad, coverage(2, 2, 0)),
+        (&stew, coverage(5, 4, 0)),
+        (&toast, coverage(4, 2, 0)),
+    ];
+    let (log, expiring, modified) = (CookingLog::default(), BTreeSet::new(), HashMap::new());
+    let keys = SortKeys { log: &log, expiring: &expiring, modified: &modified };
+
+    // Partially covered ingredients break ties in coverage
+    assert_eq!(
+        sorted(&recipes, RecipeSort::BestCoverage, &keys),
+        ["Salad", "Stew", "Lasagna", "Toast"]
+    );
+    assert_eq!(
+        sorted(&recipes, RecipeSort::FewestMissing, &keys),
+        ["Salad", "Stew", "Lasagna", "Toast"]
+    );
+    // Recipes without times come last
+    assert_eq!(
+        sorted(&recipes, RecipeSort::ShortestTime, &keys),
+        ["Toast", "Salad", "Lasagna", "Stew"]
+    );
+    assert_eq!(
+        sorted(&recipes, RecipeSort::Name, &keys),
+        ["Lasagna", "Salad", "Stew", "Toast"]
+    );
+}
+
+#[test]
+fn recently_modified_first() {
+    let data = tempdir().unwrap();
+    let dir = data.path().join("recipes");
+    std::fs::create_dir_all(&dir).unwrap();
+    let old = recipe("Old", None, None);
+    let new = recipe("New", None, None);
+    let gone = recipe("Gone", None, None);
+    for r in [&old, &new] {
+        std::fs::write(dir.join(format!("{}.md", r.file_stem)), "").unwrap();
+    }
+    let file = std::fs::File::options().write(true).open(dir.join("Old.md")).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(3600)).unwrap();
+
+    assert!(modified_time(data.path(), &gone).is_none());
+    let modified: HashMap<String, SystemTime> = [&old, &new, &gone]
+        .iter()
+        .filter_map(|r| Some((r.title.clone(), modified_time(data.path(), r)?)))
+        .collect();
+    let (log, expiring) = (CookingLog::default(), BTreeSet::new());
+    let keys = SortKeys { log: &log, expiring: &expiring, modified: &modified };
+
+    let cov = Coverage::default();
+    let recipes = vec![(&gone, cov), (&old, cov), (&new, cov)];
+    assert_eq!(sorted(&recipes, RecipeSort::RecentlyModified, &keys), ["New", "Old", "Gone"]);
+}
+
+#[test]
+fn cookability_sections() {
+    assert_eq!(Cookability::of(coverage(3, 3, 0)), Cookability::Now);
+    assert_eq!(Cookability::of(coverage(0, 0, 0)), Cookability::Now);
+    assert_eq!(Cookability::of(coverage(3, 2, 1)), Cookability::Almost);
+    assert_eq!(Cookability::of(coverage(4, 2, 0)), Cookability::Almost);
+    assert_eq!(Cookability::of(coverage(4, 1, 0)), Cookability::NeedsShopping);
+    assert!(Cookability::Now < Cookability::Almost);
+    assert!(Cookability::Almost < Cookability::NeedsShopping);
+}
+
+#[test]
+fn partially_covered_ingredients_count_as_missing_everywhere() {
+    let stew = recipe("Stew", None, None);
+    let soup = recipe("Soup", None, None);
+    // One ingredient missing, one short
+    let short = coverage(3, 1, 1);
+    assert_eq!(short.not_covered(), 2);
+    i18n::set_language(Language::English);
+    assert_eq!(
+        i18n::fmt_missing_required(short.not_covered(), short.partial),
+        "⚠ Missing 2 required ingredient(s), 1 of them partly in stock"
+    );
+
+    let mut filter = RecipeFilter::default();
+    filter.apply(FilterChange::MaxMissing(Some(1)));
+    assert!(!filter.matches(&stew, short));
+    filter.apply(FilterChange::MaxMissing(Some(2)));
+    assert!(filter.matches(&stew, short));
+
+    let recipes = vec![(&stew, short), (&soup, coverage(3, 2, 0))];
+    let (log, expiring, modified) = (CookingLog::default(), BTreeSet::new(), HashMap::new());
+    let keys = SortKeys { log: &log, expiring: &expiring, modified: &modified };
+    assert_eq!(sorted(&recipes, RecipeSort::FewestMissing, &keys), ["Soup", "Stew"]);
+
+    // Everything in stock but short isn't cookable now
+    assert_eq!(Cookability::of(coverage(2, 0, 2)), Cookability::Almost);
+}
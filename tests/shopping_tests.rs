@@ -0,0 +1,128 @@
+// Tests for the shopping basket and generated shopping list (no GTK required).
+use janus_engine::DataManager;
+use pantryman_linux::shopping_list::{ShoppingItem, ShoppingList, SHOPPING_FILE};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn fixture_data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example/data")
+}
+
+fn item(name: &str, category: &str, checked: bool) -> ShoppingItem {
+    ShoppingItem {
+        ingredient: name.to_string(),
+        quantity: None,
+        quantity_type: String::new(),
+        category: category.to_string(),
+        recipes: Vec::new(),
+        checked,
+    }
+}
+
+#[test]
+fn missing_file_loads_empty_list() {
+    let dir = tempdir().unwrap();
+    let list = ShoppingList::load(dir.path()).unwrap();
+    assert_eq!(list, ShoppingList::default());
+}
+
+#[test]
+fn save_and_load_roundtrip() {
+    let dir = tempdir().unwrap();
+    let mut list = ShoppingList::default();
+    list.add_recipe("Lasagna", Some(4));
+    list.items.push(item("potato", "vegetable", true));
+    list.save(dir.path()).unwrap();
+    assert!(dir.path().join(SHOPPING_FILE).exists());
+    assert_eq!(ShoppingList::load(dir.path()).unwrap(), list);
+}
+
+#[test]
+fn invalid_file_is_an_error() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join(SHOPPING_FILE), "basket: [").unwrap();
+    assert!(ShoppingList::load(dir.path()).is_err());
+}
+
+#[test]
+fn basket_add_update_remove_and_rename() {
+    let mut list = ShoppingList::default();
+    list.add_recipe("Lasagna", None);
+    list.add_recipe("Lasagna", Some(6));
+    assert_eq!(list.basket.len(), 1);
+    assert_eq!(list.basket[0].servings, Some(6));
+
+    list.items.push(ShoppingItem { recipes: vec!["Lasagna".into()], ..item("potato", "", false) });
+    list.rename_recipe("Lasagna", "Potato Lasagna");
+    assert!(list.contains("Potato Lasagna"));
+    assert!(!list.contains("Lasagna"));
+    assert_eq!(list.items[0].recipes, vec!["Potato Lasagna".to_string()]);
+
+    list.remove_recipe("Potato Lasagna");
+    assert!(list.basket.is_empty());
+}
+
+#[test]
+fn grouped_sorts_categories_with_uncategorised_last() {
+    let mut list = ShoppingList::default();
+    list.items = vec![
+        item("salt", "", false),
+        item("tomato", "vegetable", false),
+        item("butter", "dairy", false),
+        item("potato", "vegetable", false),
+    ];
+    let groups = list.grouped();
+    let names: Vec<&str> = groups.iter().map(|(c, _)| c.as_str()).collect();
+    assert_eq!(names, ["dairy", "vegetable", ""]);
+    assert_eq!(groups[1].1, vec![3, 1]);
+}
+
+#[test]
+fn take_checked_removes_only_checked_items() {
+    let mut list = ShoppingList::default();
+    list.items = vec![item("salt", "", true), item("potato", "", false)];
+    let taken = list.take_checked();
+    assert_eq!(taken.len(), 1);
+    assert_eq!(taken[0].ingredient, "salt");
+    assert_eq!(list.items.len(), 1);
+    assert_eq!(list.items[0].ingredient, "potato");
+}
+
+#[test]
+fn regenerate_lists_what_the_pantry_lacks() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let mut list = ShoppingList::default();
+    list.add_recipe("Lasagna", None);
+    list.regenerate(&dm);
+
+    // Tomatoes are in the pantry; potatoes are not
+    assert_eq!(list.items.len(), 1);
+    let potato = &list.items[0];
+    assert_eq!(potato.ingredient, "potato");
+    assert_eq!(potato.quantity, Some(2.0));
+    assert_eq!(potato.quantity_type, "kg");
+    assert_eq!(potato.category, "vegetable");
+    assert_eq!(potato.recipes, vec!["Lasagna".to_string()]);
+}
+
+#[test]
+fn regenerate_scales_and_keeps_check_marks() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let mut list = ShoppingList::default();
+    list.add_recipe("Lasagna", Some(4));
+    list.regenerate(&dm);
+    assert_eq!(list.items[0].quantity, Some(4.0));
+
+    list.items[0].checked = true;
+    list.regenerate(&dm);
+    assert!(list.items[0].checked);
+}
+
+#[test]
+fn regenerate_skips_unknown_recipes() {
+    let dm = DataManager::new(fixture_data_dir(), "test-device").unwrap();
+    let mut list = ShoppingList::default();
+    list.add_recipe("No Such Recipe", None);
+    list.regenerate(&dm);
+    assert!(list.items.is_empty());
+}
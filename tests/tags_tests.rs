@@ -0,0 +1,20 @@
+// Tests for the tag matching shared by the recipe and pantry filters (no GTK required).
+use pantryman_linux::tags::{all_tags, has_tag};
+
+fn tags(values: &[&str]) -> Option<Vec<String>> {
+    Some(values.iter().map(|v| v.to_string()).collect())
+}
+
+#[test]
+fn non_ascii_case_variants_are_one_tag() {
+    let lists = [
+        tags(&["Æg", "Økologisk"]),
+        tags(&["æg", "økologisk", "dessert"]),
+        None,
+    ];
+    assert_eq!(all_tags(&lists), ["dessert", "Æg", "Økologisk"]);
+
+    assert!(has_tag(&lists[1], "ÆG"));
+    assert!(has_tag(&lists[0], "økologisk"));
+    assert!(!has_tag(&lists[2], "æg"));
+}
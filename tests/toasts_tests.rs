@@ -0,0 +1,49 @@
+// Tests for toast requests and error-chain formatting (no GTK required).
+use pantryman_linux::toasts::{error_chain, ToastKind, ToastRequest};
+use std::fmt;
+
+#[derive(Debug)]
+struct Outer(Inner);
+#[derive(Debug)]
+struct Inner;
+
+impl fmt::Display for Outer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to save recipe")
+    }
+}
+impl fmt::Display for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "permission denied")
+    }
+}
+impl std::error::Error for Outer {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+impl std::error::Error for Inner {}
+
+#[test]
+fn error_chain_includes_every_source() {
+    let chain = error_chain(&Outer(Inner));
+    assert_eq!(chain, "failed to save recipe\n  caused by: permission denied");
+}
+
+#[test]
+fn error_toasts_are_high_priority_and_stay_longer() {
+    let info = ToastRequest::info("Saved");
+    let err = ToastRequest::error("Could not save", "disk full");
+    assert!(!info.is_high_priority());
+    assert!(err.is_high_priority());
+    assert!(err.timeout() > info.timeout());
+    assert_eq!(
+        err.kind,
+        ToastKind::Error { details: "disk full".to_string() }
+    );
+}
+
+#[test]
+fn undo_toast_outlasts_info_toast() {
+    assert!(ToastRequest::undo("Deleted", 0).timeout() > ToastRequest::info("x").timeout());
+}
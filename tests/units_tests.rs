@@ -0,0 +1,80 @@
+// Tests for the unit registry and conversions (no GTK required).
+use pantryman_linux::config::MeasurementSystem;
+use pantryman_linux::i18n::{self, Language};
+use pantryman_linux::units::{
+    canonical_unit, convert, convert_with, load_ingredient_units, unit_in_system, unit_size,
+    unit_system, Dimension, IngredientUnits,
+};
+use tempfile::tempdir;
+
+#[test]
+fn suggested_units_are_all_registered() {
+    for lang in [Language::English, Language::Danish] {
+        i18n::set_language(lang);
+        for unit in i18n::suggested_units() {
+            assert_eq!(canonical_unit(unit), Some(*unit), "{} not registered", unit);
+        }
+    }
+    i18n::set_language(Language::English);
+}
+
+#[test]
+fn resolves_imperial_aliases() {
+    assert_eq!(canonical_unit("Pounds"), Some("lb"));
+    assert_eq!(canonical_unit("fl oz"), Some("fl oz"));
+    assert_eq!(canonical_unit("cups"), Some("cup"));
+    assert_eq!(unit_system("oz"), Some(MeasurementSystem::Imperial));
+    assert_eq!(unit_system("dl"), Some(MeasurementSystem::Metric));
+    assert_eq!(unit_system("tsp"), None);
+}
+
+#[test]
+fn converts_within_a_dimension() {
+    assert_eq!(convert(200.0, "g", "kg"), Some(0.2));
+    assert_eq!(convert(1.5, "l", "dl"), Some(15.0));
+    assert_eq!(convert(2.0, "spsk", "tsk"), Some(6.0));
+    assert_eq!(convert(3.0, "", "stk"), Some(3.0));
+    assert_eq!(convert(2.0, "Dåser", "dåse"), Some(2.0));
+    assert_eq!(convert(16.0, "oz", "lb"), Some(1.0));
+    assert_eq!(convert(1.0, "kg", "l"), None);
+    assert_eq!(convert(1.0, "can", "g"), None);
+    assert_eq!(unit_size("Kilograms"), Some((Dimension::Mass, 1000.0)));
+}
+
+#[test]
+fn converts_across_dimensions_with_ingredient_data() {
+    let milk = IngredientUnits { density: Some(1.03), piece_weight: None };
+    let grams = convert_with(1.0, "l", "g", milk).unwrap();
+    assert!((grams - 1030.0).abs() < 1e-9);
+    let egg = IngredientUnits { density: None, piece_weight: Some(60.0) };
+    assert_eq!(convert_with(120.0, "g", "stk", egg), Some(2.0));
+    assert_eq!(convert_with(1.0, "l", "g", IngredientUnits::default()), None);
+}
+
+#[test]
+fn picks_a_readable_unit_in_the_other_system() {
+    assert_eq!(unit_in_system(500.0, "g", MeasurementSystem::Imperial), Some("lb"));
+    assert_eq!(unit_in_system(100.0, "g", MeasurementSystem::Imperial), Some("oz"));
+    assert_eq!(unit_in_system(2.0, "dl", MeasurementSystem::Imperial), Some("cup"));
+    assert_eq!(unit_in_system(2.0, "lb", MeasurementSystem::Metric), Some("g"));
+    assert_eq!(unit_in_system(1.0, "gallon", MeasurementSystem::Metric), Some("l"));
+    // Already in the preferred system, or system-neutral: shown as written
+    assert_eq!(unit_in_system(2.0, "kg", MeasurementSystem::Metric), None);
+    assert_eq!(unit_in_system(2.0, "tbsp", MeasurementSystem::Imperial), None);
+    assert_eq!(unit_in_system(2.0, "stk", MeasurementSystem::Imperial), None);
+}
+
+#[test]
+fn loads_density_and_piece_weight_from_ingredient_files() {
+    let tmp = tempdir().unwrap();
+    let dir = tmp.path().join("ingredients");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("flour.yaml"), "name: Flour\ncategory: ''\ndensity: 0.55\n").unwrap();
+    std::fs::write(dir.join("egg.yaml"), "name: egg\npiece_weight: 60\ntags: []\n").unwrap();
+    std::fs::write(dir.join("salt.yaml"), "name: salt\n").unwrap();
+
+    let units = load_ingredient_units(tmp.path());
+    assert_eq!(units.len(), 2);
+    assert_eq!(units["flour"].density, Some(0.55));
+    assert_eq!(units["egg"].piece_weight, Some(60.0));
+}
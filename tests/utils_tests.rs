@@ -24,3 +24,19 @@ fn validate_and_create_data_dir_is_idempotent() {
 
     assert!(Path::new(&dir.join("pantry.yaml")).exists());
 }
+
+#[test]
+fn write_atomically_replaces_the_file_without_leftovers() {
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("shopping.yaml");
+    std::fs::write(&path, "old").unwrap();
+
+    pantryman_linux::utils::write_atomically(&path, "new").unwrap();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+    let names: Vec<_> = std::fs::read_dir(tmp.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(names, ["shopping.yaml"]);
+}
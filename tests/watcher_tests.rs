@@ -0,0 +1,106 @@
+// Tests for the data directory watcher's path classification (no GTK required).
+use pantryman_linux::watcher::{classify_path, summarize, ChangeKind};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn classifies_engine_files() {
+    let dir = Path::new("/data");
+    assert_eq!(classify_path(dir, Path::new("/data/pantry.yaml")), Some(ChangeKind::Pantry));
+    assert_eq!(
+        classify_path(dir, Path::new("/data/pantry.kde-abc12345.yaml")),
+        Some(ChangeKind::Pantry)
+    );
+    assert_eq!(
+        classify_path(dir, Path::new("/data/recipes/Lasagna.md")),
+        Some(ChangeKind::Recipe)
+    );
+    assert_eq!(
+        classify_path(dir, Path::new("/data/ingredients/egg.yaml")),
+        Some(ChangeKind::Ingredient)
+    );
+}
+
+#[test]
+fn ignores_temp_and_unrelated_files() {
+    let dir = Path::new("/data");
+    assert_eq!(classify_path(dir, Path::new("/data/.pantry.yaml.swp")), None);
+    assert_eq!(classify_path(dir, Path::new("/data/recipes/Lasagna.md~")), None);
+    assert_eq!(classify_path(dir, Path::new("/data/recipes/Lasagna.md.part")), None);
+    assert_eq!(classify_path(dir, Path::new("/data/recipes/img/lasagna.jpg")), None);
+    assert_eq!(classify_path(dir, Path::new("/data/notes.txt")), None);
+}
+
+#[test]
+fn summary_counts_each_kind() {
+    let dir = Path::new("/data");
+    let paths: BTreeSet<PathBuf> = [
+        "/data/pantry.yaml",
+        "/data/recipes/Lasagna.md",
+        "/data/recipes/Spaghetti_Aglio_e_Olio.md",
+        "/data/ingredients/egg.yaml",
+        "/data/.sync-temp",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect();
+    let summary = summarize(dir, &paths);
+    assert!(summary.pantry);
+    assert_eq!(summary.recipes, 2);
+    assert_eq!(summary.ingredients, 1);
+    assert!(!summary.is_empty());
+}
+
+#[test]
+fn shopping_list_changes_are_reported_separately() {
+    let dir = Path::new("/data");
+    assert_eq!(classify_path(dir, Path::new("/data/shopping.yaml")), Some(ChangeKind::Shopping));
+    let paths: BTreeSet<PathBuf> = [PathBuf::from("/data/shopping.yaml")].into_iter().collect();
+    let summary = summarize(dir, &paths);
+    assert!(summary.shopping);
+    assert!(summary.only_app_files());
+    assert!(!summary.is_empty());
+}
+
+#[test]
+fn cooking_log_changes_do_not_need_an_engine_reload() {
+    let dir = Path::new("/data");
+    assert_eq!(
+        classify_path(dir, Path::new("/data/cooking_log.yaml")),
+        Some(ChangeKind::CookingLog)
+    );
+    let paths: BTreeSet<PathBuf> = [
+        PathBuf::from("/data/cooking_log.yaml"),
+        PathBuf::from("/data/recipes/Lasagna.md"),
+    ]
+    .into_iter()
+    .collect();
+    let summary = summarize(dir, &paths);
+    assert!(summary.cooking_log);
+    assert!(!summary.only_app_files());
+}
+
+#[test]
+fn meal_plan_changes_are_app_files() {
+    let dir = Path::new("/data");
+    assert_eq!(classify_path(dir, Path::new("/data/meal_plan.yaml")), Some(ChangeKind::MealPlan));
+    let paths: BTreeSet<PathBuf> = [PathBuf::from("/data/meal_plan.yaml")].into_iter().collect();
+    let summary = summarize(dir, &paths);
+    assert!(summary.meal_plan);
+    assert!(summary.only_app_files());
+}
+
+#[test]
+fn pantry_details_are_app_files() {
+    let dir = Path::new("/data");
+    assert_eq!(
+        classify_path(dir, Path::new("/data/pantry_details.yaml")),
+        Some(ChangeKind::PantryDetails)
+    );
+    let paths: BTreeSet<PathBuf> =
+        [PathBuf::from("/data/pantry_details.yaml")].into_iter().collect();
+    let summary = summarize(dir, &paths);
+    assert!(summary.pantry_details);
+    assert!(!summary.pantry);
+    assert!(summary.only_app_files());
+}